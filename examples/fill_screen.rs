@@ -11,5 +11,5 @@ fn main() {
     let dc = Pin::new(25);
     let mosi = Pin::new(23);
     let mut display: ST7734<Spidev, Pin, Delay> = ST7734::new_with_gpio(None, clk, dc, mosi, delay);
-    display.fill_screen(&Color::from_default(DefaultColor::Blue));
+    display.fill_screen(&Color::from_default(DefaultColor::Blue)).unwrap();
 }
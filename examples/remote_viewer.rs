@@ -0,0 +1,44 @@
+extern crate st7735;
+use minifb::{Window, WindowOptions};
+use std::env;
+use std::io::Read;
+use std::net::TcpStream;
+
+/// Shows the frames sent by a `RemoteServer` in a window, scaled up 4 times.
+fn main() {
+    let address = env::args().nth(1).unwrap_or_else(|| "raspberrypi.local:7735".to_string());
+    let mut stream = TcpStream::connect(address).expect("error connecting to server");
+    let mut window: Option<Window> = None;
+    let mut buffer = Vec::new();
+
+    loop {
+        let mut header = [0; 8];
+        stream.read_exact(&mut header).expect("connection closed");
+        assert_eq!(&header[..4], st7735::remote::FRAME_MAGIC, "invalid frame");
+        let width = u16::from_be_bytes([header[4], header[5]]) as usize;
+        let height = u16::from_be_bytes([header[6], header[7]]) as usize;
+
+        let mut pixels = vec![0; 2 * width * height];
+        stream.read_exact(&mut pixels).expect("connection closed");
+        buffer.clear();
+        buffer.extend(pixels.chunks(2).map(|pixel| {
+            let rgb565 = u16::from_be_bytes([pixel[0], pixel[1]]) as u32;
+            let red = (rgb565 >> 11) * 255 / 31;
+            let green = ((rgb565 >> 5) & 0x3F) * 255 / 63;
+            let blue = (rgb565 & 0x1F) * 255 / 31;
+            (red << 16) | (green << 8) | blue
+        }));
+
+        let window = window.get_or_insert_with(|| {
+            let options = WindowOptions {
+                scale: minifb::Scale::X4,
+                ..WindowOptions::default()
+            };
+            Window::new("st7735", width, height, options).expect("error opening window")
+        });
+        if !window.is_open() {
+            break;
+        }
+        window.update_with_buffer(&buffer, width, height).expect("error updating window");
+    }
+}
@@ -0,0 +1,42 @@
+extern crate st7735;
+use embedded_graphics::mono_font::{ascii::FONT_6X10, MonoTextStyle};
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_text::alignment::HorizontalAlignment;
+use embedded_text::style::TextBoxStyleBuilder;
+use embedded_text::TextBox;
+use st7735::ST7734;
+use linux_embedded_hal::spidev::{SpiModeFlags, SpidevOptions};
+use linux_embedded_hal::Spidev;
+use linux_embedded_hal::Delay;
+use linux_embedded_hal::Pin;
+
+fn main() {
+    let mut spi = Spidev::open("/dev/spidev0.0").expect("error initializing SPI");
+    let options = SpidevOptions::new()
+        .bits_per_word(8)
+        .max_speed_hz(20_000)
+        .mode(SpiModeFlags::SPI_MODE_0)
+        .build();
+    spi.configure(&options).expect("error configuring SPI");
+
+    let mut display = ST7734::new_with_spi(spi, Pin::new(25), Delay);
+    display.clear_screen().unwrap();
+
+    let character_style = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+    let textbox_style = TextBoxStyleBuilder::new()
+        .alignment(HorizontalAlignment::Justified)
+        .build();
+    let bounds = Rectangle::new(Point::new(4, 4), Size::new(120, 152));
+
+    TextBox::with_textbox_style(
+        "embedded-text wraps long lines at word boundaries, aligns them and cuts off \
+         text that does not fit into the box.",
+        bounds,
+        character_style,
+        textbox_style,
+    )
+    .draw(&mut display)
+    .unwrap();
+}
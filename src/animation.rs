@@ -0,0 +1,141 @@
+//! Playback of animated GIFs.
+//!
+//! This module is available with the `std` feature. Frames are decoded one at a time, composed
+//! onto a canvas according to their disposal method and converted to RGB565 while they are
+//! streamed to the display, so only a single canvas is kept in memory.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let file = File::open("animation.gif").expect("error opening GIF");
+//! st7735::animation::play_gif(&mut display, 0, 0, file).expect("error playing GIF");
+//! ```
+
+use crate::color::{Color, DefaultColor};
+use crate::geometry::Rect;
+use crate::controller::Controller;
+use crate::error::Error;
+use crate::ST7734;
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+use gif::{ColorOutput, Decoder, DecodingError, DisposalMethod, SetParameter};
+use std::fmt;
+use std::vec;
+use std::io::Read;
+
+/// Error while playing an animation.
+#[derive(Debug)]
+pub enum GifError<SpiE, PinE> {
+    /// The GIF could not be decoded.
+    Decoding(DecodingError),
+
+    /// A frame could not be sent to the display.
+    Display(Error<SpiE, PinE>),
+}
+
+impl<SpiE, PinE> From<DecodingError> for GifError<SpiE, PinE> {
+    fn from(error: DecodingError) -> Self {
+        GifError::Decoding(error)
+    }
+}
+
+impl<SpiE, PinE> From<Error<SpiE, PinE>> for GifError<SpiE, PinE> {
+    fn from(error: Error<SpiE, PinE>) -> Self {
+        GifError::Display(error)
+    }
+}
+
+impl<SpiE, PinE> fmt::Display for GifError<SpiE, PinE>
+where
+    SpiE: fmt::Debug,
+    PinE: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GifError::Decoding(ref error) => write!(f, "error decoding GIF: {}", error),
+            GifError::Display(ref error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl<SpiE, PinE> std::error::Error for GifError<SpiE, PinE>
+where
+    SpiE: fmt::Debug,
+    PinE: fmt::Debug,
+{
+}
+
+/// Plays an animated GIF once with its top left corner at the given coordinates, waiting for
+/// the delay of each frame using the delay provider of the driver.
+pub fn play_gif<R, SPI, PIN, DELAY, C>(
+    display: &mut ST7734<SPI, PIN, DELAY, C>,
+    x: u16,
+    y: u16,
+    input: R,
+) -> Result<(), GifError<SPI::Error, PIN::Error>>
+where
+    R: Read,
+    SPI: spi::Write<u8>,
+    PIN: OutputPin,
+    DELAY: DelayMs<u64>,
+    C: Controller,
+{
+    let mut decoder = Decoder::new(input);
+    decoder.set(ColorOutput::RGBA);
+    let mut reader = decoder.read_info()?;
+
+    let width = reader.width();
+    let height = reader.height();
+    let background = Color::from_default(DefaultColor::Black);
+    let mut canvas = vec![background; width as usize * height as usize];
+
+    while let Some(frame) = reader.read_next_frame()? {
+        let previous = match frame.dispose {
+            DisposalMethod::Previous => Some(canvas.clone()),
+            _ => None,
+        };
+
+        for (i, pixel) in frame.buffer.chunks(4).enumerate() {
+            let px = frame.left as usize + i % frame.width as usize;
+            let py = frame.top as usize + i / frame.width as usize;
+            if pixel[3] != 0 && px < width as usize && py < height as usize {
+                canvas[py * width as usize + px] = Color::from_rgb888(pixel[0], pixel[1], pixel[2]);
+            }
+        }
+
+        let right = (frame.left + frame.width).min(width);
+        let bottom = (frame.top + frame.height).min(height);
+        let visible = frame.left < right && frame.top < bottom;
+        if visible {
+            let area = Rect::new(x + frame.left, y + frame.top, x + right - 1, y + bottom - 1);
+            display.fill_region(&area, |px, py| {
+                canvas[(py - y) as usize * width as usize + (px - x) as usize]
+            })?;
+        }
+
+        // Frame delays are specified in hundredths of a second.
+        display.delay.delay_ms(frame.delay as u64 * 10);
+
+        match frame.dispose {
+            DisposalMethod::Background if visible => {
+                for py in frame.top..bottom {
+                    let row = py as usize * width as usize;
+                    for pixel in &mut canvas[row + frame.left as usize..row + right as usize] {
+                        *pixel = background;
+                    }
+                }
+            }
+            DisposalMethod::Previous => {
+                if let Some(previous) = previous {
+                    canvas = previous;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
@@ -0,0 +1,359 @@
+//! Interpretation of ANSI escape sequences in text output.
+//!
+//! Logging output often contains escape sequences for colors, which would otherwise be drawn as
+//! garbage characters. `Parser` turns a stream of characters into printable characters and
+//! actions for a supported subset of the sequences:
+//!
+//! * SGR colors: reset (`0`), the 8 standard and 8 bright foreground and background colors,
+//!   default colors (`39`, `49`), 256 color palette (`38;5;n`, `48;5;n`) and RGB (`38;2;r;g;b`)
+//! * cursor positioning: `H`, `f` and relative moves with `A`, `B`, `C` and `D`
+//! * erasing: `K` for the line and `J` for the screen
+//!
+//! Other sequences are consumed without effect.
+
+use crate::color::Color;
+
+/// Maximum number of parameters of a control sequence. Further parameters are ignored.
+const MAX_PARAMETERS: usize = 8;
+
+/// Colors of the SGR codes 30 to 37 and 90 to 97, using the VGA palette.
+const PALETTE: [Color; 16] = [
+    Color::from_rgb888(0, 0, 0),
+    Color::from_rgb888(170, 0, 0),
+    Color::from_rgb888(0, 170, 0),
+    Color::from_rgb888(170, 85, 0),
+    Color::from_rgb888(0, 0, 170),
+    Color::from_rgb888(170, 0, 170),
+    Color::from_rgb888(0, 170, 170),
+    Color::from_rgb888(170, 170, 170),
+    Color::from_rgb888(85, 85, 85),
+    Color::from_rgb888(255, 85, 85),
+    Color::from_rgb888(85, 255, 85),
+    Color::from_rgb888(255, 255, 85),
+    Color::from_rgb888(85, 85, 255),
+    Color::from_rgb888(255, 85, 255),
+    Color::from_rgb888(85, 255, 255),
+    Color::from_rgb888(255, 255, 255),
+];
+
+/// Part of the line or screen that is erased.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Erase {
+    /// From the cursor to the end.
+    ToEnd,
+
+    /// From the start to the cursor, including the cursor position.
+    ToStart,
+
+    /// Everything.
+    All,
+}
+
+/// Result of feeding a character to the parser.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Action {
+    /// Character to be drawn or handled like `\n`.
+    Print(char),
+
+    /// New text colors. `None` stands for the default color of the output.
+    Style {
+        foreground: Option<Color>,
+        background: Option<Color>,
+    },
+
+    /// Moves the cursor to a zero-based row and column.
+    MoveTo { row: u16, column: u16 },
+
+    /// Moves the cursor relative to its current position.
+    MoveBy { rows: i16, columns: i16 },
+
+    /// Erases part of the current line.
+    EraseLine(Erase),
+
+    /// Erases part of the screen.
+    EraseScreen(Erase),
+}
+
+/// Parser state between two characters.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum State {
+    /// Plain text.
+    Ground,
+
+    /// After ESC.
+    Escape,
+
+    /// Inside a control sequence started by ESC [.
+    Csi,
+}
+
+/// Parser for ANSI escape sequences keeping track of the current colors.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut parser = Parser::new();
+/// for c in "\x1b[31merror\x1b[0m".chars() {
+///     match parser.feed(c) {
+///         Some(Action::Print(c)) => draw(c),
+///         Some(Action::Style { foreground, .. }) => color = foreground.unwrap_or(white),
+///         _ => {}
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Parser {
+    /// Current state.
+    state: State,
+
+    /// Parameters of the current control sequence.
+    parameters: [u16; MAX_PARAMETERS],
+
+    /// Number of parameters started so far.
+    count: usize,
+
+    /// Current foreground color, `None` for the default.
+    foreground: Option<Color>,
+
+    /// Current background color, `None` for the default.
+    background: Option<Color>,
+}
+
+impl Default for Parser {
+    fn default() -> Parser {
+        Parser::new()
+    }
+}
+
+impl Parser {
+    /// Creates a parser using the default colors.
+    pub const fn new() -> Parser {
+        Parser {
+            state: State::Ground,
+            parameters: [0; MAX_PARAMETERS],
+            count: 0,
+            foreground: None,
+            background: None,
+        }
+    }
+
+    /// Returns the current foreground and background colors, `None` standing for the defaults.
+    pub fn style(&self) -> (Option<Color>, Option<Color>) {
+        (self.foreground, self.background)
+    }
+
+    /// Processes the next character. Returns `None` while inside of an escape sequence.
+    pub fn feed(&mut self, c: char) -> Option<Action> {
+        match self.state {
+            State::Ground => {
+                if c == '\x1b' {
+                    self.state = State::Escape;
+                    None
+                } else {
+                    Some(Action::Print(c))
+                }
+            }
+            State::Escape => {
+                if c == '[' {
+                    self.state = State::Csi;
+                    self.parameters = [0; MAX_PARAMETERS];
+                    self.count = 0;
+                } else {
+                    // Sequences other than CSI consist of a single character.
+                    self.state = State::Ground;
+                }
+                None
+            }
+            State::Csi => match c {
+                '0'..='9' => {
+                    if self.count == 0 {
+                        self.count = 1;
+                    }
+                    if let Some(parameter) = self.parameters.get_mut(self.count - 1) {
+                        let digit = c as u16 - '0' as u16;
+                        *parameter = parameter.saturating_mul(10).saturating_add(digit);
+                    }
+                    None
+                }
+                ';' => {
+                    self.count = (self.count.max(1) + 1).min(MAX_PARAMETERS + 1);
+                    None
+                }
+                '\x40'..='\x7e' => {
+                    self.state = State::Ground;
+                    self.execute(c)
+                }
+                // Intermediate and private marker bytes are skipped.
+                _ => None,
+            },
+        }
+    }
+
+    /// Executes the control sequence ended by `command`.
+    fn execute(&mut self, command: char) -> Option<Action> {
+        let count = self.count.min(MAX_PARAMETERS);
+        let first = self.parameters[0];
+        // Cursor sequences treat a missing or zero parameter as 1.
+        let amount = first.max(1).min(i16::MAX as u16) as i16;
+
+        match command {
+            'm' => {
+                self.select_graphic_rendition(count);
+                Some(Action::Style {
+                    foreground: self.foreground,
+                    background: self.background,
+                })
+            }
+            'H' | 'f' => Some(Action::MoveTo {
+                row: first.max(1) - 1,
+                column: self.parameters[1].max(1) - 1,
+            }),
+            'A' => Some(Action::MoveBy { rows: -amount, columns: 0 }),
+            'B' => Some(Action::MoveBy { rows: amount, columns: 0 }),
+            'C' => Some(Action::MoveBy { rows: 0, columns: amount }),
+            'D' => Some(Action::MoveBy { rows: 0, columns: -amount }),
+            'K' => Some(Action::EraseLine(Parser::erase(first)?)),
+            'J' => Some(Action::EraseScreen(Parser::erase(first)?)),
+            _ => None,
+        }
+    }
+
+    /// Applies the SGR parameters to the current colors.
+    fn select_graphic_rendition(&mut self, count: usize) {
+        if count == 0 {
+            self.foreground = None;
+            self.background = None;
+            return;
+        }
+
+        let parameters = &self.parameters[..count];
+        let mut i = 0;
+        while i < parameters.len() {
+            match parameters[i] {
+                0 => {
+                    self.foreground = None;
+                    self.background = None;
+                }
+                code @ 30..=37 => self.foreground = Some(PALETTE[code as usize - 30]),
+                code @ 90..=97 => self.foreground = Some(PALETTE[code as usize - 90 + 8]),
+                39 => self.foreground = None,
+                code @ 40..=47 => self.background = Some(PALETTE[code as usize - 40]),
+                code @ 100..=107 => self.background = Some(PALETTE[code as usize - 100 + 8]),
+                49 => self.background = None,
+                code @ (38 | 48) => {
+                    let (color, used) = Parser::extended_color(&parameters[i + 1..]);
+                    if let Some(color) = color {
+                        if code == 38 {
+                            self.foreground = Some(color);
+                        } else {
+                            self.background = Some(color);
+                        }
+                    }
+                    i += used;
+                }
+                // Attributes like bold or underline are not supported.
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+
+    /// Parses the color following SGR 38 or 48. Returns the color and the number of parameters
+    /// it used.
+    fn extended_color(parameters: &[u16]) -> (Option<Color>, usize) {
+        match parameters {
+            [5, index, ..] => (Some(Parser::palette_256(*index)), 2),
+            [2, red, green, blue, ..] => {
+                let component = |value: u16| value.min(255) as u8;
+                (Some(Color::from_rgb888(component(*red), component(*green), component(*blue))), 4)
+            }
+            _ => (None, parameters.len()),
+        }
+    }
+
+    /// Returns a color of the xterm 256 color palette.
+    fn palette_256(index: u16) -> Color {
+        match index {
+            0..=15 => PALETTE[index as usize],
+            16..=231 => {
+                let index = index - 16;
+                let level = |value: u16| if value == 0 { 0 } else { (55 + value * 40) as u8 };
+                Color::from_rgb888(level(index / 36), level(index / 6 % 6), level(index % 6))
+            }
+            _ => {
+                let gray = (8 + (index.min(255) - 232) * 10) as u8;
+                Color::from_rgb888(gray, gray, gray)
+            }
+        }
+    }
+
+    /// Returns the part erased by `K` or `J` with the given parameter.
+    fn erase(parameter: u16) -> Option<Erase> {
+        match parameter {
+            0 => Some(Erase::ToEnd),
+            1 => Some(Erase::ToStart),
+            2 | 3 => Some(Erase::All),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feeds `text` to `parser` and returns the resulting actions.
+    fn feed(parser: &mut Parser, text: &str) -> heapless::Vec<Action, 32> {
+        let mut actions = heapless::Vec::new();
+        for c in text.chars() {
+            if let Some(action) = parser.feed(c) {
+                actions.push(action).unwrap();
+            }
+        }
+        actions
+    }
+
+    #[test]
+    fn colors_are_decoded() {
+        let mut parser = Parser::new();
+        let actions = feed(&mut parser, "\x1b[1;31mA\x1b[38;2;255;128;0;44m");
+        assert_eq!(actions[0], Action::Style { foreground: Some(PALETTE[1]), background: None });
+        assert_eq!(actions[1], Action::Print('A'));
+        let orange = Color::from_rgb888(255, 128, 0);
+        assert_eq!(actions[2], Action::Style { foreground: Some(orange), background: Some(PALETTE[4]) });
+
+        feed(&mut parser, "\x1b[38;5;196m");
+        assert_eq!(parser.style().0, Some(Color::from_rgb888(255, 0, 0)));
+        feed(&mut parser, "\x1b[m");
+        assert_eq!(parser.style(), (None, None));
+    }
+
+    #[test]
+    fn cursor_and_erase_sequences_are_decoded() {
+        let mut parser = Parser::new();
+        let actions = feed(&mut parser, "\x1b[5;10H\x1b[A\x1b[3D\x1b[2J\x1b[K");
+        assert_eq!(&actions[..], &[
+            Action::MoveTo { row: 4, column: 9 },
+            Action::MoveBy { rows: -1, columns: 0 },
+            Action::MoveBy { rows: 0, columns: -3 },
+            Action::EraseScreen(Erase::All),
+            Action::EraseLine(Erase::ToEnd),
+        ]);
+    }
+
+    #[test]
+    fn truncated_sequences_print_nothing() {
+        let mut parser = Parser::new();
+        assert!(feed(&mut parser, "\x1b[38;5").is_empty());
+        assert!(feed(&mut parser, "\x1b").is_empty());
+
+        // Extended colors missing their parameters keep the current colors.
+        let mut parser = Parser::new();
+        feed(&mut parser, "\x1b[32m\x1b[38;2;1m");
+        assert_eq!(parser.style(), (Some(PALETTE[2]), None));
+
+        // Characters after an unsupported escape sequence are printed again.
+        let actions = feed(&mut parser, "\x1b(B\x1b[99;99;99;99;99;99;99;99;99;99mok");
+        assert_eq!(actions.last(), Some(&Action::Print('k')));
+    }
+}
@@ -0,0 +1,198 @@
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::PwmPin;
+
+/// Time between two brightness steps of a fade in milliseconds.
+const FADE_STEP_MS: u64 = 10;
+
+/// Display backlight driven by a PWM channel.
+///
+/// Brightness is expressed as a value between `0` (off) and `255` (full brightness) which is
+/// mapped to the duty cycle range of the PWM channel.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut backlight = Backlight::new(pwm);
+/// backlight.set_brightness(128);
+/// display.fade_backlight(&mut backlight, 255, 500);
+/// ```
+pub struct Backlight<PWM> {
+    /// PWM channel connected to the backlight LED.
+    pwm: PWM,
+
+    /// Current brightness.
+    brightness: u8,
+}
+
+impl<PWM> Backlight<PWM>
+where
+    PWM: PwmPin<Duty = u16>,
+{
+    /// Creates a new backlight from an enabled PWM channel. The backlight starts at full brightness.
+    pub fn new(pwm: PWM) -> Backlight<PWM> {
+        let mut backlight = Backlight {
+            pwm,
+            brightness: 255,
+        };
+
+        backlight.pwm.enable();
+        backlight.set_brightness(255);
+        backlight
+    }
+
+    /// Returns the current brightness.
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Sets the brightness immediately.
+    pub fn set_brightness(&mut self, brightness: u8) {
+        let max_duty = self.pwm.get_max_duty() as u32;
+        let duty = max_duty * (brightness as u32) / 255;
+        self.pwm.set_duty(duty as u16);
+        self.brightness = brightness;
+    }
+
+    /// Gradually changes the brightness to `brightness` over `duration` milliseconds.
+    pub fn fade_to<DELAY: DelayMs<u64>>(&mut self, brightness: u8, duration: u64, delay: &mut DELAY) {
+        let start = self.brightness as i64;
+        let difference = (brightness as i64) - start;
+        let steps = (duration / FADE_STEP_MS).min(difference.unsigned_abs());
+
+        if let Some(step_delay) = duration.checked_div(steps) {
+            for step in 1..steps {
+                delay.delay_ms(step_delay);
+                let value = start + difference * (step as i64) / (steps as i64);
+                self.set_brightness(value as u8);
+            }
+            delay.delay_ms(step_delay);
+        }
+
+        self.set_brightness(brightness);
+    }
+
+    /// Feeds an ambient light `reading` to `adaptive` and applies the resulting brightness, if it
+    /// changed. Meant to be called periodically with readings of a light sensor.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// static CURVE: [(u32, u8); 3] = [(0, 20), (200, 128), (1000, 255)];
+    /// let mut adaptive = AdaptiveBrightness::new(&CURVE);
+    ///
+    /// loop {
+    ///     backlight.adapt(&mut adaptive, light_sensor.lux());
+    ///     delay.delay_ms(100);
+    /// }
+    /// ```
+    pub fn adapt(&mut self, adaptive: &mut AdaptiveBrightness, reading: u32) {
+        if let Some(brightness) = adaptive.update(reading) {
+            self.set_brightness(brightness);
+        }
+    }
+
+    /// Releases the PWM channel.
+    pub fn release(self) -> PWM {
+        self.pwm
+    }
+}
+
+/// Maps ambient light readings to backlight brightness.
+///
+/// Readings are mapped through a curve of `(reading, brightness)` points sorted by reading, with
+/// linear interpolation in between. The mapped brightness is smoothed, so short shadows or
+/// flashes don't cause flicker, and changes smaller than the hysteresis are ignored.
+pub struct AdaptiveBrightness<'a> {
+    /// Curve points sorted by reading.
+    curve: &'a [(u32, u8)],
+
+    /// Minimum brightness change that is applied.
+    hysteresis: u8,
+
+    /// Weight of the previous value when smoothing, out of 256.
+    smoothing: u8,
+
+    /// Smoothed brightness in 8.8 fixed point, once there was a reading.
+    filtered: Option<u16>,
+
+    /// Brightness last returned by `update`.
+    brightness: u8,
+}
+
+impl<'a> AdaptiveBrightness<'a> {
+    /// Creates a mapping with the given curve, a hysteresis of 8 and moderate smoothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `curve` is empty or its points are not sorted by reading.
+    pub fn new(curve: &'a [(u32, u8)]) -> AdaptiveBrightness<'a> {
+        assert!(!curve.is_empty());
+        assert!(curve.windows(2).all(|points| points[0].0 <= points[1].0));
+        AdaptiveBrightness {
+            curve,
+            hysteresis: 8,
+            smoothing: 192,
+            filtered: None,
+            brightness: 0,
+        }
+    }
+
+    /// Sets the minimum brightness change that is applied.
+    pub fn set_hysteresis(&mut self, hysteresis: u8) {
+        self.hysteresis = hysteresis;
+    }
+
+    /// Sets how strongly readings are smoothed, from `0` (not at all) to `255` (very slow changes).
+    pub fn set_smoothing(&mut self, smoothing: u8) {
+        self.smoothing = smoothing;
+    }
+
+    /// Returns the brightness the curve assigns to `reading`, without smoothing.
+    pub fn brightness_for(&self, reading: u32) -> u8 {
+        let (first_reading, first_brightness) = self.curve[0];
+        if reading <= first_reading {
+            return first_brightness;
+        }
+
+        for points in self.curve.windows(2) {
+            let ((x0, y0), (x1, y1)) = (points[0], points[1]);
+            if reading <= x1 {
+                let t = (reading - x0) as i64;
+                let span = (x1 - x0).max(1) as i64;
+                return (y0 as i64 + (y1 as i64 - y0 as i64) * t / span) as u8;
+            }
+        }
+
+        self.curve[self.curve.len() - 1].1
+    }
+
+    /// Processes a new reading and returns the brightness to apply, or `None` if it should not
+    /// change. The first reading is applied without smoothing.
+    pub fn update(&mut self, reading: u32) -> Option<u8> {
+        let target = (self.brightness_for(reading) as u32) << 8;
+        let (filtered, first) = match self.filtered {
+            Some(previous) => {
+                let weight = self.smoothing as u32;
+                ((previous as u32 * weight + target * (256 - weight)) / 256, false)
+            }
+            None => (target, true),
+        };
+        self.filtered = Some(filtered as u16);
+
+        // Rounding, so that the smoothed value can reach the target.
+        let brightness = ((filtered + 0x80) >> 8).min(255) as u8;
+        let difference = (brightness as i16 - self.brightness as i16).abs();
+        let at_limit = brightness == 0 || brightness == 255;
+        if first || difference >= self.hysteresis.max(1) as i16 || (difference > 0 && at_limit) {
+            self.brightness = brightness;
+            Some(brightness)
+        } else {
+            None
+        }
+    }
+
+    /// Brightness last returned by `update`.
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+}
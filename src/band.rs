@@ -0,0 +1,104 @@
+//! Rendering through a small reusable pixel buffer.
+//!
+//! A full RGB565 framebuffer of a 128x160 display takes 40KB of RAM, which many MCUs can't spare.
+//! [`ST7734::render_banded`](../struct.ST7734.html#method.render_banded) instead splits the screen
+//! into horizontal bands that fit into a caller-provided buffer. The scene is drawn into each band
+//! by a callback and the band is transferred to the display before the next one is drawn, so shapes
+//! can overlap and be composed without flicker while only a few KB of RAM are used.
+
+use crate::color::Color;
+use crate::geometry::Rect;
+use crate::image::Image;
+
+/// Horizontal band of the screen that is drawn into before it is transferred to the display.
+///
+/// All coordinates are display coordinates. Drawing outside of the band area is ignored, so the
+/// same drawing code can be run for every band.
+pub struct Band<'a> {
+    /// Pixels of the band, stored row by row.
+    pixels: &'a mut [Color],
+
+    /// Area of the display covered by the band.
+    area: Rect,
+}
+
+impl<'a> Band<'a> {
+    /// Creates a band covering `area`.
+    pub(crate) fn new(pixels: &'a mut [Color], area: Rect) -> Band<'a> {
+        Band { pixels, area }
+    }
+
+    /// Area of the display covered by the band.
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+
+    /// Returns the color of the pixel at the given display coordinates, if it is within the band.
+    pub fn pixel(&self, x: u16, y: u16) -> Option<Color> {
+        if self.area.contains(x, y) {
+            Some(self.pixels[self.index(x, y)])
+        } else {
+            None
+        }
+    }
+
+    /// Sets the pixel at the given display coordinates. Pixels outside of the band are ignored.
+    pub fn set_pixel(&mut self, x: u16, y: u16, color: &Color) {
+        if self.area.contains(x, y) {
+            let index = self.index(x, y);
+            self.pixels[index] = *color;
+        }
+    }
+
+    /// Fills the whole band with `color`.
+    pub fn clear(&mut self, color: &Color) {
+        for pixel in self.pixels.iter_mut() {
+            *pixel = *color;
+        }
+    }
+
+    /// Draws a filled rectangle with the specified `color`.
+    pub fn fill_rect(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, color: &Color) {
+        if let Some(area) = Rect::new(x0, y0, x1, y1).intersection(&self.area) {
+            for y in area.y0..=area.y1 {
+                let start = self.index(area.x0, y);
+                let end = self.index(area.x1, y);
+                for pixel in &mut self.pixels[start..=end] {
+                    *pixel = *color;
+                }
+            }
+        }
+    }
+
+    /// Draws an image with its top left corner at the provided coordinates.
+    pub fn draw_image(&mut self, x: u16, y: u16, image: &Image) {
+        if image.width() == 0 || image.height() == 0 {
+            return;
+        }
+
+        let x1 = x.saturating_add(image.width() - 1);
+        let y1 = y.saturating_add(image.height() - 1);
+        if let Some(area) = Rect::new(x, y, x1, y1).intersection(&self.area) {
+            for py in area.y0..=area.y1 {
+                for px in area.x0..=area.x1 {
+                    let index = self.index(px, py);
+                    self.pixels[index] = image.pixel(px - x, py - y);
+                }
+            }
+        }
+    }
+
+    /// Blends `color` over the pixel at the given coordinates with `alpha` ranging from `0`
+    /// (transparent) to `255` (opaque). Useful for antialiased edges and translucent overlays.
+    pub fn blend_pixel(&mut self, x: u16, y: u16, color: &Color, alpha: u8) {
+        if self.area.contains(x, y) {
+            let index = self.index(x, y);
+            self.pixels[index] = self.pixels[index].blend(color, alpha);
+        }
+    }
+
+    /// Returns the buffer index of the given display coordinates.
+    fn index(&self, x: u16, y: u16) -> usize {
+        (y - self.area.y0) as usize * self.area.width() as usize + (x - self.area.x0) as usize
+    }
+}
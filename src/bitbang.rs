@@ -0,0 +1,66 @@
+//! Fast software SPI for platforms that can drive pins directly.
+//!
+//! The software SPI path of the driver toggles pins through `OutputPin`, which costs a function
+//! call per edge. Platforms with atomic port set/clear registers can shift out a byte much faster
+//! by implementing [`FastPins`](trait.FastPins.html) and passing a
+//! [`FastSpi`](struct.FastSpi.html) to `ST7734::new_with_spi`.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! struct PortPins;
+//!
+//! impl FastPins for PortPins {
+//!     fn shift_out(&mut self, byte: u8) {
+//!         for bit in 0..8 {
+//!             let mosi = if byte & (0x80 >> bit) != 0 { MOSI_MASK } else { 0 };
+//!             unsafe {
+//!                 PORT_CLEAR.write_volatile(CLK_MASK | MOSI_MASK);
+//!                 PORT_SET.write_volatile(mosi);
+//!                 PORT_SET.write_volatile(CLK_MASK);
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! let mut display = ST7734::new_with_spi(FastSpi::new(PortPins), dc, delay);
+//! ```
+
+use core::convert::Infallible;
+use embedded_hal::blocking::spi;
+
+/// Clock and data pins of a software SPI bus that are driven without `OutputPin`.
+pub trait FastPins {
+    /// Shifts out `byte` most significant bit first. Data has to be stable on the rising clock edge
+    /// and the clock has to idle low (SPI mode 0).
+    fn shift_out(&mut self, byte: u8);
+}
+
+/// Software SPI bus built on `FastPins`, usable wherever the driver expects hardware SPI.
+pub struct FastSpi<P> {
+    /// Pins the data is shifted out on.
+    pins: P,
+}
+
+impl<P: FastPins> FastSpi<P> {
+    /// Creates a bus shifting data out on `pins`.
+    pub fn new(pins: P) -> FastSpi<P> {
+        FastSpi { pins }
+    }
+
+    /// Releases the pins.
+    pub fn release(self) -> P {
+        self.pins
+    }
+}
+
+impl<P: FastPins> spi::Write<u8> for FastSpi<P> {
+    type Error = Infallible;
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Infallible> {
+        for word in words {
+            self.pins.shift_out(*word);
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,19 @@
-use num;
+use crate::palette;
 
-/// Represents a pixel color.
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+
+/// Represents a pixel color in RGB565 format.
+///
+/// All constructors except the HSV and HSL ones are `const`, so colors can be used in statics
+/// and constant palettes:
+///
+/// ```
+/// # use st7735::color::{Color, DefaultColor};
+/// const PALETTE: [Color; 2] = [Color::from_rgb(31, 0, 0), Color::from_default(DefaultColor::Navy)];
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub struct Color {
     pub hex: u16,
 }
@@ -11,9 +24,10 @@ impl Color {
     /// # Example
     ///
     /// ```
+    /// # use st7735::color::Color;
     /// let color_green = Color::from_hex(0x00FF00);
     /// ```
-    pub fn from_hex(hex: u16) -> Color {
+    pub const fn from_hex(hex: u16) -> Color {
         Color { hex }
     }
 
@@ -22,11 +36,12 @@ impl Color {
     /// # Example
     ///
     /// ```
+    /// # use st7735::color::{Color, DefaultColor};
     /// let color_red = Color::from_default(DefaultColor::Red);
     /// ```
-    pub fn from_default(default_color: DefaultColor) -> Color {
+    pub const fn from_default(default_color: DefaultColor) -> Color {
         Color {
-            hex: num::ToPrimitive::to_u16(&default_color).unwrap(),
+            hex: default_color.hex(),
         }
     }
 
@@ -35,9 +50,10 @@ impl Color {
     /// # Example
     ///
     /// ```
+    /// # use st7735::color::Color;
     /// let color_red = Color::from_rgb(255, 0, 0);
     /// ```
-    pub fn from_rgb(red: u16, green: u16, blue: u16) -> Color {
+    pub const fn from_rgb(red: u16, green: u16, blue: u16) -> Color {
         let r = red & 0x1F;
         let g = green & 0x3F;
         let b = blue & 0x1F;
@@ -45,23 +61,346 @@ impl Color {
             hex: (r << 11) + (g << 5) + b,
         }
     }
+
+    /// Create color from 8-bit red, green and blue components by truncating them. The tables of
+    /// the `gamma` module convert dark tones more accurately.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use st7735::color::Color;
+    /// let color_orange = Color::from_rgb888(255, 165, 0);
+    /// ```
+    pub const fn from_rgb888(red: u8, green: u8, blue: u8) -> Color {
+        Color::from_rgb((red >> 3) as u16, (green >> 2) as u16, (blue >> 3) as u16)
+    }
+
+    /// Create a gray color from a brightness `level`, `0` being black and `255` white. Green
+    /// has one more bit than red and blue, so some levels get a faint green or magenta tint.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use st7735::color::Color;
+    /// let dark_gray = Color::gray(64);
+    /// ```
+    pub const fn gray(level: u8) -> Color {
+        Color::from_rgb888(level, level, level)
+    }
+
+    /// Create color from hue (`0` to `359` degrees), saturation and value (`0` to `255`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use st7735::color::Color;
+    /// let color_orange = Color::from_hsv(30, 255, 255);
+    /// ```
+    pub fn from_hsv(hue: u16, saturation: u8, value: u8) -> Color {
+        let h = (hue % 360) as u32;
+        let s = saturation as u32;
+        let v = value as u32;
+
+        let remainder = (h % 60) * 255 / 60;
+        let p = v * (255 - s) / 255;
+        let q = v * (255 - s * remainder / 255) / 255;
+        let t = v * (255 - s * (255 - remainder) / 255) / 255;
+
+        let (r, g, b) = match h / 60 {
+            0 => (v, t, p),
+            1 => (q, v, p),
+            2 => (p, v, t),
+            3 => (p, q, v),
+            4 => (t, p, v),
+            _ => (v, p, q),
+        };
+
+        Color::from_rgb888(r as u8, g as u8, b as u8)
+    }
+
+    /// Create color from hue (`0` to `359` degrees), saturation and lightness (`0` to `255`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use st7735::color::Color;
+    /// let color_pink = Color::from_hsl(350, 255, 200);
+    /// ```
+    pub fn from_hsl(hue: u16, saturation: u8, lightness: u8) -> Color {
+        let h = (hue % 360) as u32;
+        let s = saturation as u32;
+        let l = lightness as u32;
+
+        let chroma = (255 - (2 * l as i32 - 255).unsigned_abs()) * s / 255;
+        let sector = h * 255 / 60;
+        let x = chroma * (255 - ((sector % 510) as i32 - 255).unsigned_abs()) / 255;
+        let m = l - chroma / 2;
+
+        let (r, g, b) = match h / 60 {
+            0 => (chroma + m, x + m, m),
+            1 => (x + m, chroma + m, m),
+            2 => (m, chroma + m, x + m),
+            3 => (m, x + m, chroma + m),
+            4 => (x + m, m, chroma + m),
+            _ => (chroma + m, m, x + m),
+        };
+
+        Color::from_rgb888(r as u8, g as u8, b as u8)
+    }
+
+    /// Mixes `other` into this color. An `alpha` of `0` keeps this color, `255` results in `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use st7735::color::{Color, DefaultColor};
+    /// let red = Color::from_default(DefaultColor::Red);
+    /// let half_transparent_blue = red.blend(&Color::from_default(DefaultColor::Blue), 128);
+    /// ```
+    pub fn blend(&self, other: &Color, alpha: u8) -> Color {
+        self.lerp(other, alpha as u16, 255)
+    }
+
+    /// Interpolates between this color and `other`. Returns this color for `step == 0` and
+    /// `other` for `step >= steps`, which makes it convenient for drawing gradients.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let black = Color::from_default(DefaultColor::Black);
+    /// let white = Color::from_default(DefaultColor::White);
+    /// for x in 0..128 {
+    ///     display.draw_vertical_line(x, 0, 159, &black.lerp(&white, x, 127));
+    /// }
+    /// ```
+    pub fn lerp(&self, other: &Color, step: u16, steps: u16) -> Color {
+        if steps == 0 || step >= steps {
+            return *other;
+        }
+
+        let (r0, g0, b0) = self.components();
+        let (r1, g1, b1) = other.components();
+        let mix = |from: u16, to: u16| -> u16 {
+            let from = from as i32;
+            let to = to as i32;
+            (from + (to - from) * (step as i32) / (steps as i32)) as u16
+        };
+
+        Color::from_rgb(mix(r0, r1), mix(g0, g1), mix(b0, b1))
+    }
+
+    /// Moves the color towards white by `percent` (`0` to `100`).
+    pub fn lighten(&self, percent: u8) -> Color {
+        self.lerp(&Color::from_default(DefaultColor::White), percent as u16, 100)
+    }
+
+    /// Moves the color towards black by `percent` (`0` to `100`).
+    pub fn darken(&self, percent: u8) -> Color {
+        self.lerp(&Color::from_default(DefaultColor::Black), percent as u16, 100)
+    }
+
+    /// Returns the color as big-endian byte pair in the order it is sent to the display.
+    pub const fn to_be_bytes(&self) -> [u8; 2] {
+        [(self.hex >> 8) as u8, self.hex as u8]
+    }
+
+    /// Returns the 8-bit red, green and blue components. The low bits are filled with the high
+    /// bits, so that white converts to `(255, 255, 255)`.
+    pub const fn to_rgb888(&self) -> (u8, u8, u8) {
+        let red = ((self.hex >> 11) & 0x1F) as u8;
+        let green = ((self.hex >> 5) & 0x3F) as u8;
+        let blue = (self.hex & 0x1F) as u8;
+        ((red << 3) | (red >> 2), (green << 2) | (green >> 4), (blue << 3) | (blue >> 2))
+    }
+
+    /// Splits the color into its 5-bit red, 6-bit green and 5-bit blue components.
+    fn components(&self) -> (u16, u16, u16) {
+        ((self.hex >> 11) & 0x1F, (self.hex >> 5) & 0x3F, self.hex & 0x1F)
+    }
+}
+
+/// Error returned when parsing a color from a string fails.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ParseColorError;
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected a color in the form #RRGGBB or #RGB or a color name")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseColorError {}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parses a color written as `#RRGGBB` or `#RGB` with hexadecimal digits in either case, or
+    /// as CSS color name. The `#` is optional. `#RGB` is expanded to `#RRGGBB` before the
+    /// conversion to RGB565.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::str::FromStr;
+    /// # use st7735::color::Color;
+    /// let orange: Color = "#FFA500".parse().unwrap();
+    /// let white = Color::from_str("#fff").unwrap();
+    /// let navy = Color::from_str("navy").unwrap();
+    /// ```
+    fn from_str(s: &str) -> Result<Color, ParseColorError> {
+        let digits = s.strip_prefix('#').unwrap_or(s);
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return palette::from_name(s).ok_or(ParseColorError);
+        }
+        let value = u32::from_str_radix(digits, 16).map_err(|_| ParseColorError)?;
+
+        let (red, green, blue) = match digits.len() {
+            6 => (value >> 16, (value >> 8) & 0xFF, value & 0xFF),
+            3 => ((value >> 8) * 0x11, ((value >> 4) & 0xF) * 0x11, (value & 0xF) * 0x11),
+            _ => return Err(ParseColorError),
+        };
+        Ok(Color::from_rgb888(red as u8, green as u8, blue as u8))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Color {
+    type Error = ParseColorError;
+
+    fn try_from(s: &'a str) -> Result<Color, ParseColorError> {
+        s.parse()
+    }
 }
 
-/// Set of hex values for default colors.
-#[derive(FromPrimitive, ToPrimitive)]
+/// Set of default colors. `Green` and `Lime` are both the full intensity green, the darker
+/// green of CSS is `palette::GREEN`. The `palette` module contains all named colors of CSS.
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum DefaultColor {
-    Black = 0x0000,
-    White = 0xFFFF,
-    Red = 0xF800,
-    Green = 0x0400,
-    Blue = 0x001F,
-    Lime = 0x07E0,
-    Cyan = 0x07FF,
-    Magenta = 0xF81F,
-    Yellow = 0xFFE0,
-    Purple = 0x8010,
-    Navy = 0x0010,
-    Teal = 0x0410,
-    Olive = 0x8400,
-    Gray = 0x8410,
+    Black,
+    White,
+    Red,
+    Green,
+    Blue,
+    Lime,
+    Cyan,
+    Magenta,
+    Yellow,
+    Purple,
+    Navy,
+    Teal,
+    Olive,
+    Gray,
+}
+
+impl DefaultColor {
+    /// Returns the RGB565 value of the color.
+    pub const fn hex(self) -> u16 {
+        match self {
+            DefaultColor::Black => 0x0000,
+            DefaultColor::White => 0xFFFF,
+            DefaultColor::Red => 0xF800,
+            DefaultColor::Green | DefaultColor::Lime => 0x07E0,
+            DefaultColor::Blue => 0x001F,
+            DefaultColor::Cyan => 0x07FF,
+            DefaultColor::Magenta => 0xF81F,
+            DefaultColor::Yellow => 0xFFE0,
+            DefaultColor::Purple => 0x8010,
+            DefaultColor::Navy => 0x0010,
+            DefaultColor::Teal => 0x0410,
+            DefaultColor::Olive => 0x8400,
+            DefaultColor::Gray => 0x8410,
+        }
+    }
+}
+
+/// Converts the RGB565 value of a color back into a `DefaultColor`. `0x07E0` converts to `Green`.
+impl num::FromPrimitive for DefaultColor {
+    fn from_i64(n: i64) -> Option<DefaultColor> {
+        u64::try_from(n).ok().and_then(DefaultColor::from_u64)
+    }
+
+    fn from_u64(n: u64) -> Option<DefaultColor> {
+        let color = match n {
+            0x0000 => DefaultColor::Black,
+            0xFFFF => DefaultColor::White,
+            0xF800 => DefaultColor::Red,
+            0x07E0 => DefaultColor::Green,
+            0x001F => DefaultColor::Blue,
+            0x07FF => DefaultColor::Cyan,
+            0xF81F => DefaultColor::Magenta,
+            0xFFE0 => DefaultColor::Yellow,
+            0x8010 => DefaultColor::Purple,
+            0x0010 => DefaultColor::Navy,
+            0x0410 => DefaultColor::Teal,
+            0x8400 => DefaultColor::Olive,
+            0x8410 => DefaultColor::Gray,
+            _ => return None,
+        };
+        Some(color)
+    }
+}
+
+/// Converts a `DefaultColor` into its RGB565 value, like `DefaultColor::hex`.
+impl num::ToPrimitive for DefaultColor {
+    fn to_i64(&self) -> Option<i64> {
+        Some(self.hex() as i64)
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        Some(self.hex() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::FromPrimitive;
+
+    #[test]
+    fn rgb888_round_trips_full_intensities() {
+        assert_eq!(Color::from_rgb888(255, 255, 255), Color::from_default(DefaultColor::White));
+        assert_eq!(Color::from_default(DefaultColor::White).to_rgb888(), (255, 255, 255));
+        assert_eq!(Color::from_default(DefaultColor::Red).to_rgb888(), (255, 0, 0));
+        assert_eq!(Color::from_hex(0).to_rgb888(), (0, 0, 0));
+        assert_eq!(Color::from_default(DefaultColor::Cyan).to_be_bytes(), [0x07, 0xFF]);
+    }
+
+    #[test]
+    fn hsv_and_hsl_hit_the_primaries() {
+        assert_eq!(Color::from_hsv(0, 255, 255), Color::from_default(DefaultColor::Red));
+        assert_eq!(Color::from_hsv(120, 255, 255), Color::from_default(DefaultColor::Green));
+        assert_eq!(Color::from_hsv(600, 255, 255), Color::from_default(DefaultColor::Blue));
+        assert_eq!(Color::from_hsv(42, 0, 0), Color::from_default(DefaultColor::Black));
+        assert_eq!(Color::from_hsl(0, 255, 128), Color::from_default(DefaultColor::Red));
+        assert_eq!(Color::from_hsl(200, 255, 255), Color::from_default(DefaultColor::White));
+    }
+
+    #[test]
+    fn lerp_reaches_both_ends() {
+        let black = Color::from_default(DefaultColor::Black);
+        let white = Color::from_default(DefaultColor::White);
+        assert_eq!(black.lerp(&white, 0, 4), black);
+        assert_eq!(black.lerp(&white, 1, 2), Color::from_rgb(15, 31, 15));
+        assert_eq!(black.lerp(&white, 9, 4), white);
+        assert_eq!(black.lerp(&white, 0, 0), white);
+        assert_eq!(black.blend(&white, 255), white);
+    }
+
+    #[test]
+    fn parses_hex_and_names() {
+        assert_eq!("#fff".parse(), Ok(Color::from_default(DefaultColor::White)));
+        assert_eq!("FFA500".parse(), Ok(Color::from_rgb888(255, 165, 0)));
+        assert_eq!("navy".parse(), Ok(Color::from_default(DefaultColor::Navy)));
+        assert_eq!("#12345".parse::<Color>(), Err(ParseColorError));
+        assert_eq!("#ggg".parse::<Color>(), Err(ParseColorError));
+    }
+
+    #[test]
+    fn default_colors_convert_from_their_hex_value() {
+        assert_eq!(DefaultColor::from_u64(0x07E0), Some(DefaultColor::Green));
+        assert_eq!(DefaultColor::from_i64(0x8410), Some(DefaultColor::Gray));
+        assert_eq!(DefaultColor::from_i64(-1), None);
+        assert_eq!(DefaultColor::from_u64(0x1234), None);
+    }
 }
@@ -1,19 +1,102 @@
-use alloc::vec::Vec;
+use heapless::Vec;
+
+/// Maximum number of arguments of a single command.
+pub const MAX_ARGUMENTS: usize = 16;
+
+/// Maximum number of commands of an initialization sequence.
+pub const MAX_INIT_COMMANDS: usize = 24;
 
 /// System function command.
+#[derive(Clone, Debug)]
 pub struct Command {
     /// Instruction to be executed.
     pub instruction: Instruction,
 
     /// List of arguments.
-    pub arguments: Vec<u8>,
+    pub arguments: Vec<u8, MAX_ARGUMENTS>,
 
     /// Delay after command is executed.
     pub delay: Option<u64>,
 }
 
+impl Command {
+    /// Creates a command.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `MAX_ARGUMENTS` arguments are given.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use st7735::command::{Command, Instruction};
+    /// let command = Command::new(Instruction::INVCTR, &[0x07], None);
+    /// ```
+    pub fn new(instruction: Instruction, arguments: &[u8], delay: Option<u64>) -> Command {
+        Command {
+            instruction,
+            arguments: Vec::from_slice(arguments).expect("too many command arguments"),
+            delay,
+        }
+    }
+}
+
+/// Sequence of up to `N` commands that is executed at once, built without heap allocations.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut queue: CommandQueue<4> = CommandQueue::new();
+/// queue.push(Command::new(Instruction::INVON, &[], None)).unwrap();
+/// queue.push(Command::new(Instruction::DISPON, &[], Some(10))).unwrap();
+/// display.execute_queue(&queue);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CommandQueue<const N: usize> {
+    /// Queued commands.
+    commands: Vec<Command, N>,
+}
+
+impl<const N: usize> CommandQueue<N> {
+    /// Creates an empty queue.
+    pub const fn new() -> CommandQueue<N> {
+        CommandQueue { commands: Vec::new() }
+    }
+
+    /// Appends a command. Returns the command back if the queue is full.
+    pub fn push(&mut self, command: Command) -> Result<(), Command> {
+        self.commands.push(command)
+    }
+
+    /// Removes all commands.
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+
+    /// Number of queued commands.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns `true` if no commands are queued.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Returns the queued commands.
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+}
+
+impl<const N: usize> Default for CommandQueue<N> {
+    fn default() -> CommandQueue<N> {
+        CommandQueue::new()
+    }
+}
+
 /// ST7735 instructions.
-#[derive(FromPrimitive, ToPrimitive)]
+#[derive(FromPrimitive, ToPrimitive, Clone, Copy, PartialEq, Debug)]
 pub enum Instruction {
     NOP = 0x00,
     SWRESET = 0x01,
@@ -25,6 +108,7 @@ pub enum Instruction {
     NORON = 0x13,
     INVOFF = 0x20,
     INVON = 0x21,
+    GAMSET = 0x26,
     DISPOFF = 0x28,
     DISPON = 0x29,
     CASET = 0x2A,
@@ -32,8 +116,14 @@ pub enum Instruction {
     RAMWR = 0x2C,
     RAMRD = 0x2E,
     PTLAR = 0x30,
+    VSCRDEF = 0x33,
+    TEOFF = 0x34,
+    TEON = 0x35,
     COLMOD = 0x3A,
     MADCTL = 0x36,
+    VSCRSADD = 0x37,
+    IDMOFF = 0x38,
+    IDMON = 0x39,
     FRMCTR1 = 0xB1,
     FRMCTR2 = 0xB2,
     FRMCTR3 = 0xB3,
@@ -0,0 +1,125 @@
+//! Display settings that can be stored and applied at once.
+//!
+//! Devices that keep the display settings in flash or EEPROM persist a `Config` and pass it to
+//! `ST7734::try_new_with_config` or `ST7734::apply_config` at startup. With the `serde` feature
+//! it implements `Serialize` and `Deserialize`, e.g. for `postcard` or JSON.
+
+use crate::controller::Chip;
+use crate::gamma::GammaCurve;
+use crate::Orientation;
+
+/// ST7735 modules, which differ in panel size, the part of the controller memory shown and
+/// the color inversion. Modules are usually told apart by the color of the tab on the
+/// protective film.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Variant {
+    /// 1.8" 128x160 panel showing the memory from the first column and row, usually with a red
+    /// tab.
+    RedTab,
+
+    /// 1.8" 128x160 panel showing the memory from column 2 and row 1.
+    GreenTab,
+
+    /// 1.44" 128x128 panel with a green tab, showing the memory from column 2 and row 3.
+    GreenTab128x128,
+
+    /// 0.96" 80x160 panel with an ST7735S, showing the memory from column 26 and row 1 with
+    /// inverted colors.
+    Mini80x160,
+}
+
+impl Variant {
+    /// Width and height of the panel in portrait orientation.
+    pub fn size(&self) -> (u16, u16) {
+        match self {
+            Variant::RedTab | Variant::GreenTab => (128, 160),
+            Variant::GreenTab128x128 => (128, 128),
+            Variant::Mini80x160 => (80, 160),
+        }
+    }
+
+    /// Column and row of the controller memory shown at the top left corner in portrait
+    /// orientation.
+    pub fn offset(&self) -> (u16, u16) {
+        match self {
+            Variant::RedTab => (0, 0),
+            Variant::GreenTab => (2, 1),
+            Variant::GreenTab128x128 => (2, 3),
+            Variant::Mini80x160 => (26, 1),
+        }
+    }
+
+    /// Returns `true` if the panel needs inverted colors.
+    pub fn inverted(&self) -> bool {
+        *self == Variant::Mini80x160
+    }
+
+    /// Revision of the ST7735 the module uses.
+    pub fn chip(&self) -> Chip {
+        match self {
+            Variant::Mini80x160 => Chip::ST7735S,
+            _ => Chip::ST7735R,
+        }
+    }
+}
+
+/// Display settings applied by `ST7734::apply_config`.
+///
+/// # Examples
+///
+/// ```ignore
+/// let config = Config {
+///     orientation: Orientation::Landscape,
+///     gamma: GammaCurve::Curve3,
+///     ..Config::new(Variant::GreenTab)
+/// };
+/// settings.store(&postcard::to_vec::<_, 16>(&config)?);
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Config {
+    /// Display orientation.
+    pub orientation: Orientation,
+
+    /// Module the panel size, offset, default inversion and chip are taken from.
+    pub variant: Variant,
+
+    /// Revision of the ST7735, for modules with a different chip than usual for the variant.
+    pub chip: Chip,
+
+    /// Column and row offset in portrait orientation added to the one of the variant, for
+    /// modules whose panel is placed differently.
+    pub offset: (u16, u16),
+
+    /// Gamma curve of the controller.
+    pub gamma: GammaCurve,
+
+    /// Maximum SPI clock frequency the display works reliably with. Not used by the driver,
+    /// but kept with the other settings for configuring the bus.
+    pub spi_speed_hz: u32,
+
+    /// Whether colors are inverted.
+    pub inverted: bool,
+}
+
+impl Config {
+    /// Creates the configuration of a module in portrait orientation.
+    pub fn new(variant: Variant) -> Config {
+        Config {
+            orientation: Orientation::Portrait,
+            variant,
+            chip: variant.chip(),
+            offset: (0, 0),
+            gamma: GammaCurve::Curve1,
+            spi_speed_hz: 15_000_000,
+            inverted: variant.inverted(),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config::new(Variant::RedTab)
+    }
+}
@@ -0,0 +1,293 @@
+//! Abstraction over the display controllers supported by the driver.
+//!
+//! Controllers of the ST77xx family share the command set for addressing and writing display
+//! memory, so the drawing code of the driver works with all of them. What differs is the panel
+//! size, the offset of the visible area within the controller memory and the initialization
+//! sequence, which are provided by implementations of [`Controller`](trait.Controller.html).
+
+use crate::command::{Command, CommandQueue, Instruction, MAX_INIT_COMMANDS};
+use crate::pixel_format::PixelFormat;
+use crate::timing::Timing;
+use crate::Orientation;
+
+/// Properties and initialization sequence of a display controller.
+pub trait Controller {
+    /// Width of the panel in portrait orientation.
+    fn width(&self) -> u16;
+
+    /// Height of the panel in portrait orientation.
+    fn height(&self) -> u16;
+
+    /// Number of rows of the controller memory, which the vertical scrolling areas have to add
+    /// up to. Defaults to the panel height.
+    fn memory_height(&self) -> u16 {
+        self.height()
+    }
+
+    /// Column and row of the controller memory shown at the top left corner of the panel in
+    /// the given orientation.
+    fn offset(&self, _orientation: Orientation) -> (u16, u16) {
+        (0, 0)
+    }
+
+    /// Value of the memory access control register for the given orientation.
+    fn madctl(&self, orientation: Orientation) -> u8 {
+        num::ToPrimitive::to_u8(&orientation).unwrap()
+    }
+
+    /// Value of the interface pixel format register for the given pixel format.
+    fn colmod(&self, pixel_format: PixelFormat) -> u8 {
+        num::ToPrimitive::to_u8(&pixel_format).unwrap()
+    }
+
+    /// Commands sent after a hard reset to initialize the controller. The sequence starts with a
+    /// software reset and ends with the display turned on.
+    fn init_commands(&self, pixel_format: PixelFormat, timing: &Timing) -> CommandQueue<MAX_INIT_COMMANDS>;
+}
+
+/// ST7735 controller with a 128x160 panel.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ST7735;
+
+impl Controller for ST7735 {
+    fn width(&self) -> u16 {
+        128
+    }
+
+    fn height(&self) -> u16 {
+        160
+    }
+
+    fn memory_height(&self) -> u16 {
+        162
+    }
+
+    fn init_commands(&self, pixel_format: PixelFormat, timing: &Timing) -> CommandQueue<MAX_INIT_COMMANDS> {
+        Chip::ST7735R.init_commands(pixel_format, timing)
+    }
+}
+
+/// Revision of the ST7735. The revisions share the command set, but their panels need different
+/// frame rate, power and gamma settings: with the settings of another revision colors look
+/// washed out or tinted, or the panel flickers. The usual modules are
+///
+/// - ST7735B: the original Adafruit 1.8" breakout.
+/// - ST7735R: 1.8" and 1.44" modules with a red, green or black tab, e.g. the current Adafruit
+///   and SainSmart ones.
+/// - ST7735S: 0.96" 80x160 modules and newer 1.8" modules, e.g. from Waveshare and most sold on
+///   AliExpress.
+///
+/// Used as the controller of the driver, `Chip::ST7735S` addresses the panel of the 0.96" 80x160
+/// modules, the other revisions a 128x160 panel. Other modules are configured with a `Variant`,
+/// see `ST7734::apply_config`.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut display = ST7734::with_controller_spi(Chip::ST7735B, spi, dc, Delay);
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Chip {
+    /// Original ST7735, also called ST7735B.
+    ST7735B,
+
+    /// ST7735R.
+    ST7735R,
+
+    /// ST7735S.
+    ST7735S,
+}
+
+impl Chip {
+    /// Frame rate, power and gamma settings of the revision, sent during initialization and by
+    /// `ST7734::apply_config`.
+    pub fn tuning_commands(&self) -> CommandQueue<MAX_INIT_COMMANDS> {
+        let mut commands = CommandQueue::new();
+        match self {
+            Chip::ST7735B => {
+                let gamma_positive = [
+                    0x09, 0x16, 0x09, 0x20, 0x21, 0x1B, 0x13, 0x19, 0x17, 0x15, 0x1E, 0x2B, 0x04, 0x05, 0x02,
+                    0x0E,
+                ];
+                let gamma_negative = [
+                    0x0B, 0x14, 0x08, 0x1E, 0x22, 0x1D, 0x18, 0x1E, 0x1B, 0x1A, 0x24, 0x2B, 0x06, 0x06, 0x02,
+                    0x0F,
+                ];
+
+                // The ST7735B has a single frame rate setting and no power settings for the
+                // partial and idle modes, but needs the source and gate output timing.
+                push(&mut commands, Command::new(Instruction::FRMCTR1, &[0x00, 0x06, 0x03], Some(10)));
+                push(&mut commands, Command::new(Instruction::DISSET5, &[0x15, 0x02], None));
+                push(&mut commands, Command::new(Instruction::INVCTR, &[0x00], None));
+                push(&mut commands, Command::new(Instruction::PWCTR1, &[0x02, 0x70], Some(10)));
+                push(&mut commands, Command::new(Instruction::PWCTR2, &[0x05], None));
+                push(&mut commands, Command::new(Instruction::PWCTR3, &[0x01, 0x02], None));
+                push(&mut commands, Command::new(Instruction::VMCTR1, &[0x3C, 0x38], Some(10)));
+                push(&mut commands, Command::new(Instruction::PWCTR6, &[0x11, 0x15], None));
+                push(&mut commands, Command::new(Instruction::GMCTRP1, &gamma_positive, None));
+                push(&mut commands, Command::new(Instruction::GMCTRN1, &gamma_negative, Some(10)));
+                push(&mut commands, Command::new(Instruction::NORON, &[], Some(10)));
+            }
+            Chip::ST7735R => {
+                push(&mut commands, Command::new(Instruction::FRMCTR1, &[0x01, 0x2C, 0x2D], None));
+                push(&mut commands, Command::new(Instruction::FRMCTR2, &[0x01, 0x2C, 0x2D], None));
+                let frame_rate = [0x01, 0x2C, 0x2D, 0x01, 0x2C, 0x2D];
+                push(&mut commands, Command::new(Instruction::FRMCTR3, &frame_rate, None));
+                push(&mut commands, Command::new(Instruction::INVCTR, &[0x07], None));
+                push(&mut commands, Command::new(Instruction::PWCTR1, &[0xA2, 0x02, 0x84], None));
+                push(&mut commands, Command::new(Instruction::PWCTR2, &[0xC5], None));
+                push(&mut commands, Command::new(Instruction::PWCTR3, &[0x0A, 0x00], None));
+                push(&mut commands, Command::new(Instruction::PWCTR4, &[0x8A, 0x2A], None));
+                push(&mut commands, Command::new(Instruction::PWCTR5, &[0x8A, 0xEE], None));
+                push(&mut commands, Command::new(Instruction::VMCTR1, &[0x0E], None));
+            }
+            Chip::ST7735S => {
+                let gamma_positive = [
+                    0x10, 0x0E, 0x02, 0x03, 0x0E, 0x07, 0x02, 0x07, 0x0A, 0x12, 0x27, 0x37, 0x00, 0x0D, 0x0E,
+                    0x10,
+                ];
+                let gamma_negative = [
+                    0x10, 0x0E, 0x03, 0x03, 0x0F, 0x06, 0x02, 0x08, 0x0A, 0x13, 0x26, 0x36, 0x00, 0x0D, 0x0E,
+                    0x10,
+                ];
+
+                push(&mut commands, Command::new(Instruction::FRMCTR1, &[0x05, 0x3A, 0x3A], None));
+                push(&mut commands, Command::new(Instruction::FRMCTR2, &[0x05, 0x3A, 0x3A], None));
+                let frame_rate = [0x05, 0x3A, 0x3A, 0x05, 0x3A, 0x3A];
+                push(&mut commands, Command::new(Instruction::FRMCTR3, &frame_rate, None));
+                push(&mut commands, Command::new(Instruction::INVCTR, &[0x03], None));
+                push(&mut commands, Command::new(Instruction::PWCTR1, &[0x62, 0x02, 0x04], None));
+                push(&mut commands, Command::new(Instruction::PWCTR2, &[0xC0], None));
+                push(&mut commands, Command::new(Instruction::PWCTR3, &[0x0D, 0x00], None));
+                push(&mut commands, Command::new(Instruction::PWCTR4, &[0x8D, 0x6A], None));
+                push(&mut commands, Command::new(Instruction::PWCTR5, &[0x8D, 0xEE], None));
+                push(&mut commands, Command::new(Instruction::VMCTR1, &[0x0E], None));
+                push(&mut commands, Command::new(Instruction::GMCTRP1, &gamma_positive, None));
+                push(&mut commands, Command::new(Instruction::GMCTRN1, &gamma_negative, None));
+            }
+        }
+        commands
+    }
+}
+
+impl Controller for Chip {
+    fn width(&self) -> u16 {
+        match self {
+            Chip::ST7735S => 80,
+            _ => 128,
+        }
+    }
+
+    fn height(&self) -> u16 {
+        160
+    }
+
+    fn memory_height(&self) -> u16 {
+        162
+    }
+
+    fn offset(&self, orientation: Orientation) -> (u16, u16) {
+        // The 80x160 panels show the middle columns of the 132x162 memory, so the offset is the
+        // same with mirrored rows or columns.
+        match (self, orientation) {
+            (Chip::ST7735S, Orientation::Portrait | Orientation::PortraitSwapped) => (26, 1),
+            (Chip::ST7735S, Orientation::Landscape | Orientation::LandScapeSwapped) => (1, 26),
+            _ => (0, 0),
+        }
+    }
+
+    fn init_commands(&self, pixel_format: PixelFormat, timing: &Timing) -> CommandQueue<MAX_INIT_COMMANDS> {
+        let mut commands = CommandQueue::new();
+        push(&mut commands, Command::new(Instruction::SWRESET, &[], Some(timing.software_reset)));
+        push(&mut commands, Command::new(Instruction::SLPOUT, &[], Some(timing.sleep_out)));
+        push(&mut commands, Command::new(Instruction::COLMOD, &[self.colmod(pixel_format)], None));
+        for command in self.tuning_commands().commands() {
+            push(&mut commands, command.clone());
+        }
+        // The 80x160 ST7735S panels are wired with inverted colors.
+        let inversion = match self {
+            Chip::ST7735S => Instruction::INVON,
+            _ => Instruction::INVOFF,
+        };
+        push(&mut commands, Command::new(inversion, &[], None));
+        push(&mut commands, Command::new(Instruction::MADCTL, &[0x00], None));
+        push(&mut commands, Command::new(Instruction::DISPON, &[], None));
+        commands
+    }
+}
+
+/// ST7789 controller. Its memory holds 240x320 pixels, of which panels show all or a square area.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut display = ST7734::with_controller_spi(ST7789::Size240x240, spi, dc, Delay);
+/// display.draw_filled_circle(120, 120, 50, &Color::from_default(DefaultColor::Red));
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ST7789 {
+    /// Square 240x240 panel.
+    Size240x240,
+
+    /// 240x320 panel.
+    Size240x320,
+}
+
+impl Controller for ST7789 {
+    fn width(&self) -> u16 {
+        240
+    }
+
+    fn height(&self) -> u16 {
+        match self {
+            ST7789::Size240x240 => 240,
+            ST7789::Size240x320 => 320,
+        }
+    }
+
+    fn memory_height(&self) -> u16 {
+        320
+    }
+
+    fn offset(&self, orientation: Orientation) -> (u16, u16) {
+        // Square panels show the first 240 memory rows, which end up at the far side of the
+        // memory when the rows are mirrored.
+        match (self, orientation) {
+            (ST7789::Size240x240, Orientation::PortraitSwapped) => (0, 80),
+            (ST7789::Size240x240, Orientation::LandScapeSwapped) => (80, 0),
+            _ => (0, 0),
+        }
+    }
+
+    fn colmod(&self, pixel_format: PixelFormat) -> u8 {
+        // The upper bits select the format of the RGB interface, which is set to match.
+        match pixel_format {
+            PixelFormat::Rgb444 => 0x33,
+            PixelFormat::Rgb565 => 0x55,
+            PixelFormat::Rgb666 => 0x66,
+        }
+    }
+
+    fn init_commands(&self, pixel_format: PixelFormat, timing: &Timing) -> CommandQueue<MAX_INIT_COMMANDS> {
+        let mut commands = CommandQueue::new();
+        push(&mut commands, Command::new(Instruction::SWRESET, &[], Some(timing.software_reset)));
+        push(&mut commands, Command::new(Instruction::SLPOUT, &[], Some(timing.sleep_out)));
+        push(&mut commands, Command::new(Instruction::COLMOD, &[self.colmod(pixel_format)], Some(10)));
+        push(&mut commands, Command::new(Instruction::MADCTL, &[0x00], None));
+        push(&mut commands, Command::new(Instruction::CASET, &[0x00, 0x00, 0x00, 0xEF], None));
+        push(&mut commands, Command::new(Instruction::RASET, &[0x00, 0x00, 0x01, 0x3F], None));
+        // ST7789 panels are wired with inverted colors.
+        push(&mut commands, Command::new(Instruction::INVON, &[], Some(10)));
+        push(&mut commands, Command::new(Instruction::NORON, &[], Some(10)));
+        push(&mut commands, Command::new(Instruction::DISPON, &[], Some(10)));
+        commands
+    }
+}
+
+/// Appends a command to an initialization sequence.
+fn push(commands: &mut CommandQueue<MAX_INIT_COMMANDS>, command: Command) {
+    if commands.push(command).is_err() {
+        panic!("initialization sequence exceeds MAX_INIT_COMMANDS");
+    }
+}
@@ -0,0 +1,71 @@
+//! Hand-off of pixel transfers to DMA.
+//!
+//! Sending a full frame over SPI blocks the CPU for tens of milliseconds. With DMA the transfer
+//! runs in the background: the driver sends the commands that start a memory write, and the
+//! pixel data is then streamed by a [`DmaWriter`](trait.DmaWriter.html) implemented on top of
+//! the DMA driver of the platform. Completion is awaited as a future, so the DMA interrupt can
+//! wake the task that started the transfer.
+//!
+//! Transferred buffers are `'static`, like the transfer buffer of the driver. A
+//! [`Transfer`](struct.Transfer.html) owns its buffer until the transfer completed, so dropping
+//! or forgetting the future early leaves the buffer borrowed instead of letting it be reused
+//! while the DMA engine still reads it.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// SPI transmitter that sends data in the background, typically using DMA.
+///
+/// The data/command pin is set for data by the driver before a transfer is started.
+pub trait DmaWriter {
+    /// Starts sending `length` bytes starting at `data`. The transfer may continue after this
+    /// method returns. The memory stays valid and unchanged until `poll_complete` returned
+    /// `Poll::Ready`.
+    fn start(&mut self, data: *const u8, length: usize);
+
+    /// Returns `Poll::Ready` once the transfer finished. Otherwise `cx.waker()` has to be woken
+    /// when it does, e.g. from the DMA interrupt handler.
+    fn poll_complete(&mut self, cx: &mut Context<'_>) -> Poll<()>;
+}
+
+/// Future resolving to the transferred buffer once a DMA transfer started with `start`
+/// completed.
+///
+/// Dropping the future before it resolved does not stop the transfer. The buffer is not
+/// returned in that case, so it can't be modified while the DMA engine may still read it.
+pub struct Transfer<'a, D> {
+    /// Writer performing the transfer.
+    dma: &'a mut D,
+
+    /// Buffer being transferred, `None` once it was returned.
+    buffer: Option<&'static mut [u8]>,
+}
+
+impl<'a, D: DmaWriter> Transfer<'a, D> {
+    /// Starts transferring the first `length` bytes of `buffer` with `dma`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` exceeds the length of `buffer`.
+    pub fn start(dma: &'a mut D, buffer: &'static mut [u8], length: usize) -> Transfer<'a, D> {
+        let data = &buffer[..length];
+        dma.start(data.as_ptr(), data.len());
+        Transfer {
+            dma,
+            buffer: Some(buffer),
+        }
+    }
+}
+
+impl<'a, D: DmaWriter> Future for Transfer<'a, D> {
+    type Output = &'static mut [u8];
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<&'static mut [u8]> {
+        let transfer = self.get_mut();
+        match transfer.dma.poll_complete(cx) {
+            Poll::Ready(()) => Poll::Ready(transfer.buffer.take().expect("transfer polled after completion")),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,690 @@
+//! Async driver for the embassy runtime.
+//!
+//! This module is available with the `embassy` feature. [`AsyncST7734`](struct.AsyncST7734.html)
+//! talks to the display through the async SPI of embassy HALs and waits with `embassy_time`
+//! timers, so the executor keeps running other tasks during transfers and reset delays.
+//!
+//! The driver either owns an exclusive [`SpiBus`](https://docs.rs/embedded-hal-async/1/embedded_hal_async/spi/trait.SpiBus.html)
+//! and drives the chip select pin itself, see [`ExclusiveBus`](struct.ExclusiveBus.html), or
+//! shares the bus with other devices through an `embassy_sync` mutex, see
+//! [`SharedDevice`](struct.SharedDevice.html).
+//!
+//! Embassy tasks can't be generic, so the ready-made task loop [`run`](fn.run.html) is called
+//! from a task defined by the application. Other tasks send [`DrawOp`](../queue/enum.DrawOp.html)s
+//! to it over a channel.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! static DRAW: Channel<CriticalSectionRawMutex, DrawOp, 16> = Channel::new();
+//!
+//! #[embassy_executor::task]
+//! async fn display_task(spi: Spi<'static, Async>, dc: Output<'static>, rst: Output<'static>) {
+//!     let mut display = AsyncST7734::new(ST7735, spi, dc, Some(rst));
+//!     display.init().await.unwrap();
+//!     let error = st7735::embassy::run(&mut display, DRAW.receiver()).await;
+//!     panic!("display failed: {:?}", error);
+//! }
+//!
+//! // In any other task:
+//! DRAW.send(DrawOp::FillScreen { color: Color::from_default(DefaultColor::Blue) }).await;
+//! ```
+//!
+//! On a bus shared with other devices, e.g. an SD card, the display is created from the mutex
+//! the bus is shared with, which the other devices use with the `SpiDevice` of
+//! `embassy_embedded_hal`:
+//!
+//! ```ignore
+//! static SPI_BUS: StaticCell<Mutex<CriticalSectionRawMutex, Spi<'static, Async>>> = StaticCell::new();
+//!
+//! let bus = SPI_BUS.init(Mutex::new(spi));
+//! let sd_card = SdCard::new(SpiDevice::new(bus, sd_cs), Delay);
+//! let mut display = AsyncST7734::new_with_device(ST7735, bus, display_cs, dc, Some(rst));
+//! ```
+
+use crate::circle_columns;
+use crate::color::Color;
+use crate::command::{Command, Instruction};
+use crate::controller::{Controller, ST7735};
+use crate::error::Error;
+use crate::geometry::Rect;
+use crate::image::Image;
+use crate::pixel_format::{ByteOrder, PixelEncoder, PixelFormat};
+use crate::queue::DrawOp;
+use crate::timing::Timing;
+use crate::Orientation;
+
+use alloc::vec::Vec;
+use core::cmp::min;
+use core::convert::Infallible;
+use embassy_sync::blocking_mutex::raw::RawMutex;
+use embassy_sync::channel::Receiver;
+use embassy_sync::mutex::{Mutex, MutexGuard};
+use embassy_time::Timer;
+use embedded_hal_1::digital::{ErrorType, OutputPin};
+use embedded_hal_async::spi::SpiBus;
+use num::integer::sqrt;
+
+/// Maximum number of pixels encoded for a single SPI transfer.
+const CHUNK_PIXELS: usize = 512;
+
+/// Error of an `AsyncST7734` using the SPI connection `SPI` and data/command pin `DC`.
+pub type AsyncDisplayError<SPI, DC> = Error<<SPI as SpiInterface>::Error, <DC as ErrorType>::Error>;
+
+/// SPI connection of an `AsyncST7734`.
+///
+/// Every command and pixel stream is sent between a call to `select` and `deselect`. The
+/// data/command pin is driven by the driver.
+#[allow(async_fn_in_trait)]
+pub trait SpiInterface {
+    /// Error of the connection.
+    type Error;
+
+    /// Starts a transfer.
+    async fn select(&mut self) -> Result<(), Self::Error>;
+
+    /// Writes bytes to the display and waits until they were sent, so that the data/command pin
+    /// can change afterwards.
+    async fn write(&mut self, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Ends the transfer.
+    async fn deselect(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Error of an `ExclusiveBus` or `SharedDevice`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BusError<BUS, CS> {
+    /// Writing to the SPI bus failed.
+    Spi(BUS),
+
+    /// Setting the chip select pin failed.
+    Cs(CS),
+}
+
+/// Exclusive SPI bus. The driver drives the chip select pin, so each command is sent together
+/// with its arguments and each pixel stream with a single CS assertion.
+pub struct ExclusiveBus<BUS, CS = NoCs> {
+    /// SPI bus.
+    bus: BUS,
+
+    /// Chip select pin, active low.
+    cs: CS,
+}
+
+impl<BUS, CS> ExclusiveBus<BUS, CS> {
+    /// Releases the bus and chip select pin.
+    pub fn release(self) -> (BUS, CS) {
+        (self.bus, self.cs)
+    }
+}
+
+impl<BUS, CS> SpiInterface for ExclusiveBus<BUS, CS>
+where
+    BUS: SpiBus<u8>,
+    CS: OutputPin,
+{
+    type Error = BusError<BUS::Error, CS::Error>;
+
+    async fn select(&mut self) -> Result<(), Self::Error> {
+        self.cs.set_low().map_err(BusError::Cs)
+    }
+
+    async fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.bus.write(data).await.map_err(BusError::Spi)?;
+        self.bus.flush().await.map_err(BusError::Spi)
+    }
+
+    async fn deselect(&mut self) -> Result<(), Self::Error> {
+        self.cs.set_high().map_err(BusError::Cs)
+    }
+}
+
+/// Chip select pin of a display that is tied low.
+pub struct NoCs;
+
+impl ErrorType for NoCs {
+    type Error = Infallible;
+}
+
+impl OutputPin for NoCs {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// Device on an SPI bus shared with other devices through an `embassy_sync` mutex, in the same
+/// way as the `SpiDevice` of `embassy_embedded_hal`.
+///
+/// The bus is locked from `select` to `deselect`, so a command or the address window, memory
+/// write command and all pixel chunks of a drawing operation are sent without transfers to other
+/// devices in between. The bus is free for other devices between two operations.
+pub struct SharedDevice<'a, M: RawMutex, BUS, CS> {
+    /// Shared SPI bus.
+    bus: &'a Mutex<M, BUS>,
+
+    /// Chip select pin, active low.
+    cs: CS,
+
+    /// Lock on the bus while the display is selected.
+    guard: Option<MutexGuard<'a, M, BUS>>,
+}
+
+impl<'a, M: RawMutex, BUS, CS> SharedDevice<'a, M, BUS, CS> {
+    /// Releases the bus and chip select pin.
+    pub fn release(self) -> (&'a Mutex<M, BUS>, CS) {
+        (self.bus, self.cs)
+    }
+}
+
+impl<'a, M, BUS, CS> SpiInterface for SharedDevice<'a, M, BUS, CS>
+where
+    M: RawMutex,
+    BUS: SpiBus<u8>,
+    CS: OutputPin,
+{
+    type Error = BusError<BUS::Error, CS::Error>;
+
+    async fn select(&mut self) -> Result<(), Self::Error> {
+        let guard = self.bus.lock().await;
+        self.cs.set_low().map_err(BusError::Cs)?;
+        self.guard = Some(guard);
+        Ok(())
+    }
+
+    /// Writes bytes to the display. Writes outside of `select` and `deselect` lock the bus for
+    /// the duration of the write.
+    async fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        let mut lock = None;
+        let bus = match self.guard {
+            Some(ref mut guard) => guard,
+            None => lock.insert(self.bus.lock().await),
+        };
+        bus.write(data).await.map_err(BusError::Spi)?;
+        bus.flush().await.map_err(BusError::Spi)
+    }
+
+    async fn deselect(&mut self) -> Result<(), Self::Error> {
+        let result = self.cs.set_high().map_err(BusError::Cs);
+        self.guard = None;
+        result
+    }
+}
+
+/// Async ST7735 driver using embassy SPI and `embassy_time` delays.
+///
+/// Errors of the SPI connection are reported as `Error::Spi`, and errors of the data/command and
+/// reset pin, which have to be of the same type, as `Error::Pin`.
+pub struct AsyncST7734<SPI, DC, RST, C = ST7735> {
+    /// SPI connection.
+    spi: SPI,
+
+    /// Data/command pin.
+    dc: DC,
+
+    /// Reset pin.
+    rst: Option<RST>,
+
+    /// Display controller.
+    controller: C,
+
+    /// Current display orientation.
+    orientation: Orientation,
+
+    /// Reset and initialization delays.
+    timing: Timing,
+
+    /// Format pixels are transferred in.
+    pixel_format: PixelFormat,
+
+    /// Byte order of `Rgb565` pixels.
+    byte_order: ByteOrder,
+}
+
+impl<BUS, DC, RST, C> AsyncST7734<ExclusiveBus<BUS>, DC, RST, C>
+where
+    BUS: SpiBus<u8>,
+    DC: OutputPin,
+    RST: OutputPin<Error = DC::Error>,
+    C: Controller,
+{
+    /// Creates a new driver instance for the given controller on an SPI bus used only by the
+    /// display, whose chip select pin is tied low. The display is initialized with `init`.
+    pub fn new(controller: C, spi: BUS, dc: DC, rst: Option<RST>) -> Self {
+        AsyncST7734::new_with_cs(controller, spi, NoCs, dc, rst)
+    }
+}
+
+impl<BUS, CS, DC, RST, C> AsyncST7734<ExclusiveBus<BUS, CS>, DC, RST, C>
+where
+    BUS: SpiBus<u8>,
+    CS: OutputPin,
+    DC: OutputPin,
+    RST: OutputPin<Error = DC::Error>,
+    C: Controller,
+{
+    /// Creates a new driver instance on an exclusive SPI bus whose chip select pin is driven by
+    /// the driver. The display is deselected by `init`.
+    pub fn new_with_cs(controller: C, spi: BUS, cs: CS, dc: DC, rst: Option<RST>) -> Self {
+        AsyncST7734::with_interface(controller, ExclusiveBus { bus: spi, cs }, dc, rst)
+    }
+}
+
+impl<'a, M, BUS, CS, DC, RST, C> AsyncST7734<SharedDevice<'a, M, BUS, CS>, DC, RST, C>
+where
+    M: RawMutex,
+    BUS: SpiBus<u8>,
+    CS: OutputPin,
+    DC: OutputPin,
+    RST: OutputPin<Error = DC::Error>,
+    C: Controller,
+{
+    /// Creates a new driver instance for a device on an SPI bus shared through `bus`, whose chip
+    /// select pin is driven by the driver. The display is deselected by `init`.
+    pub fn new_with_device(controller: C, bus: &'a Mutex<M, BUS>, cs: CS, dc: DC, rst: Option<RST>) -> Self {
+        let device = SharedDevice { bus, cs, guard: None };
+        AsyncST7734::with_interface(controller, device, dc, rst)
+    }
+}
+
+impl<SPI, DC, RST, C> AsyncST7734<SPI, DC, RST, C>
+where
+    SPI: SpiInterface,
+    DC: OutputPin,
+    RST: OutputPin<Error = DC::Error>,
+    C: Controller,
+{
+    /// Creates a new driver instance using a custom SPI connection.
+    pub fn with_interface(controller: C, spi: SPI, dc: DC, rst: Option<RST>) -> Self {
+        AsyncST7734 {
+            spi,
+            dc,
+            rst,
+            controller,
+            orientation: Orientation::Portrait,
+            timing: Timing::default(),
+            pixel_format: PixelFormat::Rgb565,
+            byte_order: ByteOrder::BigEndian,
+        }
+    }
+
+    /// Releases the SPI connection and pins.
+    pub fn release(self) -> (SPI, DC, Option<RST>) {
+        (self.spi, self.dc, self.rst)
+    }
+
+    /// Resets the display and sends the initialization sequence of the controller.
+    pub async fn init(&mut self) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        self.spi.deselect().await.map_err(Error::Spi)?;
+        self.hard_reset().await?;
+        let init_commands = self.controller.init_commands(self.pixel_format, &self.timing);
+        for command in init_commands.commands() {
+            self.execute_command(command).await?;
+        }
+        Ok(())
+    }
+
+    /// Resets the display using the rst pin, if there is one.
+    pub async fn hard_reset(&mut self) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        if let Some(ref mut rst) = self.rst {
+            rst.set_high().map_err(Error::Pin)?;
+            Timer::after_millis(1).await;
+            rst.set_low().map_err(Error::Pin)?;
+            Timer::after_millis(self.timing.reset_pulse).await;
+            rst.set_high().map_err(Error::Pin)?;
+            Timer::after_millis(self.timing.reset_wait).await;
+        }
+        Ok(())
+    }
+
+    /// Changes the delays used by `init` and `hard_reset`.
+    pub fn set_timing(&mut self, timing: Timing) {
+        self.timing = timing;
+    }
+
+    /// Changes the byte order of `Rgb565` pixels.
+    pub fn set_byte_order(&mut self, byte_order: ByteOrder) {
+        self.byte_order = byte_order;
+    }
+
+    /// Sends a single command to the display.
+    pub async fn execute_command(&mut self, cmd: &Command) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        self.spi.select().await.map_err(Error::Spi)?;
+        let result = self.send_command(cmd).await;
+        self.end_transfer(result).await?;
+
+        if let Some(d) = cmd.delay {
+            Timer::after_millis(d).await;
+        }
+        Ok(())
+    }
+
+    /// Changes the display orientation.
+    pub async fn set_orientation(
+        &mut self,
+        orientation: &Orientation,
+    ) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        let command = Command::new(Instruction::MADCTL, &[self.controller.madctl(*orientation)], None);
+        self.execute_command(&command).await?;
+        self.orientation = *orientation;
+        Ok(())
+    }
+
+    /// Width of the display in the current orientation.
+    pub fn width(&self) -> u16 {
+        match self.orientation {
+            Orientation::Portrait | Orientation::PortraitSwapped => self.controller.width(),
+            Orientation::Landscape | Orientation::LandScapeSwapped => self.controller.height(),
+        }
+    }
+
+    /// Height of the display in the current orientation.
+    pub fn height(&self) -> u16 {
+        match self.orientation {
+            Orientation::Portrait | Orientation::PortraitSwapped => self.controller.height(),
+            Orientation::Landscape | Orientation::LandScapeSwapped => self.controller.width(),
+        }
+    }
+
+    /// Area covering the whole display in the current orientation.
+    pub fn bounds(&self) -> Rect {
+        Rect::new(0, 0, self.width() - 1, self.height() - 1)
+    }
+
+    /// Writes an instruction byte with the data/command pin low and sets the pin for data
+    /// afterwards.
+    async fn write_instruction(
+        &mut self,
+        instruction: Instruction,
+    ) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        self.dc.set_low().map_err(Error::Pin)?;
+        self.spi.write(&[num::ToPrimitive::to_u8(&instruction).unwrap()]).await.map_err(Error::Spi)?;
+        self.dc.set_high().map_err(Error::Pin)
+    }
+
+    /// Writes a command and its arguments to the selected display.
+    async fn send_command(&mut self, cmd: &Command) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        self.write_instruction(cmd.instruction).await?;
+        if !cmd.arguments.is_empty() {
+            self.spi.write(&cmd.arguments).await.map_err(Error::Spi)?;
+        }
+        Ok(())
+    }
+
+    /// Deselects the display after a transfer that ended with `result`. The display is deselected
+    /// even if the transfer failed, and the first error is returned.
+    async fn end_transfer(
+        &mut self,
+        result: Result<(), AsyncDisplayError<SPI, DC>>,
+    ) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        let deselected = self.spi.deselect().await.map_err(Error::Spi);
+        result.and(deselected)
+    }
+
+    /// Sends the commands that start writing to `area` of the display memory to the selected
+    /// display.
+    async fn begin_memory_write(&mut self, area: &Rect) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        let (x_offset, y_offset) = self.controller.offset(self.orientation);
+        let x0 = (area.x0 + x_offset).to_be_bytes();
+        let x1 = (area.x1 + x_offset).to_be_bytes();
+        let y0 = (area.y0 + y_offset).to_be_bytes();
+        let y1 = (area.y1 + y_offset).to_be_bytes();
+        self.send_command(&Command::new(Instruction::CASET, &[x0[0], x0[1], x1[0], x1[1]], None)).await?;
+        self.send_command(&Command::new(Instruction::RASET, &[y0[0], y0[1], y1[0], y1[1]], None)).await?;
+        self.write_instruction(Instruction::RAMWR).await
+    }
+
+    /// Fills `area` with the colors returned by `pixel` for each of its coordinates. The area is
+    /// clipped to the display bounds.
+    pub async fn fill_region<F>(&mut self, area: &Rect, pixel: F) -> Result<(), AsyncDisplayError<SPI, DC>>
+    where
+        F: FnMut(u16, u16) -> Color,
+    {
+        let area = match area.intersection(&self.bounds()) {
+            Some(a) => a,
+            None => return Ok(()),
+        };
+        self.spi.select().await.map_err(Error::Spi)?;
+        let result = self.write_region(&area, pixel).await;
+        self.end_transfer(result).await
+    }
+
+    /// Writes the pixels of `area` to the selected display.
+    async fn write_region<F>(&mut self, area: &Rect, mut pixel: F) -> Result<(), AsyncDisplayError<SPI, DC>>
+    where
+        F: FnMut(u16, u16) -> Color,
+    {
+        self.begin_memory_write(area).await?;
+
+        let mut encoder = PixelEncoder::new(self.pixel_format, self.byte_order);
+        let mut buffer: Vec<u8> = Vec::with_capacity(self.pixel_format.bytes(CHUNK_PIXELS) + 1);
+        let mut count = 0;
+        for y in area.y0..=area.y1 {
+            for x in area.x0..=area.x1 {
+                encoder.push(&pixel(x, y), &mut buffer);
+                count += 1;
+                if count == CHUNK_PIXELS {
+                    self.spi.write(&buffer).await.map_err(Error::Spi)?;
+                    buffer.clear();
+                    count = 0;
+                }
+            }
+        }
+        encoder.finish(&mut buffer);
+        self.spi.write(&buffer).await.map_err(Error::Spi)
+    }
+
+    /// Draws a single pixel. Pixels outside of the display are ignored.
+    pub async fn draw_pixel(
+        &mut self,
+        x: u16,
+        y: u16,
+        color: &Color,
+    ) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        self.fill_region(&Rect::new(x, y, x, y), |_, _| *color).await
+    }
+
+    /// Draws a filled rectangle. The rectangle is clipped to the display bounds.
+    pub async fn draw_filled_rect(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        color: &Color,
+    ) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        let area = match Rect::new(x0, y0, x1, y1).intersection(&self.bounds()) {
+            Some(a) => a,
+            None => return Ok(()),
+        };
+        self.spi.select().await.map_err(Error::Spi)?;
+        let result = self.write_filled_rect(&area, color).await;
+        self.end_transfer(result).await
+    }
+
+    /// Writes `area` filled with `color` to the selected display.
+    async fn write_filled_rect(
+        &mut self,
+        area: &Rect,
+        color: &Color,
+    ) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        self.begin_memory_write(area).await?;
+
+        let total = area.width() as usize * area.height() as usize;
+        let chunk = min(total, CHUNK_PIXELS);
+        let mut encoder = PixelEncoder::new(self.pixel_format, self.byte_order);
+        let mut buffer: Vec<u8> = Vec::with_capacity(self.pixel_format.bytes(chunk));
+        for _ in 0..chunk {
+            encoder.push(color, &mut buffer);
+        }
+        encoder.finish(&mut buffer);
+
+        let mut remaining = total;
+        while remaining > 0 {
+            let length = min(remaining, chunk);
+            self.spi.write(&buffer[..self.pixel_format.bytes(length)]).await.map_err(Error::Spi)?;
+            remaining -= length;
+        }
+        Ok(())
+    }
+
+    /// Draws a rectangle border.
+    pub async fn draw_rect(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        color: &Color,
+    ) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        self.draw_filled_rect(x0, y0, x1, y0, color).await?;
+        self.draw_filled_rect(x0, y1, x1, y1, color).await?;
+        self.draw_filled_rect(x0, y0, x0, y1, color).await?;
+        self.draw_filled_rect(x1, y0, x1, y1, color).await
+    }
+
+    /// Draws a line between the provided coordinates.
+    pub async fn draw_line(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        color: &Color,
+    ) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        if x0 == x1 || y0 == y1 {
+            return self.draw_filled_rect(min(x0, x1), min(y0, y1), x0.max(x1), y0.max(y1), color).await;
+        }
+
+        // Bresenham's line algorithm.
+        let (x1, y1) = (x1 as i32, y1 as i32);
+        let (mut x, mut y) = (x0 as i32, y0 as i32);
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let step_x = if x < x1 { 1 } else { -1 };
+        let step_y = if y < y1 { 1 } else { -1 };
+        let mut error = dx + dy;
+        loop {
+            self.draw_pixel(x as u16, y as u16, color).await?;
+            if x == x1 && y == y1 {
+                return Ok(());
+            }
+
+            let e2 = 2 * error;
+            if e2 >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if e2 <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Draws a circle border around the provided coordinates.
+    pub async fn draw_circle(
+        &mut self,
+        x_pos: u16,
+        y_pos: u16,
+        radius: u16,
+        color: &Color,
+    ) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        let (cx, cy) = (x_pos as i32, y_pos as i32);
+        let x_end = ((core::f32::consts::FRAC_1_SQRT_2 * (radius as f32)) + 1.0) as i32;
+        let r2 = (radius as i32) * (radius as i32);
+        for x in 0..x_end {
+            let y = sqrt(r2 - x * x);
+            let octants = [
+                (cx + x, cy + y),
+                (cx + x, cy - y),
+                (cx - x, cy + y),
+                (cx - x, cy - y),
+                (cx + y, cy + x),
+                (cx + y, cy - x),
+                (cx - y, cy + x),
+                (cx - y, cy - x),
+            ];
+
+            for &(px, py) in octants.iter() {
+                if px >= 0 && py >= 0 && px <= u16::MAX as i32 && py <= u16::MAX as i32 {
+                    self.draw_pixel(px as u16, py as u16, color).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws a filled circle around the provided coordinates.
+    pub async fn draw_filled_circle(
+        &mut self,
+        x_pos: u16,
+        y_pos: u16,
+        radius: u16,
+        color: &Color,
+    ) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        for column in circle_columns(x_pos as i32, y_pos as i32, radius) {
+            self.draw_filled_rect(column.x0, column.y0, column.x1, column.y1, color).await?;
+        }
+        Ok(())
+    }
+
+    /// Draws an image with its top left corner at the provided coordinates.
+    pub async fn draw_image(
+        &mut self,
+        x: u16,
+        y: u16,
+        image: &Image<'_>,
+    ) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        if image.width() == 0 || image.height() == 0 {
+            return Ok(());
+        }
+
+        let area = Rect::new(x, y, x.saturating_add(image.width() - 1), y.saturating_add(image.height() - 1));
+        self.fill_region(&area, |px, py| image.pixel(px - x, py - y)).await
+    }
+
+    /// Fills the whole display with `color`.
+    pub async fn fill_screen(&mut self, color: &Color) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        let bounds = self.bounds();
+        self.draw_filled_rect(bounds.x0, bounds.y0, bounds.x1, bounds.y1, color).await
+    }
+
+    /// Performs a recorded draw operation.
+    pub async fn execute(&mut self, op: &DrawOp) -> Result<(), AsyncDisplayError<SPI, DC>> {
+        match *op {
+            DrawOp::Pixel { x, y, color } => self.draw_pixel(x, y, &color).await,
+            DrawOp::Line { x0, y0, x1, y1, color } => self.draw_line(x0, y0, x1, y1, &color).await,
+            DrawOp::Rect { x0, y0, x1, y1, color } => self.draw_rect(x0, y0, x1, y1, &color).await,
+            DrawOp::FilledRect { x0, y0, x1, y1, color } => self.draw_filled_rect(x0, y0, x1, y1, &color).await,
+            DrawOp::Circle { x, y, radius, color } => self.draw_circle(x, y, radius, &color).await,
+            DrawOp::FilledCircle { x, y, radius, color } => self.draw_filled_circle(x, y, radius, &color).await,
+            DrawOp::FillScreen { color } => self.fill_screen(&color).await,
+        }
+    }
+}
+
+/// Task loop that performs the draw operations received over a channel. It is meant to be the
+/// body of a task owning the display and only returns if a draw operation failed, with the error.
+pub async fn run<SPI, DC, RST, C, M, const N: usize>(
+    display: &mut AsyncST7734<SPI, DC, RST, C>,
+    receiver: Receiver<'_, M, DrawOp, N>,
+) -> AsyncDisplayError<SPI, DC>
+where
+    SPI: SpiInterface,
+    DC: OutputPin,
+    RST: OutputPin<Error = DC::Error>,
+    C: Controller,
+    M: RawMutex,
+{
+    loop {
+        let op = receiver.receive().await;
+        if let Err(error) = display.execute(&op).await {
+            return error;
+        }
+    }
+}
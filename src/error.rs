@@ -0,0 +1,72 @@
+//! Errors reported by the driver.
+//!
+//! Drawing and configuration methods return [`Error`](enum.Error.html), which carries the error
+//! of the SPI bus or output pin that failed, or describes an invalid request. With the `defmt`
+//! feature errors can be logged with `defmt` as well.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! match display.draw_image_region(0, 0, &atlas, &source) {
+//!     Ok(()) => {}
+//!     Err(Error::OutOfBounds) => log("sprite outside of atlas"),
+//!     Err(error) => panic!("display failed: {:?}", error),
+//! }
+//! ```
+
+use core::fmt;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Error of a driver operation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error<SpiE, PinE> {
+    /// Writing to the SPI bus failed.
+    Spi(SpiE),
+
+    /// Setting the data/command, reset, power or software SPI pin failed.
+    Pin(PinE),
+
+    /// Coordinates or an area are outside of the display or image.
+    OutOfBounds,
+
+    /// Data is not in a supported format, e.g. an image that can't be decoded.
+    UnsupportedFormat,
+
+    /// The operation is not possible with the bus the driver uses, e.g. reading from the
+    /// display with hardware SPI.
+    Unsupported,
+
+    /// A buffer needed for the operation is missing or can't hold the data of a single
+    /// transfer.
+    BufferTooSmall,
+}
+
+/// Error of a driver using the bus `SPI` and pins `PIN`.
+pub type DisplayError<SPI, PIN> = Error<<SPI as spi::Write<u8>>::Error, <PIN as OutputPin>::Error>;
+
+impl<SpiE, PinE> fmt::Display for Error<SpiE, PinE>
+where
+    SpiE: fmt::Debug,
+    PinE: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Spi(error) => write!(f, "SPI error: {:?}", error),
+            Error::Pin(error) => write!(f, "pin error: {:?}", error),
+            Error::OutOfBounds => write!(f, "out of bounds"),
+            Error::UnsupportedFormat => write!(f, "unsupported format"),
+            Error::Unsupported => write!(f, "not supported by the bus"),
+            Error::BufferTooSmall => write!(f, "buffer missing or too small"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<SpiE, PinE> std::error::Error for Error<SpiE, PinE>
+where
+    SpiE: fmt::Debug,
+    PinE: fmt::Debug,
+{
+}
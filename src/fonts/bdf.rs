@@ -0,0 +1,304 @@
+//! Compile-time conversion of BDF fonts.
+//!
+//! The [`include_font!`](../../macro.include_font.html) macro embeds a font in the
+//! [Glyph Bitmap Distribution Format](https://en.wikipedia.org/wiki/Glyph_Bitmap_Distribution_Format)
+//! and implements `Font` for it. The file is parsed by `const fn`s while compiling, so only the
+//! converted glyphs end up in the binary and no build script is needed.
+//!
+//! Glyphs are placed in a cell of 5x8 pixels according to the bounding box of the font, e.g.
+//! the `5x7` and `5x8` fonts of X11. Compilation fails if a glyph doesn't fit.
+//!
+//! Only some characters of a font can be embedded to save flash, see the
+//! [`subset`](../subset/index.html) module.
+
+use crate::fonts::font57::Font57;
+use crate::fonts::Font;
+
+/// Number of columns of a glyph.
+pub const GLYPH_WIDTH: usize = 5;
+
+/// Number of rows of a glyph.
+pub const GLYPH_HEIGHT: i32 = 8;
+
+/// Embeds a BDF font and implements `Font` for a new type. The path is relative to the current
+/// file, like `include_str!`. Characters missing from the font are drawn with `Font57`.
+///
+/// Given a string of characters as third argument, only the glyphs of these characters are
+/// embedded and all other characters are drawn blank, see the [`subset`](fonts/subset/index.html)
+/// module.
+///
+/// # Examples
+///
+/// ```ignore
+/// st7735::include_font!(pub Fixed5x7, "../fonts/5x7.bdf");
+/// st7735::include_font!(pub Temperature, "../fonts/5x7.bdf", "0123456789.-°C");
+///
+/// write!(display.text_at(0, 0).with_font(Fixed5x7 {}), "Hello").unwrap();
+/// write!(display.text_at(0, 10).with_font(Temperature {}), "21.5°C").unwrap();
+/// ```
+#[macro_export]
+macro_rules! include_font {
+    ($vis:vis $name:ident, $path:expr, $characters:expr) => {
+        $vis struct $name {}
+
+        const _: () = {
+            const SOURCE: &str = include_str!($path);
+
+            const CHARACTERS: &str = $characters;
+
+            const COUNT: usize = $crate::fonts::bdf::glyph_count(SOURCE);
+
+            const SELECTED: usize = $crate::fonts::subset::char_count(CHARACTERS);
+
+            // Only the selected glyphs are stored, the parsed font is a constant used while
+            // compiling.
+            const FONT: [(char, [u8; $crate::fonts::bdf::GLYPH_WIDTH]); COUNT] =
+                $crate::fonts::bdf::parse(SOURCE);
+
+            static GLYPHS: [(char, [u8; $crate::fonts::bdf::GLYPH_WIDTH]); SELECTED] =
+                $crate::fonts::subset::select(&FONT, CHARACTERS);
+
+            impl $crate::fonts::Font for $name {
+                fn get_char(c: char) -> &'static [u8] {
+                    $crate::fonts::subset::lookup(&GLYPHS, c)
+                }
+            }
+        };
+    };
+    ($vis:vis $name:ident, $path:expr) => {
+        $vis struct $name {}
+
+        const _: () = {
+            const SOURCE: &str = include_str!($path);
+
+            const COUNT: usize = $crate::fonts::bdf::glyph_count(SOURCE);
+
+            static GLYPHS: [(char, [u8; $crate::fonts::bdf::GLYPH_WIDTH]); COUNT] =
+                $crate::fonts::bdf::parse(SOURCE);
+
+            impl $crate::fonts::Font for $name {
+                fn get_char(c: char) -> &'static [u8] {
+                    $crate::fonts::bdf::lookup(&GLYPHS, c)
+                }
+            }
+        };
+    };
+}
+
+/// Returns the columns of `c` from glyphs converted by `parse`, or the glyph of `Font57` if the
+/// font doesn't contain `c`.
+pub fn lookup(glyphs: &'static [(char, [u8; GLYPH_WIDTH])], c: char) -> &'static [u8] {
+    match glyphs.iter().find(|&&(code, _)| code == c) {
+        Some((_, columns)) => columns,
+        None => Font57::get_char(c),
+    }
+}
+
+/// Returns the number of glyphs of a BDF font that are mapped to a character.
+pub const fn glyph_count(source: &str) -> usize {
+    let bytes = source.as_bytes();
+    let mut count = 0;
+    let mut start = 0;
+    while start < bytes.len() {
+        let end = line_end(bytes, start);
+        if keyword(bytes, start, end, b"ENCODING") {
+            let (encoding, _) = parse_int(bytes, start + 8, end);
+            if encoding >= 0 {
+                count += 1;
+            }
+        }
+        start = end + 1;
+    }
+    count
+}
+
+/// Converts the glyphs of a BDF font into columns with the top row in the least significant
+/// bit. `N` has to be the result of `glyph_count`.
+///
+/// # Panics
+///
+/// Panics if the font is malformed or a glyph doesn't fit into 5x8 pixels, which fails the
+/// compilation when called in a constant.
+pub const fn parse<const N: usize>(source: &str) -> [(char, [u8; GLYPH_WIDTH]); N] {
+    let bytes = source.as_bytes();
+    let mut glyphs = [(' ', [0; GLYPH_WIDTH]); N];
+    let mut count = 0;
+
+    // Left edge of the glyph cell and height of the cell above the baseline.
+    let mut font_x = 0;
+    let mut ascent = GLYPH_HEIGHT;
+
+    let mut encoding = -1;
+    let mut bbx = (0, 0, 0, 0);
+    let mut row = -1;
+
+    let mut start = 0;
+    while start < bytes.len() {
+        let end = line_end(bytes, start);
+        if keyword(bytes, start, end, b"FONTBOUNDINGBOX") {
+            let (_, next) = parse_int(bytes, start + 15, end);
+            let (height, next) = parse_int(bytes, next, end);
+            let (x, next) = parse_int(bytes, next, end);
+            let (y, _) = parse_int(bytes, next, end);
+            font_x = x;
+            ascent = height + y;
+        } else if keyword(bytes, start, end, b"ENCODING") {
+            encoding = parse_int(bytes, start + 8, end).0;
+            if encoding >= 0 {
+                glyphs[count].0 = match char::from_u32(encoding as u32) {
+                    Some(c) => c,
+                    None => panic!("invalid glyph encoding"),
+                };
+            }
+        } else if keyword(bytes, start, end, b"BBX") {
+            let (width, next) = parse_int(bytes, start + 3, end);
+            let (height, next) = parse_int(bytes, next, end);
+            let (x, next) = parse_int(bytes, next, end);
+            let (y, _) = parse_int(bytes, next, end);
+            bbx = (width, height, x, y);
+        } else if keyword(bytes, start, end, b"BITMAP") {
+            row = 0;
+        } else if keyword(bytes, start, end, b"ENDCHAR") {
+            if encoding >= 0 {
+                count += 1;
+            }
+            encoding = -1;
+            row = -1;
+        } else if row >= 0 {
+            if encoding >= 0 {
+                let (width, height, x, y) = bbx;
+                let cell_row = ascent - (y + height) + row;
+                let mut bit = 0;
+                while bit < width {
+                    if hex_bit(bytes, start, end, bit) {
+                        let column = x - font_x + bit;
+                        let outside_columns = column < 0 || column >= GLYPH_WIDTH as i32;
+                        if outside_columns || cell_row < 0 || cell_row >= GLYPH_HEIGHT {
+                            panic!("glyph doesn't fit into 5x8 pixels");
+                        }
+                        glyphs[count].1[column as usize] |= 1 << cell_row;
+                    }
+                    bit += 1;
+                }
+            }
+            row += 1;
+        }
+        start = end + 1;
+    }
+
+    if count != N {
+        panic!("glyph count doesn't match the font");
+    }
+    glyphs
+}
+
+/// Returns the index of the line break ending the line that starts at `start`.
+const fn line_end(bytes: &[u8], start: usize) -> usize {
+    let mut end = start;
+    while end < bytes.len() && bytes[end] != b'\n' {
+        end += 1;
+    }
+    end
+}
+
+/// Returns `true` if the line between `start` and `end` starts with `word` followed by a
+/// separator.
+const fn keyword(bytes: &[u8], start: usize, end: usize, word: &[u8]) -> bool {
+    if end - start < word.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < word.len() {
+        if bytes[start + i] != word[i] {
+            return false;
+        }
+        i += 1;
+    }
+    start + i == end || bytes[start + i] == b' ' || bytes[start + i] == b'\r'
+}
+
+/// Parses a decimal number after leading spaces. Returns the number and the position after it.
+const fn parse_int(bytes: &[u8], mut position: usize, end: usize) -> (i32, usize) {
+    while position < end && bytes[position] == b' ' {
+        position += 1;
+    }
+    let negative = position < end && bytes[position] == b'-';
+    if negative {
+        position += 1;
+    }
+    if position == end || !bytes[position].is_ascii_digit() {
+        panic!("malformed number in BDF font");
+    }
+    let mut value = 0;
+    while position < end && bytes[position].is_ascii_digit() {
+        value = value * 10 + (bytes[position] - b'0') as i32;
+        position += 1;
+    }
+    (if negative { -value } else { value }, position)
+}
+
+/// Returns bit `bit` of a bitmap row given as hexadecimal digits, starting at the most
+/// significant bit of the first digit.
+const fn hex_bit(bytes: &[u8], start: usize, end: usize, bit: i32) -> bool {
+    let position = start + bit as usize / 4;
+    if position >= end {
+        panic!("bitmap row is shorter than the glyph");
+    }
+    let digit = match bytes[position] {
+        b @ b'0'..=b'9' => b - b'0',
+        b @ b'A'..=b'F' => b - b'A' + 10,
+        b @ b'a'..=b'f' => b - b'a' + 10,
+        _ => panic!("malformed bitmap row in BDF font"),
+    };
+    digit & (0x8 >> (bit % 4)) != 0
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::fonts::font57;
+
+    /// Font holding the glyph of `A` from `Font57` and an unmapped glyph.
+    const FONT: &str = "STARTFONT 2.1
+FONTBOUNDINGBOX 5 8 0 -1
+CHARS 2
+STARTCHAR A
+ENCODING 65
+BBX 5 7 0 0
+BITMAP
+70
+88
+88
+88
+F8
+88
+88
+ENDCHAR
+STARTCHAR unmapped
+ENCODING -1
+BBX 5 1 0 0
+BITMAP
+F8
+ENDCHAR
+ENDFONT
+";
+
+    #[test]
+    fn parse_converts_glyphs_like_font57() {
+        assert_eq!(glyph_count(FONT), 1);
+        let glyphs: [(char, [u8; GLYPH_WIDTH]); 1] = parse(FONT);
+        assert_eq!(glyphs[0], ('A', font57::glyph('A').unwrap()));
+    }
+
+    #[test]
+    #[should_panic(expected = "malformed number in BDF font")]
+    fn parse_rejects_malformed_numbers() {
+        let _: [(char, [u8; GLYPH_WIDTH]); 1] = parse(&FONT.replace("BBX 5 7 0 0", "BBX 5 x 0 0"));
+    }
+
+    #[test]
+    #[should_panic(expected = "glyph doesn't fit into 5x8 pixels")]
+    fn parse_rejects_wide_glyphs() {
+        let _: [(char, [u8; GLYPH_WIDTH]); 1] = parse(&FONT.replace("BBX 5 7 0 0", "BBX 5 7 1 0"));
+    }
+}
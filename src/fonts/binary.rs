@@ -0,0 +1,303 @@
+//! Fonts loaded from a byte slice at runtime.
+//!
+//! Fonts implementing `Font` are compiled into the firmware. A [`BinaryFont`](struct.BinaryFont.html)
+//! is read from bytes instead, e.g. from an SD card or received over the air, so fonts can be
+//! changed without reflashing. The data is borrowed and not copied, text is drawn with
+//! `ST7734::draw_text_binary`.
+//!
+//! # Layout
+//!
+//! All numbers are little endian.
+//!
+//! | Offset     | Size             | Content                                              |
+//! | ---------- | ---------------- | ---------------------------------------------------- |
+//! | 0          | 4                | Magic bytes `STF1`                                   |
+//! | 4          | 1                | Glyph width in columns                               |
+//! | 5          | 1                | Glyph height in rows, 1 to 32                        |
+//! | 6          | 1                | Horizontal distance between consecutive glyphs       |
+//! | 7          | 1                | Vertical distance between consecutive lines          |
+//! | 8          | 2                | Number of glyphs `n`                                 |
+//! | 10         | 4 `n`            | Code points of the glyphs in ascending order         |
+//! | 10 + 4 `n` | `n` glyph sizes  | Glyphs in the order of the code points               |
+//!
+//! Each glyph is stored column by column from left to right. A column takes `(height + 7) / 8`
+//! bytes, the first one holding the top 8 rows with the top row in the least significant bit,
+//! like the glyphs of `Font`.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let data = sd_card.read_file("fonts/large.stf")?;
+//! let font = BinaryFont::from_bytes(&data).expect("invalid font");
+//!
+//! let white = Color::from_default(DefaultColor::White);
+//! display.draw_text_binary("Hello", 0, 0, &font, &white, None)?;
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::fonts::{Font, CHARACTER_ADVANCE, LINE_ADVANCE};
+
+/// Magic bytes at the start of a font.
+pub const MAGIC: [u8; 4] = *b"STF1";
+
+/// Size of the header before the code points.
+const HEADER_SIZE: usize = 10;
+
+/// Reason a byte slice is not a valid font.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FontError {
+    /// The magic bytes are missing or the glyph size is invalid.
+    InvalidHeader,
+
+    /// The data ends before the last glyph.
+    Truncated,
+
+    /// The code points are not in ascending order or not valid characters.
+    InvalidCharacters,
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FontError::InvalidHeader => write!(f, "invalid font header"),
+            FontError::Truncated => write!(f, "font data is truncated"),
+            FontError::InvalidCharacters => write!(f, "invalid font characters"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FontError {}
+
+/// Font read from bytes in the layout described in the [module documentation](index.html).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BinaryFont<'a> {
+    /// Glyph width in columns.
+    width: u8,
+
+    /// Glyph height in rows.
+    height: u8,
+
+    /// Horizontal distance between consecutive glyphs.
+    advance: u8,
+
+    /// Vertical distance between consecutive lines.
+    line_advance: u8,
+
+    /// Code points, 4 bytes each.
+    characters: &'a [u8],
+
+    /// Glyph data.
+    glyphs: &'a [u8],
+}
+
+impl<'a> BinaryFont<'a> {
+    /// Reads a font from `data`. The header and code points are validated, so drawing with the
+    /// font can't fail. Bytes after the last glyph are ignored.
+    pub fn from_bytes(data: &'a [u8]) -> Result<BinaryFont<'a>, FontError> {
+        if data.len() < HEADER_SIZE {
+            return Err(FontError::Truncated);
+        }
+        if data[0..4] != MAGIC || data[4] == 0 || data[5] == 0 || data[5] > 32 {
+            return Err(FontError::InvalidHeader);
+        }
+        let count = u16::from_le_bytes([data[8], data[9]]) as usize;
+        let glyph_size = data[4] as usize * (data[5] as usize).div_ceil(8);
+        let glyphs_start = HEADER_SIZE + 4 * count;
+        let end = glyphs_start + count * glyph_size;
+        if data.len() < end {
+            return Err(FontError::Truncated);
+        }
+
+        let characters = &data[HEADER_SIZE..glyphs_start];
+        let mut previous = None;
+        for code in characters.chunks_exact(4) {
+            let code = u32::from_le_bytes([code[0], code[1], code[2], code[3]]);
+            if char::from_u32(code).is_none() || matches!(previous, Some(previous) if previous >= code) {
+                return Err(FontError::InvalidCharacters);
+            }
+            previous = Some(code);
+        }
+
+        Ok(BinaryFont {
+            width: data[4],
+            height: data[5],
+            advance: data[6],
+            line_advance: data[7],
+            characters,
+            glyphs: &data[glyphs_start..end],
+        })
+    }
+
+    /// Returns the glyph width in columns.
+    pub fn width(&self) -> u16 {
+        self.width as u16
+    }
+
+    /// Returns the glyph height in rows.
+    pub fn height(&self) -> u16 {
+        self.height as u16
+    }
+
+    /// Returns the horizontal distance between consecutive glyphs.
+    pub fn advance(&self) -> u16 {
+        self.advance as u16
+    }
+
+    /// Returns the vertical distance between consecutive lines.
+    pub fn line_advance(&self) -> u16 {
+        self.line_advance as u16
+    }
+
+    /// Returns the number of glyphs.
+    pub fn len(&self) -> usize {
+        self.characters.len() / 4
+    }
+
+    /// Returns `true` if the font has no glyphs.
+    pub fn is_empty(&self) -> bool {
+        self.characters.is_empty()
+    }
+
+    /// Returns the glyph of `c`, or `None` if the font doesn't contain `c`.
+    pub fn glyph(&self, c: char) -> Option<BinaryGlyph<'a>> {
+        let code = c as u32;
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let middle = (low + high) / 2;
+            let bytes = &self.characters[4 * middle..4 * middle + 4];
+            let candidate = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            if candidate == code {
+                let size = self.glyphs.len() / self.len();
+                return Some(BinaryGlyph {
+                    column_bytes: (self.height as usize).div_ceil(8),
+                    data: &self.glyphs[middle * size..(middle + 1) * size],
+                });
+            } else if candidate < code {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        None
+    }
+
+    /// Returns the width in pixels of the longest line of `text`, including the spacing after
+    /// the last character.
+    pub fn text_width(&self, text: &str) -> u16 {
+        let characters = text.split('\n').map(|line| line.chars().count()).max().unwrap_or(0);
+        characters as u16 * self.advance()
+    }
+}
+
+/// Glyph of a `BinaryFont`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BinaryGlyph<'a> {
+    /// Number of bytes of a column.
+    column_bytes: usize,
+
+    /// Columns of the glyph.
+    data: &'a [u8],
+}
+
+impl<'a> BinaryGlyph<'a> {
+    /// Returns `true` if the pixel at the given glyph coordinates is set. Coordinates outside
+    /// of the glyph are not set.
+    pub fn is_set(&self, x: u16, y: u16) -> bool {
+        if y as usize >= 8 * self.column_bytes {
+            return false;
+        }
+        let index = x as usize * self.column_bytes + y as usize / 8;
+        index < self.data.len() && self.data[index] & (1 << (y % 8)) != 0
+    }
+}
+
+/// Converts the given characters of a compiled font into the binary layout, e.g. to store a
+/// `Font57` subset on an SD card. Characters may be given in any order.
+///
+/// # Examples
+///
+/// ```ignore
+/// let data = encode::<Font57>("0123456789");
+/// std::fs::write("digits.stf", &data)?;
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode<F: Font>(characters: &str) -> Vec<u8> {
+    let mut characters: Vec<char> = characters.chars().collect();
+    characters.sort_unstable();
+    characters.dedup();
+
+    let mut data = Vec::with_capacity(HEADER_SIZE + characters.len() * 9);
+    data.extend_from_slice(&MAGIC);
+    data.extend_from_slice(&[5, 7, CHARACTER_ADVANCE as u8, LINE_ADVANCE as u8]);
+    data.extend_from_slice(&(characters.len() as u16).to_le_bytes());
+    for &c in &characters {
+        data.extend_from_slice(&(c as u32).to_le_bytes());
+    }
+    for &c in &characters {
+        data.extend_from_slice(&F::get_char(c)[..5]);
+    }
+    data
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::fonts::font57::{self, Font57};
+
+    #[test]
+    fn encoded_font_matches_font57() {
+        let data = encode::<Font57>("10A");
+        let font = BinaryFont::from_bytes(&data).unwrap();
+        assert_eq!(font.len(), 3);
+        assert_eq!(font.advance(), CHARACTER_ADVANCE);
+
+        for c in "01A".chars() {
+            let glyph = font.glyph(c).unwrap();
+            let columns = font57::glyph(c).unwrap();
+            for x in 0..5 {
+                for y in 0..8 {
+                    assert_eq!(glyph.is_set(x, y), columns[x as usize] & (1 << y) != 0);
+                }
+            }
+        }
+        assert!(font.glyph('B').is_none());
+    }
+
+    #[test]
+    fn truncated_header_is_rejected() {
+        let data = encode::<Font57>("0");
+        assert_eq!(BinaryFont::from_bytes(&data[..HEADER_SIZE - 1]), Err(FontError::Truncated));
+        assert_eq!(BinaryFont::from_bytes(&[]), Err(FontError::Truncated));
+    }
+
+    #[test]
+    fn glyph_table_beyond_the_data_is_rejected() {
+        let mut data = encode::<Font57>("01");
+        data[8] = 3;
+        assert_eq!(BinaryFont::from_bytes(&data), Err(FontError::Truncated));
+
+        data[8] = 0xFF;
+        data[9] = 0xFF;
+        assert_eq!(BinaryFont::from_bytes(&data), Err(FontError::Truncated));
+    }
+
+    #[test]
+    fn invalid_headers_and_characters_are_rejected() {
+        let mut data = encode::<Font57>("01");
+        data[0] = b'X';
+        assert_eq!(BinaryFont::from_bytes(&data), Err(FontError::InvalidHeader));
+
+        let mut data = encode::<Font57>("01");
+        data[5] = 33;
+        assert_eq!(BinaryFont::from_bytes(&data), Err(FontError::InvalidHeader));
+
+        let mut data = encode::<Font57>("01");
+        data.swap(HEADER_SIZE, HEADER_SIZE + 4);
+        assert_eq!(BinaryFont::from_bytes(&data), Err(FontError::InvalidCharacters));
+    }
+}
@@ -0,0 +1,58 @@
+//! Icon font with common status symbols.
+//!
+//! Icons are glyphs at code points of the Unicode private use area, so they are drawn through
+//! the normal text functions and can be mixed with text. Characters that are not icons are drawn
+//! with `Font57`.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use core::fmt::Write;
+//! use st7735::fonts::icons::{self, Icons};
+//!
+//! write!(display.text_at(100, 0).with_font(Icons {}), "{} 85%", icons::BATTERY_FULL).unwrap();
+//! ```
+
+use crate::fonts::font57::Font57;
+use crate::fonts::Font;
+
+pub const WIFI: char = '\u{E000}';
+pub const BATTERY_EMPTY: char = '\u{E001}';
+pub const BATTERY_HALF: char = '\u{E002}';
+pub const BATTERY_FULL: char = '\u{E003}';
+pub const ARROW_UP: char = '\u{E004}';
+pub const ARROW_DOWN: char = '\u{E005}';
+pub const ARROW_LEFT: char = '\u{E006}';
+pub const ARROW_RIGHT: char = '\u{E007}';
+pub const WARNING: char = '\u{E008}';
+pub const CHECK: char = '\u{E009}';
+pub const CROSS: char = '\u{E00A}';
+pub const BELL: char = '\u{E00B}';
+
+/// Font drawing the icons of this module in 5x7 pixels, and all other characters like `Font57`.
+pub struct Icons {}
+
+impl Font for Icons {
+    fn get_char(c: char) -> &'static [u8] {
+        match ICONS.iter().find(|&&(code, _)| code == c) {
+            Some((_, columns)) => columns,
+            None => Font57::get_char(c),
+        }
+    }
+}
+
+/// Icon glyphs stored as 5 columns with the top row in the least significant bit, like `Font57`.
+static ICONS: &[(char, [u8; 5])] = &[
+    (WIFI, [0x12, 0x09, 0x69, 0x09, 0x12]),
+    (BATTERY_EMPTY, [0x7E, 0x41, 0x41, 0x41, 0x7E]),
+    (BATTERY_HALF, [0x7E, 0x71, 0x71, 0x71, 0x7E]),
+    (BATTERY_FULL, [0x7E, 0x7F, 0x7F, 0x7F, 0x7E]),
+    (ARROW_UP, [0x04, 0x02, 0x7F, 0x02, 0x04]),
+    (ARROW_DOWN, [0x10, 0x20, 0x7F, 0x20, 0x10]),
+    (ARROW_LEFT, [0x08, 0x1C, 0x2A, 0x08, 0x08]),
+    (ARROW_RIGHT, [0x08, 0x08, 0x2A, 0x1C, 0x08]),
+    (WARNING, [0x70, 0x4C, 0x63, 0x4C, 0x70]),
+    (CHECK, [0x08, 0x10, 0x08, 0x04, 0x02]),
+    (CROSS, [0x22, 0x14, 0x08, 0x14, 0x22]),
+    (BELL, [0x10, 0x1E, 0x5F, 0x1E, 0x10]),
+];
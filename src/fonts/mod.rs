@@ -1,8 +1,156 @@
+pub mod bdf;
+pub mod binary;
 pub mod font57;
-use alloc::vec::Vec;
+pub mod icons;
+pub mod subset;
+
+/// Horizontal distance between two consecutive characters of a `Font`, including one column of
+/// spacing.
+pub const CHARACTER_ADVANCE: u16 = 6;
+
+/// Vertical distance between two consecutive lines of a `Font`.
+pub const LINE_ADVANCE: u16 = 8;
 
 /// Font trait implemented by fonts that can be used to display text on the display.
 pub trait Font {
-    /// Returns the bit representation of character `c` that can be displayed on the display.
-    fn get_char(c: char) -> Vec<u8>;
+    /// Returns the bit representation of character `c` that can be displayed on the display: one
+    /// byte per column with the top row in the least significant bit. Glyphs are stored in
+    /// static memory, so looking them up doesn't allocate.
+    ///
+    /// Text often comes from formatting, so characters missing from the font have to return a
+    /// replacement glyph instead of panicking.
+    fn get_char(c: char) -> &'static [u8];
+}
+
+/// Returns the width in pixels of `text` drawn with `font` enlarged by `scale`, e.g. by
+/// `TextWriter::with_scale`. The width includes the spacing after the last character, which is
+/// the area filled by `TextWriter::with_background`. For text spanning several lines, the width
+/// of the longest line is returned.
+///
+/// # Examples
+///
+/// ```ignore
+/// use core::fmt::Write;
+///
+/// // Center a label on a 128 pixel wide display.
+/// let x = (128 - text_width("READY", Font57 {}, 2)) / 2;
+/// write!(display.text_at(x, 20).with_scale(2), "READY").unwrap();
+/// ```
+pub fn text_width<F: Font>(text: &str, _font: F, scale: u16) -> u16 {
+    let characters = text.split('\n').map(|line| line.chars().count()).max().unwrap_or(0);
+    characters as u16 * CHARACTER_ADVANCE * scale
+}
+
+/// Returns the height in pixels of a line of text drawn with `font` enlarged by `scale`.
+pub fn text_height<F: Font>(_font: F, scale: u16) -> u16 {
+    LINE_ADVANCE * scale
+}
+
+/// Font trait implemented by anti-aliased fonts whose glyph pixels store a coverage value
+/// instead of a single bit.
+pub trait AlphaFont {
+    /// Returns the glyph of character `c`.
+    fn get_glyph(c: char) -> AlphaGlyph;
+
+    /// Returns the adjustment in pixels added to the advance of `left` when it is followed by
+    /// `right`, usually negative to move pairs like "AV" closer together. Fonts without kerning
+    /// keep the default, which is optimized away.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// static KERNING: [KerningPair; 2] = [
+    ///     KerningPair { left: 'A', right: 'V', adjustment: -2 },
+    ///     KerningPair { left: 'T', right: 'o', adjustment: -1 },
+    /// ];
+    ///
+    /// impl AlphaFont for MyAlphaFont {
+    ///     fn get_glyph(c: char) -> AlphaGlyph { ... }
+    ///
+    ///     fn kerning(left: char, right: char) -> i16 {
+    ///         kerning_lookup(&KERNING, left, right)
+    ///     }
+    /// }
+    /// ```
+    fn kerning(_left: char, _right: char) -> i16 {
+        0
+    }
+}
+
+/// Kerning adjustment of a pair of characters.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct KerningPair {
+    /// Character on the left.
+    pub left: char,
+
+    /// Character on the right.
+    pub right: char,
+
+    /// Pixels added to the advance of `left`.
+    pub adjustment: i8,
+}
+
+/// Returns the adjustment of a pair from a kerning table sorted by `left` and then `right`, or
+/// 0 if the table doesn't contain the pair.
+pub fn kerning_lookup(table: &[KerningPair], left: char, right: char) -> i16 {
+    match table.binary_search_by(|pair| (pair.left, pair.right).cmp(&(left, right))) {
+        Ok(index) => table[index].adjustment as i16,
+        Err(_) => 0,
+    }
+}
+
+/// Returns the width in pixels of `text` drawn with the anti-aliased `font`, including kerning.
+/// This is the distance from the first glyph to where a following glyph would be placed.
+pub fn text_width_antialiased<F: AlphaFont>(text: &str, _font: F) -> u16 {
+    let mut width = 0i32;
+    let mut previous = None;
+    for c in text.chars() {
+        if let Some(previous) = previous {
+            width += F::kerning(previous, c) as i32;
+        }
+        width = width.max(0) + F::get_glyph(c).advance as i32;
+        previous = Some(c);
+    }
+    width as u16
+}
+
+/// Anti-aliased glyph. Pixels are stored row by row with `bits_per_pixel` (1, 2, 4 or 8) bits each,
+/// starting at the most significant bit of every byte. Rows start at a byte boundary.
+pub struct AlphaGlyph {
+    /// Width in pixels.
+    pub width: u16,
+
+    /// Height in pixels.
+    pub height: u16,
+
+    /// Horizontal distance to the next glyph.
+    pub advance: u16,
+
+    /// Number of bits storing the coverage of a single pixel.
+    pub bits_per_pixel: u8,
+
+    /// Packed coverage values.
+    pub data: &'static [u8],
+}
+
+impl AlphaGlyph {
+    /// Returns the coverage of the pixel at the given glyph coordinates scaled to `0..=255`.
+    /// Pixels outside of `data` and glyphs with `bits_per_pixel` other than 1, 2, 4 or 8 have no
+    /// coverage.
+    pub fn coverage(&self, x: u16, y: u16) -> u8 {
+        let bits = self.bits_per_pixel as usize;
+        if !matches!(bits, 1 | 2 | 4 | 8) {
+            return 0;
+        }
+
+        let row_bytes = (self.width as usize * bits).div_ceil(8);
+        let bit = x as usize * bits;
+        let byte = match self.data.get(y as usize * row_bytes + bit / 8) {
+            Some(&byte) => byte,
+            None => return 0,
+        };
+        let max = (1u16 << bits) - 1;
+        let value = (byte >> (8 - bits - bit % 8)) as u16 & max;
+        (value * 255 / max) as u8
+    }
 }
@@ -0,0 +1,153 @@
+//! Fonts containing only some characters of another font.
+//!
+//! A full font takes up several hundred bytes of flash, while a clock or a sensor readout only
+//! needs digits and a few letters. The [`font_subset!`](../../macro.font_subset.html) macro
+//! copies the glyphs of the chosen characters from `Font57` into a new font while compiling, and
+//! `include_font!` accepts the characters to keep from a BDF font. Binaries that only draw text
+//! with subsets don't contain the full glyph tables.
+//!
+//! Characters missing from a subset are drawn blank rather than with `Font57`, which would pull
+//! in the full font again.
+
+use crate::fonts::bdf::GLYPH_WIDTH;
+use crate::fonts::font57;
+
+/// Glyph drawn for characters missing from a subset.
+static BLANK: [u8; GLYPH_WIDTH] = [0; GLYPH_WIDTH];
+
+/// Embeds the glyphs of the given characters of `Font57` and implements `Font` for a new type.
+/// Compilation fails if `Font57` doesn't contain one of the characters.
+///
+/// # Examples
+///
+/// ```ignore
+/// st7735::font_subset!(pub ClockDigits, "0123456789:");
+///
+/// write!(display.text_at(0, 0).with_font(ClockDigits {}).with_scale(3), "12:45").unwrap();
+/// ```
+#[macro_export]
+macro_rules! font_subset {
+    ($vis:vis $name:ident, $characters:expr) => {
+        $vis struct $name {}
+
+        const _: () = {
+            const CHARACTERS: &str = $characters;
+
+            const COUNT: usize = $crate::fonts::subset::char_count(CHARACTERS);
+
+            static GLYPHS: [(char, [u8; $crate::fonts::bdf::GLYPH_WIDTH]); COUNT] =
+                $crate::fonts::subset::font57(CHARACTERS);
+
+            impl $crate::fonts::Font for $name {
+                fn get_char(c: char) -> &'static [u8] {
+                    $crate::fonts::subset::lookup(&GLYPHS, c)
+                }
+            }
+        };
+    };
+}
+
+/// Returns the number of characters of `characters`.
+pub const fn char_count(characters: &str) -> usize {
+    let bytes = characters.as_bytes();
+    let mut count = 0;
+    let mut position = 0;
+    while position < bytes.len() {
+        position += char_length(bytes[position]);
+        count += 1;
+    }
+    count
+}
+
+/// Copies the glyphs of `characters` from `Font57`. `N` has to be the result of `char_count`.
+///
+/// # Panics
+///
+/// Panics if `Font57` doesn't contain one of the characters, which fails the compilation when
+/// called in a constant.
+pub const fn font57<const N: usize>(characters: &str) -> [(char, [u8; GLYPH_WIDTH]); N] {
+    let bytes = characters.as_bytes();
+    let mut glyphs = [(' ', [0; GLYPH_WIDTH]); N];
+    let mut count = 0;
+    let mut position = 0;
+    while position < bytes.len() {
+        let c = decode(bytes, position);
+        glyphs[count] = match font57::glyph(c) {
+            Some(columns) => (c, columns),
+            None => panic!("character not contained in Font57"),
+        };
+        count += 1;
+        position += char_length(bytes[position]);
+    }
+    glyphs
+}
+
+/// Copies the glyphs of `characters` from a font converted by `bdf::parse`. `N` has to be the
+/// result of `char_count`.
+///
+/// # Panics
+///
+/// Panics if the font doesn't contain one of the characters, which fails the compilation when
+/// called in a constant.
+pub const fn select<const N: usize>(
+    glyphs: &[(char, [u8; GLYPH_WIDTH])],
+    characters: &str,
+) -> [(char, [u8; GLYPH_WIDTH]); N] {
+    let bytes = characters.as_bytes();
+    let mut selected = [(' ', [0; GLYPH_WIDTH]); N];
+    let mut count = 0;
+    let mut position = 0;
+    while position < bytes.len() {
+        let c = decode(bytes, position);
+        let mut index = 0;
+        while index < glyphs.len() && glyphs[index].0 as u32 != c as u32 {
+            index += 1;
+        }
+        if index == glyphs.len() {
+            panic!("character not contained in the font");
+        }
+        selected[count] = glyphs[index];
+        count += 1;
+        position += char_length(bytes[position]);
+    }
+    selected
+}
+
+/// Returns the columns of `c` from the glyphs of a subset, or a blank glyph if the subset
+/// doesn't contain `c`.
+pub fn lookup(glyphs: &'static [(char, [u8; GLYPH_WIDTH])], c: char) -> &'static [u8] {
+    match glyphs.iter().find(|&&(code, _)| code == c) {
+        Some((_, columns)) => columns,
+        None => &BLANK,
+    }
+}
+
+/// Returns the number of bytes of the UTF-8 sequence starting with `byte`.
+const fn char_length(byte: u8) -> usize {
+    match byte {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    }
+}
+
+/// Decodes the character whose UTF-8 sequence starts at `position`.
+const fn decode(bytes: &[u8], position: usize) -> char {
+    let length = char_length(bytes[position]);
+    let mut code = match length {
+        1 => bytes[position] as u32,
+        2 => (bytes[position] & 0x1F) as u32,
+        3 => (bytes[position] & 0x0F) as u32,
+        _ => (bytes[position] & 0x07) as u32,
+    };
+    let mut i = 1;
+    while i < length {
+        code = (code << 6) | (bytes[position + i] & 0x3F) as u32;
+        i += 1;
+    }
+    match char::from_u32(code) {
+        Some(c) => c,
+        None => panic!("invalid character"),
+    }
+}
@@ -0,0 +1,27 @@
+//! In-RAM framebuffers that are drawn into and transferred to the display at once.
+//!
+//! Drawing into a framebuffer avoids flicker and lets shapes overlap freely, at the cost of
+//! keeping the frame in RAM. The variants in this module trade color depth or drawing speed for
+//! a smaller memory footprint, and the memory holding the frame can be chosen with `storage`.
+pub mod mono;
+pub mod rgb;
+pub mod rle;
+pub mod storage;
+
+use embedded_hal::digital::v2::InputPin;
+
+/// Blocks until the tearing effect signal of the display rises, which marks the start of the
+/// vertical blanking period. Tearing effect output must be enabled with
+/// [`ST7734::set_tearing_effect`](../struct.ST7734.html#method.set_tearing_effect).
+///
+/// # Example
+///
+/// ```ignore
+/// display.set_tearing_effect(true);
+/// frame.flush_vsync(&mut display, || framebuffer::wait_for_te(&te_pin).unwrap());
+/// ```
+pub fn wait_for_te<TE: InputPin>(te: &TE) -> Result<(), TE::Error> {
+    while te.is_high()? {}
+    while te.is_low()? {}
+    Ok(())
+}
@@ -0,0 +1,237 @@
+use crate::color::Color;
+#[cfg(feature = "fonts")]
+use crate::fonts::{Font, CHARACTER_ADVANCE, LINE_ADVANCE};
+use crate::geometry::Rect;
+use crate::controller::Controller;
+use crate::error::DisplayError;
+use crate::framebuffer::storage::Storage;
+use crate::ST7734;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Framebuffer storing a single bit per pixel.
+///
+/// Set pixels are shown in the foreground color and cleared pixels in the background color when
+/// the framebuffer is flushed, so a 128x160 frame takes 2.5KB instead of 40KB. Both colors can be
+/// changed at any time without redrawing, for example to invert the screen or switch themes.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut frame = MonoFramebuffer::new(
+///     128,
+///     160,
+///     Color::from_default(DefaultColor::Green),
+///     Color::from_default(DefaultColor::Black),
+/// );
+/// frame.draw_text::<Font57>("READY.", 0, 0);
+/// frame.flush(&mut display);
+/// ```
+pub struct MonoFramebuffer<S = Vec<u8>> {
+    /// Width in pixels.
+    width: u16,
+
+    /// Height in pixels.
+    height: u16,
+
+    /// Pixel bits stored row by row, the most significant bit being the leftmost pixel. Rows
+    /// start at a byte boundary. Only the bytes covering `width * height` pixels are used.
+    bits: S,
+
+    /// Color of set pixels.
+    foreground: Color,
+
+    /// Color of cleared pixels.
+    background: Color,
+}
+
+impl MonoFramebuffer {
+    /// Creates a framebuffer with all pixels cleared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is zero.
+    pub fn new(width: u16, height: u16, foreground: Color, background: Color) -> MonoFramebuffer {
+        let bits = vec![0; byte_length(width, height)];
+        MonoFramebuffer::with_storage(width, height, foreground, background, bits)
+    }
+}
+
+impl<S: Storage<u8>> MonoFramebuffer<S> {
+    /// Creates a framebuffer keeping its pixel bits in `storage` and clears all pixels. Every row
+    /// takes `(width + 7) / 8` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is zero or `storage` holds fewer bytes than needed for
+    /// `width * height` pixels.
+    pub fn with_storage(
+        width: u16,
+        height: u16,
+        foreground: Color,
+        background: Color,
+        storage: S,
+    ) -> MonoFramebuffer<S> {
+        assert!(width > 0 && height > 0);
+        assert!(storage.as_slice().len() >= byte_length(width, height));
+        let mut frame = MonoFramebuffer {
+            width,
+            height,
+            bits: storage,
+            foreground,
+            background,
+        };
+        frame.clear(false);
+        frame
+    }
+
+    /// Returns the storage of the pixel bits.
+    pub fn into_storage(self) -> S {
+        self.bits
+    }
+
+    /// Width in pixels.
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Height in pixels.
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Area covered by the framebuffer.
+    pub fn bounds(&self) -> Rect {
+        Rect::new(0, 0, self.width - 1, self.height - 1)
+    }
+
+    /// Changes the colors set and cleared pixels are shown in.
+    pub fn set_palette(&mut self, foreground: Color, background: Color) {
+        self.foreground = foreground;
+        self.background = background;
+    }
+
+    /// Returns the raw pixel bits.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bits.as_slice()[..byte_length(self.width, self.height)]
+    }
+
+    /// Sets or clears all pixels.
+    pub fn clear(&mut self, on: bool) {
+        let value = if on { 0xFF } else { 0x00 };
+        let length = byte_length(self.width, self.height);
+        for byte in self.bits.as_mut_slice()[..length].iter_mut() {
+            *byte = value;
+        }
+    }
+
+    /// Returns `true` if the pixel at the given coordinates is set.
+    pub fn pixel(&self, x: u16, y: u16) -> bool {
+        let (index, mask) = self.position(x, y);
+        self.bits.as_slice()[index] & mask != 0
+    }
+
+    /// Sets (`on == true`) or clears the pixel at the given coordinates. Pixels outside of the
+    /// framebuffer are ignored.
+    pub fn set_pixel(&mut self, x: u16, y: u16, on: bool) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+
+        let (index, mask) = self.position(x, y);
+        if on {
+            self.bits.as_mut_slice()[index] |= mask;
+        } else {
+            self.bits.as_mut_slice()[index] &= !mask;
+        }
+    }
+
+    /// Sets or clears all pixels of a rectangle. The rectangle is clipped to the framebuffer bounds.
+    pub fn fill_rect(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, on: bool) {
+        if let Some(area) = Rect::new(x0, y0, x1, y1).intersection(&self.bounds()) {
+            for y in area.y0..=area.y1 {
+                for x in area.x0..=area.x1 {
+                    self.set_pixel(x, y, on);
+                }
+            }
+        }
+    }
+
+    /// Draws a character of the given font with its top left corner at the provided coordinates.
+    /// Only the set pixels of the glyph are drawn.
+    #[cfg(feature = "fonts")]
+    pub fn draw_character<F: Font>(&mut self, c: char, x: u16, y: u16) {
+        for (column, bits) in F::get_char(c).iter().enumerate() {
+            for row in 0..8 {
+                if bits & (1 << row) != 0 {
+                    self.set_pixel(x.saturating_add(column as u16), y.saturating_add(row), true);
+                }
+            }
+        }
+    }
+
+    /// Draws a string with its top left corner at the provided coordinates. A `\n` moves to the
+    /// start of the next line.
+    #[cfg(feature = "fonts")]
+    pub fn draw_text<F: Font>(&mut self, text: &str, x: u16, y: u16) {
+        let (mut cx, mut cy) = (x, y);
+        for c in text.chars() {
+            if c == '\n' {
+                cx = x;
+                cy = cy.saturating_add(LINE_ADVANCE);
+            } else {
+                self.draw_character::<F>(c, cx, cy);
+                cx = cx.saturating_add(CHARACTER_ADVANCE);
+            }
+        }
+    }
+
+    /// Transfers the framebuffer to the display with its top left corner at the display origin,
+    /// expanding each bit to the foreground or background color.
+    pub fn flush<SPI, PIN, DELAY, C>(
+        &self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        display.fill_region(&self.bounds(), |x, y| {
+            if self.pixel(x, y) {
+                self.foreground
+            } else {
+                self.background
+            }
+        })
+    }
+
+    /// Returns the byte index and bit mask of the given coordinates.
+    fn position(&self, x: u16, y: u16) -> (usize, u8) {
+        let stride = (self.width as usize).div_ceil(8);
+        (y as usize * stride + x as usize / 8, 0x80 >> (x % 8))
+    }
+}
+
+/// Number of bytes needed for the pixel bits of a frame.
+fn byte_length(width: u16, height: u16) -> usize {
+    (width as usize).div_ceil(8) * height as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::DefaultColor;
+
+    #[test]
+    #[should_panic]
+    fn rejects_empty_frames() {
+        let black = Color::from_default(DefaultColor::Black);
+        MonoFramebuffer::new(0, 160, black, black);
+    }
+}
@@ -0,0 +1,560 @@
+use crate::color::Color;
+use crate::dma::{DmaWriter, Transfer};
+use crate::error::{DisplayError, Error};
+use crate::framebuffer::storage::Storage;
+#[cfg(feature = "fonts")]
+use crate::fonts::{Font, CHARACTER_ADVANCE, LINE_ADVANCE};
+use crate::geometry::Rect;
+use crate::image::Image;
+use crate::pixel_format::PixelFormat;
+use crate::stats::FrameTimes;
+use crate::tiled::TiledDisplay;
+use crate::controller::Controller;
+use crate::ST7734;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Edge length of the square tiles whose checksums are compared by `flush_changed`.
+pub const TILE_SIZE: u16 = 16;
+
+/// Framebuffer storing every pixel as RGB565, which takes 40KB for a 128x160 frame. The pixels
+/// are kept in a `Vec` unless another [`Storage`](../storage/trait.Storage.html) is provided.
+///
+/// Besides transferring the whole frame, the framebuffer can transfer only the tiles that
+/// changed since the previous flush. A checksum of every `TILE_SIZE` x `TILE_SIZE` tile is kept
+/// and compared, so scattered small updates don't require sending a large dirty bounding box.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut frame = Framebuffer::new(128, 160, Color::from_default(DefaultColor::Black));
+/// loop {
+///     frame.fill_rect(0, 0, 127, 15, &status_color);
+///     frame.set_pixel(cursor_x, cursor_y, &Color::from_default(DefaultColor::White));
+///     frame.flush_changed(&mut display);
+/// }
+/// ```
+pub struct Framebuffer<S = Vec<Color>> {
+    /// Width in pixels.
+    width: u16,
+
+    /// Height in pixels.
+    height: u16,
+
+    /// Pixels stored row by row. Only the first `width * height` elements are used.
+    pixels: S,
+
+    /// Checksums of the tiles as of the last flush, row by row. `None` if the tile has not been
+    /// transferred yet.
+    checksums: Vec<Option<u32>>,
+
+    /// Durations of timed flushes.
+    frame_times: FrameTimes,
+}
+
+impl Framebuffer {
+    /// Creates a framebuffer filled with `color`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is zero.
+    pub fn new(width: u16, height: u16, color: Color) -> Framebuffer {
+        Framebuffer::with_storage(width, height, color, vec![color; width as usize * height as usize])
+    }
+}
+
+impl<S: Storage<Color>> Framebuffer<S> {
+    /// Creates a framebuffer keeping its pixels in `storage` and fills it with `color`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is zero or `storage` holds fewer than `width * height`
+    /// pixels.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use st7735::color::{Color, DefaultColor};
+    /// # use st7735::framebuffer::rgb::Framebuffer;
+    /// let mut pixels = [Color::from_hex(0); 80 * 160];
+    /// let black = Color::from_default(DefaultColor::Black);
+    /// let frame = Framebuffer::with_storage(80, 160, black, &mut pixels[..]);
+    /// ```
+    pub fn with_storage(width: u16, height: u16, color: Color, storage: S) -> Framebuffer<S> {
+        assert!(width > 0 && height > 0);
+        assert!(storage.as_slice().len() >= width as usize * height as usize);
+        let tiles = tiles(width) as usize * tiles(height) as usize;
+        let mut frame = Framebuffer {
+            width,
+            height,
+            pixels: storage,
+            checksums: vec![None; tiles],
+            frame_times: FrameTimes::new(),
+        };
+        frame.clear(&color);
+        frame
+    }
+
+    /// Returns the storage of the pixels.
+    pub fn into_storage(self) -> S {
+        self.pixels
+    }
+
+    /// Width in pixels.
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Height in pixels.
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Area covered by the framebuffer.
+    pub fn bounds(&self) -> Rect {
+        Rect::new(0, 0, self.width - 1, self.height - 1)
+    }
+
+    /// Returns the pixels row by row.
+    pub fn pixels(&self) -> &[Color] {
+        self.buffer()
+    }
+
+    /// Returns the framebuffer as an image, for example to draw it scaled or transformed.
+    pub fn as_image(&self) -> Image<'_> {
+        Image::new(self.buffer(), self.width, self.height)
+    }
+
+    /// Saves the framebuffer content to `path` as PNG if the file name ends with `.png`, and
+    /// as PPM otherwise. Available with the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn save_screenshot<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        crate::screenshot::save_image(path, &self.as_image())
+    }
+
+    /// Fills the whole framebuffer with `color`.
+    pub fn clear(&mut self, color: &Color) {
+        for pixel in self.buffer_mut().iter_mut() {
+            *pixel = *color;
+        }
+    }
+
+    /// Returns the color of the pixel at the given coordinates.
+    pub fn pixel(&self, x: u16, y: u16) -> Color {
+        self.buffer()[self.index(x, y)]
+    }
+
+    /// Sets the pixel at the given coordinates. Pixels outside of the framebuffer are ignored.
+    pub fn set_pixel(&mut self, x: u16, y: u16, color: &Color) {
+        if x < self.width && y < self.height {
+            let index = self.index(x, y);
+            self.buffer_mut()[index] = *color;
+        }
+    }
+
+    /// Draws a filled rectangle with the specified `color`. The rectangle is clipped to the
+    /// framebuffer bounds.
+    pub fn fill_rect(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, color: &Color) {
+        if let Some(area) = Rect::new(x0, y0, x1, y1).intersection(&self.bounds()) {
+            for y in area.y0..=area.y1 {
+                let start = self.index(area.x0, y);
+                let end = self.index(area.x1, y);
+                for pixel in &mut self.buffer_mut()[start..=end] {
+                    *pixel = *color;
+                }
+            }
+        }
+    }
+
+    /// Draws the set pixels of a character of the given font with its top left corner at the
+    /// provided coordinates. The character is clipped to the framebuffer bounds. Characters
+    /// missing from the font are drawn with its replacement glyph.
+    #[cfg(feature = "fonts")]
+    pub fn draw_character<F: Font>(&mut self, c: char, x: u16, y: u16, color: &Color) {
+        for (column, bits) in F::get_char(c).iter().enumerate() {
+            for row in 0..8 {
+                if bits & (1 << row) != 0 {
+                    self.set_pixel(x.saturating_add(column as u16), y.saturating_add(row), color);
+                }
+            }
+        }
+    }
+
+    /// Draws a string with its top left corner at the provided coordinates. A `\n` moves to the
+    /// start of the next line.
+    #[cfg(feature = "fonts")]
+    pub fn draw_text<F: Font>(&mut self, text: &str, x: u16, y: u16, color: &Color) {
+        let (mut cx, mut cy) = (x, y);
+        for c in text.chars() {
+            if c == '\n' {
+                cx = x;
+                cy = cy.saturating_add(LINE_ADVANCE);
+            } else {
+                self.draw_character::<F>(c, cx, cy, color);
+                cx = cx.saturating_add(CHARACTER_ADVANCE);
+            }
+        }
+    }
+
+    /// Draws an image with its top left corner at the provided coordinates. The image is clipped
+    /// to the framebuffer bounds.
+    pub fn draw_image(&mut self, x: u16, y: u16, image: &Image) {
+        if image.width() == 0 || image.height() == 0 {
+            return;
+        }
+
+        let x1 = x.saturating_add(image.width() - 1);
+        let y1 = y.saturating_add(image.height() - 1);
+        if let Some(area) = Rect::new(x, y, x1, y1).intersection(&self.bounds()) {
+            for py in area.y0..=area.y1 {
+                for px in area.x0..=area.x1 {
+                    let index = self.index(px, py);
+                    self.buffer_mut()[index] = image.pixel(px - x, py - y);
+                }
+            }
+        }
+    }
+
+    /// Transfers the whole framebuffer to the display with its top left corner at the display origin.
+    pub fn flush<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let width = self.width as usize;
+        let pixels = self.buffer();
+        display.fill_region(&self.bounds(), |x, y| pixels[y as usize * width + x as usize])?;
+        self.mark_flushed();
+        Ok(())
+    }
+
+    /// Transfers the whole framebuffer to a surface of several panels with its top left corner
+    /// at the logical origin.
+    pub fn flush_tiled<SPI, PIN, DELAY, C, const N: usize>(
+        &mut self,
+        display: &mut TiledDisplay<SPI, PIN, DELAY, C, N>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let width = self.width as usize;
+        let pixels = self.buffer();
+        display.fill_region(&self.bounds(), |x, y| pixels[y as usize * width + x as usize])?;
+        self.mark_flushed();
+        Ok(())
+    }
+
+    /// Transfers the whole framebuffer like `flush` and records how long it took in `frame_times`.
+    /// `now` returns the current time in microseconds.
+    pub fn flush_timed<SPI, PIN, DELAY, C, T>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        now: T,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+        T: FnMut() -> u64,
+    {
+        let mut frame_times = self.frame_times;
+        let result = frame_times.measure(now, || self.flush(display));
+        self.frame_times = frame_times;
+        result
+    }
+
+    /// Returns the statistics of timed flushes.
+    pub fn frame_times(&self) -> &FrameTimes {
+        &self.frame_times
+    }
+
+    /// Discards the statistics of timed flushes.
+    pub fn reset_frame_times(&mut self) {
+        self.frame_times.reset();
+    }
+
+    /// Waits for the vertical blanking period by calling `wait` and then transfers the whole
+    /// framebuffer, so the update doesn't tear while the panel is being refreshed.
+    ///
+    /// `wait` should block until the tearing effect signal rises, e.g. by polling the TE pin with
+    /// [`wait_for_te`](../fn.wait_for_te.html) or waiting for an interrupt.
+    pub fn flush_vsync<SPI, PIN, DELAY, C, W>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        mut wait: W,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+        W: FnMut(),
+    {
+        wait();
+        self.flush(display)
+    }
+
+    /// Transfers the whole framebuffer using DMA while other tasks keep running.
+    ///
+    /// Pixels are encoded into the transfer buffer of the display a few rows at a time and sent
+    /// with `dma`, so the buffer can be much smaller than the frame. The buffer is put back into
+    /// the display afterwards.
+    ///
+    /// Returns `Error::BufferTooSmall` if no transfer buffer is set or it can't hold two rows.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// static mut BUFFER: [u8; 4096] = [0; 4096];
+    ///
+    /// display.set_transfer_buffer(unsafe { &mut BUFFER });
+    /// frame.flush_async(&mut display, &mut spi_dma).await?;
+    /// ```
+    pub async fn flush_async<SPI, PIN, DELAY, C, D>(
+        &self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        dma: &mut D,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+        D: DmaWriter,
+    {
+        let area = self.bounds().intersection(&display.bounds()).ok_or(Error::OutOfBounds)?;
+
+        let mut buffer = display.take_transfer_buffer().ok_or(Error::BufferTooSmall)?;
+        let row_bytes = display.pixel_format().bytes(area.width() as usize);
+        let mut rows = (buffer.len() / row_bytes) as u16;
+        if display.pixel_format() == PixelFormat::Rgb444 && area.width() % 2 == 1 {
+            // Packed pixel pairs must not be split between two transfers.
+            rows -= rows % 2;
+        }
+        if rows == 0 {
+            display.set_transfer_buffer(buffer);
+            return Err(Error::BufferTooSmall);
+        }
+
+        if let Err(error) = display.begin_memory_write(&area) {
+            display.set_transfer_buffer(buffer);
+            return Err(error);
+        }
+        let mut y = area.y0;
+        while y <= area.y1 {
+            let chunk = Rect::new(area.x0, y, area.x1, (y + rows - 1).min(area.y1));
+            let length = self.encode_into(display, &chunk, buffer);
+            buffer = Transfer::start(dma, buffer, length).await;
+            y = chunk.y1 + 1;
+        }
+        display.set_transfer_buffer(buffer);
+        display.end_memory_write()
+    }
+
+    /// Starts transferring the whole framebuffer from the transfer buffer of the display using
+    /// DMA, for integration with custom executors and DMA drivers. The returned future resolves
+    /// to the buffer once the transfer completed. Put it back with `ST7734::set_transfer_buffer`
+    /// and call `ST7734::end_memory_write` to deselect the display.
+    ///
+    /// Returns `Error::BufferTooSmall` if no transfer buffer is set or it can't hold the encoded
+    /// frame, see `PixelFormat::bytes`.
+    pub fn start_flush<'a, SPI, PIN, DELAY, C, D>(
+        &self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        dma: &'a mut D,
+    ) -> Result<Transfer<'a, D>, DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+        D: DmaWriter,
+    {
+        let area = self.bounds().intersection(&display.bounds()).ok_or(Error::OutOfBounds)?;
+        let buffer = display.take_transfer_buffer().ok_or(Error::BufferTooSmall)?;
+        let pixels = area.width() as usize * area.height() as usize;
+        if buffer.len() < display.pixel_format().bytes(pixels) {
+            display.set_transfer_buffer(buffer);
+            return Err(Error::BufferTooSmall);
+        }
+        let length = self.encode_into(display, &area, buffer);
+        if let Err(error) = display.begin_memory_write(&area) {
+            display.set_transfer_buffer(buffer);
+            return Err(error);
+        }
+        Ok(Transfer::start(dma, buffer, length))
+    }
+
+    /// Encodes the pixels of `area` for `display` into `buffer` and returns the number of bytes.
+    fn encode_into<SPI, PIN, DELAY, C>(&self, display: &ST7734<SPI, PIN, DELAY, C>, area: &Rect, buffer: &mut [u8]) -> usize
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        display.encode_region(area, buffer, |x, y| self.pixel(x, y))
+    }
+
+    /// Transfers only the tiles whose content changed since they were last transferred and
+    /// returns the number of transferred tiles. The first call transfers all tiles.
+    pub fn flush_changed<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<usize, DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let width = self.width as usize;
+        let mut transferred = 0;
+
+        for tile in 0..self.checksums.len() {
+            let area = self.tile_area(tile);
+            let checksum = self.checksum(&area);
+            if self.checksums[tile] != Some(checksum) {
+                let pixels = self.buffer();
+                display.fill_region(&area, |x, y| pixels[y as usize * width + x as usize])?;
+                self.checksums[tile] = Some(checksum);
+                transferred += 1;
+            }
+        }
+
+        Ok(transferred)
+    }
+
+    /// Forgets the checksums of all tiles, so the next `flush_changed` transfers the whole frame.
+    /// Needed if the display content was changed without the framebuffer, e.g. after a reset.
+    pub fn invalidate(&mut self) {
+        for checksum in self.checksums.iter_mut() {
+            *checksum = None;
+        }
+    }
+
+    /// Records the checksums of all tiles after the whole frame was transferred.
+    fn mark_flushed(&mut self) {
+        for tile in 0..self.checksums.len() {
+            self.checksums[tile] = Some(self.checksum(&self.tile_area(tile)));
+        }
+    }
+
+    /// Returns the area of the tile with the given index.
+    fn tile_area(&self, tile: usize) -> Rect {
+        let columns = tiles(self.width) as usize;
+        let x0 = (tile % columns) as u16 * TILE_SIZE;
+        let y0 = (tile / columns) as u16 * TILE_SIZE;
+        Rect::new(
+            x0,
+            y0,
+            (x0 + TILE_SIZE - 1).min(self.width - 1),
+            (y0 + TILE_SIZE - 1).min(self.height - 1),
+        )
+    }
+
+    /// Computes the FNV-1a hash of the pixels within `area`.
+    fn checksum(&self, area: &Rect) -> u32 {
+        let mut hash: u32 = 0x811C_9DC5;
+        for y in area.y0..=area.y1 {
+            let start = self.index(area.x0, y);
+            let end = self.index(area.x1, y);
+            for pixel in &self.buffer()[start..=end] {
+                for byte in pixel.to_be_bytes().iter() {
+                    hash ^= *byte as u32;
+                    hash = hash.wrapping_mul(0x0100_0193);
+                }
+            }
+        }
+
+        hash
+    }
+
+    /// Returns the pixels in use, row by row.
+    fn buffer(&self) -> &[Color] {
+        &self.pixels.as_slice()[..self.width as usize * self.height as usize]
+    }
+
+    /// Returns the pixels in use for modification.
+    fn buffer_mut(&mut self) -> &mut [Color] {
+        let length = self.width as usize * self.height as usize;
+        &mut self.pixels.as_mut_slice()[..length]
+    }
+
+    /// Returns the index of the pixel at the given coordinates.
+    fn index(&self, x: u16, y: u16) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+}
+
+/// Number of tiles needed to cover `length` pixels.
+fn tiles(length: u16) -> u16 {
+    (length as u32).div_ceil(TILE_SIZE as u32) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::DefaultColor;
+
+    #[test]
+    fn tiles_cover_partial_edges() {
+        let frame = Framebuffer::new(40, 20, Color::from_default(DefaultColor::Black));
+        assert_eq!(frame.checksums.len(), 3 * 2);
+        assert_eq!(frame.tile_area(2), Rect::new(32, 0, 39, 15));
+        assert_eq!(frame.tile_area(5), Rect::new(32, 16, 39, 19));
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_empty_frames() {
+        Framebuffer::new(0, 0, Color::from_default(DefaultColor::Black));
+    }
+
+    #[test]
+    fn checksums_only_change_with_the_tile_content() {
+        let black = Color::from_default(DefaultColor::Black);
+        let mut frame = Framebuffer::new(40, 20, black);
+        let before: Vec<u32> = (0..6).map(|tile| frame.checksum(&frame.tile_area(tile))).collect();
+
+        frame.set_pixel(39, 19, &Color::from_default(DefaultColor::White));
+        let after: Vec<u32> = (0..6).map(|tile| frame.checksum(&frame.tile_area(tile))).collect();
+        assert_eq!(before[..5], after[..5]);
+        assert_ne!(before[5], after[5]);
+
+        frame.set_pixel(39, 19, &black);
+        assert_eq!(frame.checksum(&frame.tile_area(5)), before[5]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn flush_changed_transfers_changed_tiles() {
+        use crate::testing::Panel;
+
+        let panel = Panel::new(40, 20);
+        let mut display = panel.display();
+        let mut frame = Framebuffer::new(40, 20, Color::from_default(DefaultColor::Navy));
+        assert_eq!(frame.flush_changed(&mut display).unwrap(), 6);
+        assert_eq!(frame.flush_changed(&mut display).unwrap(), 0);
+
+        frame.fill_rect(10, 10, 20, 18, &Color::from_default(DefaultColor::Red));
+        assert_eq!(frame.flush_changed(&mut display).unwrap(), 4);
+        assert_eq!(panel.pixels().pixels(), frame.pixels());
+
+        frame.invalidate();
+        assert_eq!(frame.flush_changed(&mut display).unwrap(), 6);
+    }
+}
@@ -0,0 +1,294 @@
+use crate::color::Color;
+use crate::geometry::Rect;
+use crate::controller::Controller;
+use crate::error::DisplayError;
+use crate::ST7734;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::{max, min};
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Run of consecutive pixels of the same color.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Run {
+    /// Number of pixels.
+    pub length: u16,
+
+    /// Color of all pixels of the run.
+    pub color: Color,
+}
+
+/// Framebuffer storing each row as a list of runs of equally colored pixels.
+///
+/// A row takes 4 bytes per run instead of 2 bytes per pixel, so screens made of flat areas fit
+/// into a fraction of the RAM a raw framebuffer needs. Rows are expanded while they are
+/// transferred to the display. Drawing gets slower and memory usage grows with the number of
+/// color changes per row, which makes this a poor fit for photos and gradients.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut frame = RleFramebuffer::new(128, 160, Color::from_default(DefaultColor::Black));
+/// frame.fill_rect(0, 0, 127, 15, &Color::from_default(DefaultColor::Blue));
+/// frame.fill_rect(10, 40, 117, 60, &Color::from_default(DefaultColor::White));
+/// frame.flush(&mut display);
+/// ```
+pub struct RleFramebuffer {
+    /// Width in pixels.
+    width: u16,
+
+    /// Height in pixels.
+    height: u16,
+
+    /// Runs of each row, covering exactly `width` pixels.
+    rows: Vec<Vec<Run>>,
+}
+
+impl RleFramebuffer {
+    /// Creates a framebuffer filled with `color`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is zero.
+    pub fn new(width: u16, height: u16, color: Color) -> RleFramebuffer {
+        assert!(width > 0 && height > 0);
+        let mut frame = RleFramebuffer {
+            width,
+            height,
+            rows: vec![Vec::new(); height as usize],
+        };
+
+        frame.clear(&color);
+        frame
+    }
+
+    /// Width in pixels.
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Height in pixels.
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Area covered by the framebuffer.
+    pub fn bounds(&self) -> Rect {
+        Rect::new(0, 0, self.width - 1, self.height - 1)
+    }
+
+    /// Returns the runs of row `y`.
+    pub fn row(&self, y: u16) -> &[Run] {
+        &self.rows[y as usize]
+    }
+
+    /// Total number of runs, which determines the memory used by the framebuffer.
+    pub fn run_count(&self) -> usize {
+        self.rows.iter().map(|row| row.len()).sum()
+    }
+
+    /// Fills the whole framebuffer with `color`.
+    pub fn clear(&mut self, color: &Color) {
+        let run = Run {
+            length: self.width,
+            color: *color,
+        };
+
+        for row in self.rows.iter_mut() {
+            row.clear();
+            row.push(run);
+            row.shrink_to_fit();
+        }
+    }
+
+    /// Returns the color of the pixel at the given coordinates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinates are outside of the framebuffer.
+    pub fn pixel(&self, x: u16, y: u16) -> Color {
+        assert!(x < self.width);
+        let mut start = 0;
+        for run in &self.rows[y as usize] {
+            start += run.length;
+            if x < start {
+                return run.color;
+            }
+        }
+
+        unreachable!()
+    }
+
+    /// Sets the pixel at the given coordinates. Pixels outside of the framebuffer are ignored.
+    pub fn set_pixel(&mut self, x: u16, y: u16, color: &Color) {
+        if x < self.width && y < self.height {
+            self.fill_span(y, x, x, *color);
+        }
+    }
+
+    /// Draws a filled rectangle with the specified `color`. The rectangle is clipped to the
+    /// framebuffer bounds.
+    pub fn fill_rect(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, color: &Color) {
+        if let Some(area) = Rect::new(x0, y0, x1, y1).intersection(&self.bounds()) {
+            for y in area.y0..=area.y1 {
+                self.fill_span(y, area.x0, area.x1, *color);
+            }
+        }
+    }
+
+    /// Transfers the framebuffer to the display with its top left corner at the display origin.
+    pub fn flush<SPI, PIN, DELAY, C>(
+        &self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let mut runs = [].iter();
+        let mut color = Color::from_hex(0);
+        let mut remaining = 0;
+
+        // Pixels are requested row by row from the left edge, so the runs of each row can be
+        // expanded while walking through them.
+        display.fill_region(&self.bounds(), |x, y| {
+            if x == 0 {
+                runs = self.rows[y as usize].iter();
+                remaining = 0;
+            }
+            if remaining == 0 {
+                let run = runs.next().unwrap();
+                color = run.color;
+                remaining = run.length;
+            }
+
+            remaining -= 1;
+            color
+        })
+    }
+
+    /// Replaces the pixels `x0` to `x1` of row `y` by a single run of `color`, merging it with
+    /// neighboring runs of the same color.
+    fn fill_span(&mut self, y: u16, x0: u16, x1: u16, color: Color) {
+        let row = &self.rows[y as usize];
+        let mut result = Vec::with_capacity(row.len() + 2);
+
+        let mut start = 0;
+        for run in row {
+            let end = start + run.length;
+            if start < x0 {
+                push_run(&mut result, min(end, x0) - start, run.color);
+            }
+            start = end;
+        }
+
+        push_run(&mut result, x1 - x0 + 1, color);
+
+        let mut start = 0;
+        for run in row {
+            let end = start + run.length;
+            if end > x1 + 1 {
+                push_run(&mut result, end - max(start, x1 + 1), run.color);
+            }
+            start = end;
+        }
+
+        result.shrink_to_fit();
+        self.rows[y as usize] = result;
+    }
+}
+
+/// Appends a run to `row`, extending the last run instead if it has the same color.
+fn push_run(row: &mut Vec<Run>, length: u16, color: Color) {
+    if let Some(last) = row.last_mut() {
+        if last.color == color {
+            last.length += length;
+            return;
+        }
+    }
+
+    row.push(Run { length, color });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::DefaultColor;
+
+    #[test]
+    fn runs_round_trip_drawn_pixels() {
+        let (width, height) = (23, 5);
+        let black = Color::from_default(DefaultColor::Black);
+        let colors = [Color::from_default(DefaultColor::Red), Color::from_default(DefaultColor::Blue), black];
+        let mut frame = RleFramebuffer::new(width, height, black);
+        let mut expected = vec![black; width as usize * height as usize];
+
+        for step in 0..200u16 {
+            let (x, y) = (step * 7 % width, step * 3 % height);
+            let color = colors[step as usize % colors.len()];
+            frame.set_pixel(x, y, &color);
+            expected[y as usize * width as usize + x as usize] = color;
+        }
+        frame.fill_rect(5, 1, 9, 2, &colors[0]);
+        for y in 1..=2 {
+            for x in 5..=9 {
+                expected[y * width as usize + x] = colors[0];
+            }
+        }
+
+        for y in 0..height {
+            let length: u16 = frame.row(y).iter().map(|run| run.length).sum();
+            assert_eq!(length, width);
+            for x in 0..width {
+                assert_eq!(frame.pixel(x, y), expected[y as usize * width as usize + x as usize]);
+            }
+        }
+    }
+
+    #[test]
+    fn equal_neighbours_merge_into_one_run() {
+        let black = Color::from_default(DefaultColor::Black);
+        let red = Color::from_default(DefaultColor::Red);
+        let mut frame = RleFramebuffer::new(10, 1, black);
+        frame.fill_rect(2, 0, 4, 0, &red);
+        frame.fill_rect(5, 0, 6, 0, &red);
+        assert_eq!(frame.row(0), &[
+            Run { length: 2, color: black },
+            Run { length: 5, color: red },
+            Run { length: 3, color: black },
+        ]);
+
+        frame.fill_rect(0, 0, 9, 0, &black);
+        assert_eq!(frame.run_count(), 1);
+    }
+
+    #[test]
+    fn drawing_is_clipped_to_the_frame() {
+        let black = Color::from_default(DefaultColor::Black);
+        let red = Color::from_default(DefaultColor::Red);
+        let mut frame = RleFramebuffer::new(4, 2, black);
+        frame.set_pixel(4, 0, &red);
+        frame.set_pixel(0, 2, &red);
+        assert_eq!(frame.run_count(), 2);
+
+        frame.fill_rect(2, 1, 100, 100, &red);
+        assert_eq!(frame.row(1), &[Run { length: 2, color: black }, Run { length: 2, color: red }]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn pixel_outside_of_the_frame_panics() {
+        RleFramebuffer::new(4, 2, Color::from_default(DefaultColor::Black)).pixel(4, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_empty_frames() {
+        RleFramebuffer::new(4, 0, Color::from_default(DefaultColor::Black));
+    }
+}
@@ -0,0 +1,60 @@
+//! Memory holding the pixels of a framebuffer.
+//!
+//! Framebuffers allocate their pixels on the heap by default. With
+//! [`Framebuffer::with_storage`](../rgb/struct.Framebuffer.html#method.with_storage) and
+//! [`MonoFramebuffer::with_storage`](../mono/struct.MonoFramebuffer.html#method.with_storage)
+//! they use any memory implementing [`Storage`](trait.Storage.html) instead: a `static` array
+//! placed by the linker, a `Vec`, or a slice of externally provided memory like PSRAM.
+//!
+//! # Examples
+//!
+//! ```
+//! # use st7735::color::{Color, DefaultColor};
+//! # use st7735::framebuffer::rgb::Framebuffer;
+//! static mut PIXELS: [Color; 128 * 160] = [Color::from_hex(0); 128 * 160];
+//!
+//! let pixels = unsafe { &mut PIXELS[..] };
+//! let black = Color::from_default(DefaultColor::Black);
+//! let mut frame = Framebuffer::with_storage(128, 160, black, pixels);
+//! ```
+
+use alloc::vec::Vec;
+
+/// Contiguous memory of elements a framebuffer stores its pixels in.
+pub trait Storage<T> {
+    /// Returns the elements.
+    fn as_slice(&self) -> &[T];
+
+    /// Returns the elements for modification.
+    fn as_mut_slice(&mut self) -> &mut [T];
+}
+
+impl<T> Storage<T> for Vec<T> {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, const N: usize> Storage<T> for [T; N] {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T> Storage<T> for &mut [T] {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
@@ -0,0 +1,150 @@
+use core::cmp::{max, min};
+
+/// Rectangular area on the display. Both corners are inclusive.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Rect {
+    /// Left edge.
+    pub x0: u16,
+
+    /// Top edge.
+    pub y0: u16,
+
+    /// Right edge.
+    pub x1: u16,
+
+    /// Bottom edge.
+    pub y1: u16,
+}
+
+impl Rect {
+    /// Creates a rectangle from two corner points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use st7735::geometry::Rect;
+    /// let rect = Rect::new(10, 10, 20, 30);
+    /// ```
+    pub fn new(x0: u16, y0: u16, x1: u16, y1: u16) -> Rect {
+        Rect {
+            x0: min(x0, x1),
+            y0: min(y0, y1),
+            x1: max(x0, x1),
+            y1: max(y0, y1),
+        }
+    }
+
+    /// Creates a rectangle from two corner points given in signed coordinates, cutting off the
+    /// parts with negative coordinates. Returns `None` if nothing remains.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use st7735::geometry::Rect;
+    /// let rect = Rect::clipped(-8, 10, 7, 25);
+    /// assert_eq!(rect, Some(Rect::new(0, 10, 7, 25)));
+    /// ```
+    pub fn clipped(x0: i32, y0: i32, x1: i32, y1: i32) -> Option<Rect> {
+        let (x0, x1) = (min(x0, x1), max(x0, x1));
+        let (y0, y1) = (min(y0, y1), max(y0, y1));
+        let limit = u16::MAX as i32;
+        if x1 < 0 || y1 < 0 || x0 > limit || y0 > limit {
+            return None;
+        }
+
+        Some(Rect {
+            x0: max(x0, 0) as u16,
+            y0: max(y0, 0) as u16,
+            x1: min(x1, limit) as u16,
+            y1: min(y1, limit) as u16,
+        })
+    }
+
+    /// Width of the rectangle in pixels.
+    pub fn width(&self) -> u16 {
+        self.x1 - self.x0 + 1
+    }
+
+    /// Height of the rectangle in pixels.
+    pub fn height(&self) -> u16 {
+        self.y1 - self.y0 + 1
+    }
+
+    /// Returns `true` if the point lies within the rectangle.
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        x >= self.x0 && x <= self.x1 && y >= self.y0 && y <= self.y1
+    }
+
+    /// Returns `true` if both rectangles share at least one pixel.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.x0 <= other.x1 && other.x0 <= self.x1 && self.y0 <= other.y1 && other.y0 <= self.y1
+    }
+
+    /// Returns the area covered by both rectangles, if any.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        if self.intersects(other) {
+            Some(Rect {
+                x0: max(self.x0, other.x0),
+                y0: max(self.y0, other.y0),
+                x1: min(self.x1, other.x1),
+                y1: min(self.y1, other.y1),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            x0: min(self.x0, other.x0),
+            y0: min(self.y0, other.y0),
+            x1: max(self.x1, other.x1),
+            y1: max(self.y1, other.y1),
+        }
+    }
+
+    /// Returns the rectangle shrunk by `amount` pixels on every side, or `None` if nothing remains.
+    pub fn inset(&self, amount: u16) -> Option<Rect> {
+        if self.width() <= amount.saturating_mul(2) || self.height() <= amount.saturating_mul(2) {
+            return None;
+        }
+
+        Some(Rect {
+            x0: self.x0 + amount,
+            y0: self.y0 + amount,
+            x1: self.x1 - amount,
+            y1: self.y1 - amount,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersection_of_overlapping_touching_and_disjoint_rects() {
+        let rect = Rect::new(10, 10, 20, 20);
+        assert_eq!(rect.intersection(&Rect::new(15, 0, 30, 12)), Some(Rect::new(15, 10, 20, 12)));
+        assert_eq!(rect.intersection(&Rect::new(20, 20, 25, 25)), Some(Rect::new(20, 20, 20, 20)));
+        assert_eq!(rect.intersection(&Rect::new(21, 10, 25, 20)), None);
+        assert_eq!(rect.intersection(&Rect::new(0, 0, 40, 40)), Some(rect));
+    }
+
+    #[test]
+    fn clipped_cuts_off_negative_coordinates() {
+        assert_eq!(Rect::clipped(5, 6, -3, 2), Some(Rect::new(0, 2, 5, 6)));
+        assert_eq!(Rect::clipped(-10, -10, -1, 5), None);
+        assert_eq!(Rect::clipped(70_000, 0, 80_000, 1), None);
+        assert_eq!(Rect::clipped(65_000, 0, 80_000, 1), Some(Rect::new(65_000, 0, u16::MAX, 1)));
+    }
+
+    #[test]
+    fn inset_needs_a_remaining_pixel() {
+        let rect = Rect::new(0, 0, 4, 9);
+        assert_eq!(rect.inset(2), Some(Rect::new(2, 2, 2, 7)));
+        assert_eq!(rect.inset(3), None);
+        assert_eq!(rect.inset(u16::MAX), None);
+    }
+}
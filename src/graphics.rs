@@ -0,0 +1,191 @@
+//! Integration with [`embedded-graphics`](https://docs.rs/embedded-graphics).
+//!
+//! This module is available with the `graphics` feature. It implements `DrawTarget` for the
+//! driver, so the display can be used with embedded-graphics primitives, fonts and images as
+//! well as with crates building on top of it such as `embedded-text`, which the `text-box`
+//! feature pulls in. Colors and rectangles convert between the driver and embedded-graphics types
+//! with `From` and `TryFrom`. `Color` converts from and to both `Rgb565` and `Rgb888`, the latter
+//! dropping the low bits of each component.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use embedded_graphics::mono_font::{ascii::FONT_6X10, MonoTextStyle};
+//! use embedded_graphics::pixelcolor::Rgb565;
+//! use embedded_graphics::prelude::*;
+//! use embedded_text::TextBox;
+//!
+//! let style = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+//! let bounds = Rectangle::new(Point::zero(), Size::new(128, 160));
+//! TextBox::new("Long text that is wrapped at the display edge.", bounds, style)
+//!     .draw(&mut display)
+//!     .unwrap();
+//! ```
+
+use crate::color::{Color, DefaultColor};
+use crate::geometry::Rect;
+use crate::controller::Controller;
+use crate::error::DisplayError;
+use crate::ST7734;
+
+use core::convert::TryFrom;
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::{Dimensions, OriginDimensions, Point, Size};
+use embedded_graphics_core::pixelcolor::raw::RawU16;
+use embedded_graphics_core::pixelcolor::{IntoStorage, Rgb565, Rgb888, RgbColor};
+use embedded_graphics_core::primitives::{PointsIter, Rectangle};
+use embedded_graphics_core::Pixel;
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+impl From<Rgb565> for Color {
+    fn from(color: Rgb565) -> Color {
+        Color::from_hex(color.into_storage())
+    }
+}
+
+impl From<Color> for Rgb565 {
+    fn from(color: Color) -> Rgb565 {
+        RawU16::new(color.hex).into()
+    }
+}
+
+impl From<Rgb888> for Color {
+    fn from(color: Rgb888) -> Color {
+        Color::from_rgb888(color.r(), color.g(), color.b())
+    }
+}
+
+impl From<Color> for Rgb888 {
+    fn from(color: Color) -> Rgb888 {
+        let (red, green, blue) = color.to_rgb888();
+        Rgb888::new(red, green, blue)
+    }
+}
+
+impl From<Rect> for Rectangle {
+    fn from(rect: Rect) -> Rectangle {
+        Rectangle::new(
+            Point::new(rect.x0 as i32, rect.y0 as i32),
+            Size::new(rect.width() as u32, rect.height() as u32),
+        )
+    }
+}
+
+/// Error returned when converting a rectangle that is empty or has coordinates outside of the
+/// range of the driver.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct InvalidRectangle;
+
+impl TryFrom<Rectangle> for Rect {
+    type Error = InvalidRectangle;
+
+    fn try_from(area: Rectangle) -> Result<Rect, InvalidRectangle> {
+        let bottom_right = area.bottom_right().ok_or(InvalidRectangle)?;
+        let coordinate = |value: i32| u16::try_from(value).map_err(|_| InvalidRectangle);
+        Ok(Rect::new(
+            coordinate(area.top_left.x)?,
+            coordinate(area.top_left.y)?,
+            coordinate(bottom_right.x)?,
+            coordinate(bottom_right.y)?,
+        ))
+    }
+}
+
+impl<SPI, PIN, DELAY, C> ST7734<SPI, PIN, DELAY, C>
+where
+    SPI: spi::Write<u8>,
+    PIN: OutputPin,
+    DELAY: DelayMs<u64>,
+    C: Controller,
+{
+    /// Fills `area` with the colors returned by `pixel` for each of its points, like `fill_region`
+    /// but using embedded-graphics types. Parts of the area outside of the display are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let area = Rectangle::new(Point::new(-10, 20), Size::new(50, 30));
+    /// display.fill_rectangle(&area, |point| Rgb565::new(point.x as u8 % 32, 0, 31));
+    /// ```
+    pub fn fill_rectangle<F>(&mut self, area: &Rectangle, mut pixel: F) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        F: FnMut(Point) -> Rgb565,
+    {
+        let area = area.intersection(&self.bounding_box());
+        if let Ok(rect) = Rect::try_from(area) {
+            self.fill_region(&rect, |x, y| pixel(Point::new(x as i32, y as i32)).into())?;
+        }
+        Ok(())
+    }
+}
+
+impl<SPI, PIN, DELAY, C> OriginDimensions for ST7734<SPI, PIN, DELAY, C>
+where
+    SPI: spi::Write<u8>,
+    PIN: OutputPin,
+    DELAY: DelayMs<u64>,
+    C: Controller,
+{
+    fn size(&self) -> Size {
+        Size::new(self.width() as u32, self.height() as u32)
+    }
+}
+
+impl<SPI, PIN, DELAY, C> DrawTarget for ST7734<SPI, PIN, DELAY, C>
+where
+    SPI: spi::Write<u8>,
+    PIN: OutputPin,
+    DELAY: DelayMs<u64>,
+    C: Controller,
+{
+    type Color = Rgb565;
+    type Error = DisplayError<SPI, PIN>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounds = self.bounding_box();
+        self.draw_pixels(
+            pixels
+                .into_iter()
+                .filter(|Pixel(point, _)| bounds.contains(*point))
+                .map(|Pixel(point, color)| (point.x as u16, point.y as u16, Color::from(color))),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        if area.intersection(&self.bounding_box()) != *area {
+            return self.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(point, color)| Pixel(point, color)),
+            );
+        }
+
+        if let Ok(rect) = Rect::try_from(*area) {
+            let mut colors = colors.into_iter();
+            self.fill_region(&rect, |_, _| {
+                colors
+                    .next()
+                    .map(Color::from)
+                    .unwrap_or(Color::from_default(DefaultColor::Black))
+            })?;
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        if let Ok(rect) = Rect::try_from(area) {
+            self.draw_filled_rect(rect.x0, rect.y0, rect.x1, rect.y1, &Color::from(color))?;
+        }
+        Ok(())
+    }
+}
+
@@ -0,0 +1,344 @@
+use crate::color::Color;
+use crate::geometry::Rect;
+
+/// Converts 8-bit grayscale levels, e.g. of a camera or a thermal sensor, to RGB565 pixels
+/// using `Color::gray`. Converts as many pixels as both buffers hold.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut pixels = [Color::from_default(DefaultColor::Black); 80 * 60];
+/// grayscale_to_rgb565(&camera.frame(), &mut pixels);
+/// display.draw_image(0, 0, &Image::new(&pixels, 80, 60));
+/// ```
+pub fn grayscale_to_rgb565(levels: &[u8], pixels: &mut [Color]) {
+    for (pixel, &level) in pixels.iter_mut().zip(levels) {
+        *pixel = Color::gray(level);
+    }
+}
+
+/// Image made of RGB565 pixels stored row by row.
+///
+/// The pixel data is borrowed, so images can be kept in flash as `static` arrays. Rows may be
+/// further apart than the image width, which allows images to refer to a region of a larger buffer.
+///
+/// # Examples
+///
+/// ```ignore
+/// static PIXELS: [Color; 4] = [
+///     Color::from_default(DefaultColor::Red),
+///     Color::from_default(DefaultColor::Green),
+///     Color::from_default(DefaultColor::Blue),
+///     Color::from_default(DefaultColor::White),
+/// ];
+///
+/// let image = Image::new(&PIXELS, 2, 2);
+/// display.draw_image(10, 10, &image);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Image<'a> {
+    /// Pixel data.
+    pixels: &'a [Color],
+
+    /// Width in pixels.
+    width: u16,
+
+    /// Height in pixels.
+    height: u16,
+
+    /// Distance between the start of two consecutive rows in pixels.
+    stride: u16,
+}
+
+impl<'a> Image<'a> {
+    /// Creates an image from pixel data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pixels` holds fewer than `width * height` pixels.
+    pub fn new(pixels: &'a [Color], width: u16, height: u16) -> Image<'a> {
+        Image::with_stride(pixels, width, height, width)
+    }
+
+    /// Creates an image from pixel data whose rows are `stride` pixels apart, for example
+    /// a region of a larger buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is smaller than `width` or `pixels` is too short to hold all rows.
+    pub fn with_stride(pixels: &'a [Color], width: u16, height: u16, stride: u16) -> Image<'a> {
+        assert!(stride >= width);
+        assert!(height == 0 || pixels.len() >= (height as usize - 1) * stride as usize + width as usize);
+        Image {
+            pixels,
+            width,
+            height,
+            stride,
+        }
+    }
+
+    /// Returns the part of the image covered by `area` without copying pixel data. Useful for
+    /// drawing sprites from an atlas.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `area` is not within the image.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let atlas = Image::new(&ATLAS, 64, 16);
+    /// let sprite = atlas.sub_image(&Rect::new(16, 0, 31, 15));
+    /// display.draw_image(40, 40, &sprite);
+    /// ```
+    pub fn sub_image(&self, area: &Rect) -> Image<'a> {
+        assert!(area.x1 < self.width && area.y1 < self.height);
+        let start = area.y0 as usize * self.stride as usize + area.x0 as usize;
+        Image::with_stride(&self.pixels[start..], area.width(), area.height(), self.stride)
+    }
+
+    /// Width in pixels.
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Height in pixels.
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Returns the color of the pixel at the given image coordinates.
+    pub fn pixel(&self, x: u16, y: u16) -> Color {
+        self.pixels[y as usize * self.stride as usize + x as usize]
+    }
+
+    /// Returns the size of the image after applying `transform`.
+    pub fn transformed_size(&self, transform: &Transform) -> (u16, u16) {
+        match transform.rotation {
+            Rotation::Deg0 | Rotation::Deg180 => (self.width, self.height),
+            Rotation::Deg90 | Rotation::Deg270 => (self.height, self.width),
+        }
+    }
+
+    /// Returns the color of the pixel at the given coordinates of the image after applying `transform`.
+    pub fn transformed_pixel(&self, transform: &Transform, x: u16, y: u16) -> Color {
+        let (width, height) = self.transformed_size(transform);
+        let x = if transform.flip_horizontal { width - 1 - x } else { x };
+        let y = if transform.flip_vertical { height - 1 - y } else { y };
+
+        match transform.rotation {
+            Rotation::Deg0 => self.pixel(x, y),
+            Rotation::Deg90 => self.pixel(y, self.height - 1 - x),
+            Rotation::Deg180 => self.pixel(self.width - 1 - x, self.height - 1 - y),
+            Rotation::Deg270 => self.pixel(self.width - 1 - y, x),
+        }
+    }
+
+    /// Returns the color of the pixel at the given coordinates of the image scaled to `width` x `height`.
+    pub fn scaled_pixel(&self, filter: Filter, width: u16, height: u16, x: u16, y: u16) -> Color {
+        match filter {
+            Filter::Nearest => self.pixel(
+                ((x as u32) * (self.width as u32) / (width as u32)) as u16,
+                ((y as u32) * (self.height as u32) / (height as u32)) as u16,
+            ),
+            Filter::Bilinear => {
+                // Source coordinates of the pixel center in 24.8 fixed point.
+                let source = |position: u16, source_size: u16, size: u16| -> (u16, u16, u8) {
+                    let center = ((2 * position as i32 + 1) * (source_size as i32) * 128 / (size as i32) - 128).max(0);
+                    let start = (center >> 8) as u16;
+                    let end = (start + 1).min(source_size - 1);
+                    (start, end, (center & 0xFF) as u8)
+                };
+
+                let (x0, x1, fraction_x) = source(x, self.width, width);
+                let (y0, y1, fraction_y) = source(y, self.height, height);
+                let top = self.pixel(x0, y0).blend(&self.pixel(x1, y0), fraction_x);
+                let bottom = self.pixel(x0, y1).blend(&self.pixel(x1, y1), fraction_x);
+                top.blend(&bottom, fraction_y)
+            }
+        }
+    }
+
+    /// Returns the color of the image mapped onto an area of `width` x `height` pixels at the
+    /// given coordinates within that area.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty.
+    pub fn sample(&self, mode: TextureMode, width: u16, height: u16, x: u16, y: u16) -> Color {
+        match mode {
+            TextureMode::Tile => self.pixel(x % self.width, y % self.height),
+            TextureMode::Stretch => self.pixel(
+                ((x as u32) * (self.width as u32) / (width as u32)) as u16,
+                ((y as u32) * (self.height as u32) / (height as u32)) as u16,
+            ),
+        }
+    }
+}
+
+/// Image split into nine slices for drawing frames of any size.
+///
+/// The corners are drawn unscaled, the edges are stretched along one axis and the center is
+/// stretched along both, so buttons, panels and dialog frames of different sizes can be drawn
+/// from one small image.
+///
+/// # Examples
+///
+/// ```ignore
+/// // 12x12 button image with 4 pixel corners.
+/// let button = NinePatch::new(Image::new(&BUTTON, 12, 12), 4, 4, 4, 4);
+/// display.draw_nine_patch(10, 10, 80, 24, &button);
+/// ```
+#[derive(Clone, Copy)]
+pub struct NinePatch<'a> {
+    /// Source image.
+    image: Image<'a>,
+
+    /// Width of the left edge.
+    left: u16,
+
+    /// Height of the top edge.
+    top: u16,
+
+    /// Width of the right edge.
+    right: u16,
+
+    /// Height of the bottom edge.
+    bottom: u16,
+}
+
+impl<'a> NinePatch<'a> {
+    /// Creates a nine-patch from `image` with the given edge sizes in pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the edges leave no center column and row in the image.
+    pub fn new(image: Image<'a>, left: u16, top: u16, right: u16, bottom: u16) -> NinePatch<'a> {
+        assert!((left as u32 + right as u32) < image.width() as u32);
+        assert!((top as u32 + bottom as u32) < image.height() as u32);
+        NinePatch {
+            image,
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Smallest width the nine-patch can be drawn with without cutting off corners.
+    pub fn min_width(&self) -> u16 {
+        self.left + self.right
+    }
+
+    /// Smallest height the nine-patch can be drawn with without cutting off corners.
+    pub fn min_height(&self) -> u16 {
+        self.top + self.bottom
+    }
+
+    /// Returns the color at the given coordinates of the nine-patch drawn with `width` x `height`
+    /// pixels.
+    pub fn pixel(&self, width: u16, height: u16, x: u16, y: u16) -> Color {
+        let x = NinePatch::source(x, width, self.image.width(), self.left, self.right);
+        let y = NinePatch::source(y, height, self.image.height(), self.top, self.bottom);
+        self.image.pixel(x, y)
+    }
+
+    /// Maps a coordinate along one axis of the drawn area to the source image.
+    fn source(position: u16, size: u16, source_size: u16, start: u16, end: u16) -> u16 {
+        if position < start {
+            position
+        } else if size - position <= end {
+            source_size - (size - position)
+        } else {
+            let center = source_size - start - end;
+            let stretched = size.saturating_sub(start + end).max(1);
+            start + ((position - start) as u32 * center as u32 / stretched as u32) as u16
+        }
+    }
+}
+
+/// How an image is mapped onto a shape that is filled with it.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TextureMode {
+    /// The image is repeated, aligned to the top left corner of the shape.
+    Tile,
+
+    /// The image is scaled to the bounding box of the shape.
+    Stretch,
+}
+
+/// Interpolation used when drawing a scaled image.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Filter {
+    /// Picks the closest source pixel. Fast and keeps hard edges, suitable for icons and pixel art.
+    Nearest,
+
+    /// Interpolates between the four closest source pixels. Smoother, but considerably slower.
+    Bilinear,
+}
+
+/// Clockwise rotation applied when drawing an image.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Rotation {
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+/// Rotation and mirroring applied when drawing an image. The image is rotated first and the
+/// rotated image is then flipped.
+///
+/// # Examples
+///
+/// ```ignore
+/// let transform = Transform {
+///     rotation: Rotation::Deg90,
+///     ..Transform::default()
+/// };
+/// display.draw_image_transformed(10, 10, &image, &transform);
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Transform {
+    /// Clockwise rotation.
+    pub rotation: Rotation,
+
+    /// Mirror the image along the vertical axis.
+    pub flip_horizontal: bool,
+
+    /// Mirror the image along the horizontal axis.
+    pub flip_vertical: bool,
+}
+
+impl Default for Transform {
+    fn default() -> Transform {
+        Transform {
+            rotation: Rotation::Deg0,
+            flip_horizontal: false,
+            flip_vertical: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nine_patch_keeps_edges_and_stretches_the_center() {
+        let pixels: [Color; 12] = core::array::from_fn(|i| Color::from_hex(i as u16));
+        let patch = NinePatch::new(Image::new(&pixels, 4, 3), 1, 1, 1, 1);
+        let columns: [u16; 6] = core::array::from_fn(|x| patch.pixel(6, 3, x as u16, 0).hex);
+        assert_eq!(columns, [0, 1, 1, 2, 2, 3]);
+        let rows: [u16; 5] = core::array::from_fn(|y| patch.pixel(4, 5, 0, y as u16).hex);
+        assert_eq!(rows, [0, 4, 4, 4, 8]);
+        assert_eq!(patch.pixel(2, 2, 1, 1).hex, 11);
+    }
+
+    #[test]
+    #[should_panic]
+    fn nine_patch_needs_a_center() {
+        let pixels = [Color::from_hex(0); 9];
+        NinePatch::new(Image::new(&pixels, 3, 3), 2, 1, 1, 1);
+    }
+}
@@ -0,0 +1,154 @@
+//! Navigation input from buttons and rotary encoders.
+//!
+//! Input sources are polled regularly, e.g. from the main loop or a timer interrupt, and report
+//! an [`InputEvent`](enum.InputEvent.html) when something happened. The events are passed on to
+//! the user interface, see `UiState::handle`.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let mut up = Button::new(up_pin, InputEvent::Up);
+//! let mut down = Button::new(down_pin, InputEvent::Down);
+//! let mut knob = RotaryEncoder::new(encoder_a, encoder_b);
+//!
+//! loop {
+//!     for event in [up.poll()?, down.poll()?, knob.poll()?].iter().flatten() {
+//!         state.handle(*event);
+//!     }
+//!     // ...
+//! }
+//! ```
+
+use embedded_hal::digital::v2::InputPin;
+
+/// Navigation event.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum InputEvent {
+    /// Moves to the previous item.
+    Up,
+
+    /// Moves to the next item.
+    Down,
+
+    /// Activates the current item.
+    Select,
+
+    /// Leaves the current screen.
+    Back,
+
+    /// Rotary encoder turned by the given number of detents, positive values clockwise.
+    Encoder(i8),
+}
+
+/// Push button reporting an event when it is pressed.
+///
+/// Buttons are expected to pull the pin low when pressed, with a pull-up keeping it high
+/// otherwise. Bouncing contacts are ignored as long as the button is polled less often than
+/// they bounce, every 10 ms or more is enough for most buttons.
+pub struct Button<P> {
+    /// Input pin of the button.
+    pin: P,
+
+    /// Event reported when the button is pressed.
+    event: InputEvent,
+
+    /// Whether the button was pressed when it was last polled.
+    pressed: bool,
+}
+
+impl<P: InputPin> Button<P> {
+    /// Creates a button reporting `event` when it is pressed.
+    pub fn new(pin: P, event: InputEvent) -> Button<P> {
+        Button {
+            pin,
+            event,
+            pressed: false,
+        }
+    }
+
+    /// Returns the event of the button if it was pressed since the last poll.
+    pub fn poll(&mut self) -> Result<Option<InputEvent>, P::Error> {
+        let pressed = self.pin.is_low()?;
+        let event = if pressed && !self.pressed { Some(self.event) } else { None };
+        self.pressed = pressed;
+        Ok(event)
+    }
+
+    /// Returns the input pin.
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+/// Quadrature rotary encoder reporting `InputEvent::Encoder` for every detent it is turned.
+///
+/// The encoder has to be polled fast enough to see every change of its outputs, e.g. every
+/// millisecond or from a pin change interrupt.
+pub struct RotaryEncoder<A, B> {
+    /// Pin of output A.
+    a: A,
+
+    /// Pin of output B.
+    b: B,
+
+    /// Levels of A and B when the encoder was last polled.
+    state: u8,
+
+    /// Steps counted since the last reported detent.
+    steps: i8,
+
+    /// Steps between two detents.
+    steps_per_detent: i8,
+}
+
+impl<A, B, E> RotaryEncoder<A, B>
+where
+    A: InputPin<Error = E>,
+    B: InputPin<Error = E>,
+{
+    /// Creates an encoder with four steps per detent, which most encoders with detents have.
+    pub fn new(a: A, b: B) -> RotaryEncoder<A, B> {
+        RotaryEncoder {
+            a,
+            b,
+            state: 0b11,
+            steps: 0,
+            steps_per_detent: 4,
+        }
+    }
+
+    /// Changes the number of steps between two detents, e.g. to 2 or 1 for encoders reporting
+    /// more than one detent per turn step.
+    pub fn with_steps_per_detent(mut self, steps: i8) -> RotaryEncoder<A, B> {
+        self.steps_per_detent = steps.max(1);
+        self
+    }
+
+    /// Returns the event for the detents the encoder was turned by since the last report, if
+    /// it reached one.
+    pub fn poll(&mut self) -> Result<Option<InputEvent>, E> {
+        let state = ((self.a.is_high()? as u8) << 1) | self.b.is_high()? as u8;
+
+        // Gray code sequence 00 -> 01 -> 11 -> 10 is one direction, the reverse the other.
+        // Changes of both outputs at once were missed and are ignored.
+        let step = match (self.state, state) {
+            (0b00, 0b01) | (0b01, 0b11) | (0b11, 0b10) | (0b10, 0b00) => 1,
+            (0b00, 0b10) | (0b10, 0b11) | (0b11, 0b01) | (0b01, 0b00) => -1,
+            _ => 0,
+        };
+        self.state = state;
+        self.steps += step;
+
+        let detents = self.steps / self.steps_per_detent;
+        if detents == 0 {
+            return Ok(None);
+        }
+        self.steps -= detents * self.steps_per_detent;
+        Ok(Some(InputEvent::Encoder(detents)))
+    }
+
+    /// Returns the pins of outputs A and B.
+    pub fn release(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
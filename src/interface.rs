@@ -0,0 +1,132 @@
+//! Support for the `display-interface` crate.
+//!
+//! This module is available with the `display-interface` feature. It lets the driver talk to the
+//! display through any `WriteOnlyDataCommand` implementation, such as `SPIInterface` of
+//! `display-interface-spi` or parallel GPIO and PIO interfaces, instead of an SPI bus and a
+//! data/command pin.
+//!
+//! The interface is wrapped in an [`Interface`](struct.Interface.html), which provides a bus and
+//! a data/command pin sharing it. The driver toggles the pin before sending commands or data,
+//! and the bus passes the bytes on as commands or data accordingly. Displays attached this way
+//! have no reset pin and are reset with the software reset command.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let interface = Interface::new(SPIInterface::new(spi_device, dc));
+//! let mut display = ST7734::new_with_interface(&interface, delay);
+//! display.clear_screen()?;
+//! ```
+
+use crate::controller::ST7735;
+use crate::error::DisplayError;
+use crate::ST7734;
+
+use core::cell::{Cell, RefCell};
+use core::convert::Infallible;
+use display_interface::{DataFormat, DisplayError as InterfaceError, WriteOnlyDataCommand};
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Driver talking to the display through an [`Interface`](struct.Interface.html).
+pub type InterfaceDisplay<'a, DI, DELAY> = ST7734<InterfaceBus<'a, DI>, InterfaceDc<'a, DI>, DELAY, ST7735>;
+
+/// Error of a driver talking to the display through an [`Interface`](struct.Interface.html).
+pub type InterfaceDisplayError<'a, DI> = DisplayError<InterfaceBus<'a, DI>, InterfaceDc<'a, DI>>;
+
+/// Display interface shared by the bus and data/command pin handed to the driver.
+pub struct Interface<DI> {
+    /// Wrapped interface.
+    interface: RefCell<DI>,
+
+    /// Whether bytes written to the bus are data, set by the data/command pin.
+    data: Cell<bool>,
+}
+
+impl<DI: WriteOnlyDataCommand> Interface<DI> {
+    /// Wraps a display interface.
+    pub fn new(interface: DI) -> Interface<DI> {
+        Interface {
+            interface: RefCell::new(interface),
+            data: Cell::new(false),
+        }
+    }
+
+    /// Returns the bus passed to the driver, e.g. to use `ST7734::with_controller_spi`.
+    pub fn bus(&self) -> InterfaceBus<'_, DI> {
+        InterfaceBus { interface: self }
+    }
+
+    /// Returns the data/command pin passed to the driver.
+    pub fn dc(&self) -> InterfaceDc<'_, DI> {
+        InterfaceDc { interface: self }
+    }
+
+    /// Returns the wrapped interface.
+    pub fn release(self) -> DI {
+        self.interface.into_inner()
+    }
+}
+
+/// Bus sending the written bytes as commands or data through an [`Interface`](struct.Interface.html).
+pub struct InterfaceBus<'a, DI> {
+    /// Interface the bytes are sent through.
+    interface: &'a Interface<DI>,
+}
+
+impl<'a, DI: WriteOnlyDataCommand> spi::Write<u8> for InterfaceBus<'a, DI> {
+    type Error = InterfaceError;
+
+    fn write(&mut self, words: &[u8]) -> Result<(), InterfaceError> {
+        let mut interface = self.interface.interface.borrow_mut();
+        if self.interface.data.get() {
+            interface.send_data(DataFormat::U8(words))
+        } else {
+            interface.send_commands(DataFormat::U8(words))
+        }
+    }
+}
+
+/// Data/command pin selecting how an [`InterfaceBus`](struct.InterfaceBus.html) sends bytes.
+pub struct InterfaceDc<'a, DI> {
+    /// Interface whose mode is selected.
+    interface: &'a Interface<DI>,
+}
+
+impl<'a, DI> OutputPin for InterfaceDc<'a, DI> {
+    type Error = Infallible;
+
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.interface.data.set(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.interface.data.set(true);
+        Ok(())
+    }
+}
+
+impl<'a, DI, DELAY> ST7734<InterfaceBus<'a, DI>, InterfaceDc<'a, DI>, DELAY, ST7735>
+where
+    DI: WriteOnlyDataCommand,
+    DELAY: DelayMs<u64>,
+{
+    /// Creates a new driver instance that sends commands and data through a display interface.
+    pub fn new_with_interface(
+        interface: &'a Interface<DI>,
+        delay: DELAY,
+    ) -> InterfaceDisplay<'a, DI, DELAY> {
+        ST7734::new_with_spi(interface.bus(), interface.dc(), delay)
+    }
+
+    /// Creates a new driver instance that sends commands and data through a display interface.
+    /// Returns the error if initializing the display fails.
+    pub fn try_new_with_interface(
+        interface: &'a Interface<DI>,
+        delay: DELAY,
+    ) -> Result<InterfaceDisplay<'a, DI, DELAY>, InterfaceDisplayError<'a, DI>> {
+        ST7734::try_new_with_spi(interface.bus(), interface.dc(), delay)
+    }
+}
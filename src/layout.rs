@@ -0,0 +1,148 @@
+//! Computation of widget areas from the display size.
+//!
+//! A [`Layout`](struct.Layout.html) splits an area into a row or column of children with fixed
+//! or weighted sizes. Layouts are nested by splitting the areas of children again, so user
+//! interfaces adapt to the panel size and orientation instead of using hard-coded coordinates.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! // Status bar at the top, a sidebar and the main area below.
+//! let screen = Layout::column(&[Size::Fixed(12), Size::Weight(1)]).split(&display.bounds());
+//! let body = Layout::row(&[Size::Weight(1), Size::Weight(3)])
+//!     .with_padding(2)
+//!     .with_spacing(2)
+//!     .split(&screen[1]);
+//!
+//! let mut bar = StatusBar::new(screen[0], white, navy);
+//! let scope = Oscilloscope::new(body[1], -512, 511, green, black);
+//! ```
+
+use crate::geometry::Rect;
+
+use alloc::vec::Vec;
+
+/// Size of a child along the direction of a layout.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Size {
+    /// Fixed number of pixels.
+    Fixed(u16),
+
+    /// Share of the space left after fixed children, relative to the weights of the other
+    /// weighted children.
+    Weight(u16),
+}
+
+/// Direction children of a layout are placed in.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Direction {
+    /// Left to right.
+    Row,
+
+    /// Top to bottom.
+    Column,
+}
+
+/// Row or column of children.
+#[derive(Clone, Copy, Debug)]
+pub struct Layout<'a> {
+    /// Direction children are placed in.
+    direction: Direction,
+
+    /// Sizes of the children.
+    sizes: &'a [Size],
+
+    /// Space between the edges of the area and the children.
+    padding: u16,
+
+    /// Space between two children.
+    spacing: u16,
+}
+
+impl<'a> Layout<'a> {
+    /// Creates a layout placing children with the given sizes left to right.
+    pub fn row(sizes: &'a [Size]) -> Layout<'a> {
+        Layout::new(Direction::Row, sizes)
+    }
+
+    /// Creates a layout placing children with the given sizes top to bottom.
+    pub fn column(sizes: &'a [Size]) -> Layout<'a> {
+        Layout::new(Direction::Column, sizes)
+    }
+
+    /// Creates a layout placing children with the given sizes in `direction`.
+    pub fn new(direction: Direction, sizes: &'a [Size]) -> Layout<'a> {
+        Layout {
+            direction,
+            sizes,
+            padding: 0,
+            spacing: 0,
+        }
+    }
+
+    /// Leaves `padding` pixels between the edges of the area and the children.
+    pub fn with_padding(mut self, padding: u16) -> Layout<'a> {
+        self.padding = padding;
+        self
+    }
+
+    /// Leaves `spacing` pixels between two children.
+    pub fn with_spacing(mut self, spacing: u16) -> Layout<'a> {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Returns the areas of the children within `area`, in the order of the sizes.
+    ///
+    /// Rounding leftovers of weighted children go to the last weighted child, so the children
+    /// fill the area exactly. If the area is too small, children are at least one pixel in size
+    /// and extend past the area.
+    pub fn split(&self, area: &Rect) -> Vec<Rect> {
+        let padded = area.inset(self.padding).unwrap_or(*area);
+        let (start, length, cross_start, cross_end) = match self.direction {
+            Direction::Row => (padded.x0, padded.width(), padded.y0, padded.y1),
+            Direction::Column => (padded.y0, padded.height(), padded.x0, padded.x1),
+        };
+
+        let count = self.sizes.len() as u16;
+        let spacing = self.spacing.saturating_mul(count.saturating_sub(1));
+        let fixed: u16 = self.sizes.iter().fold(0u16, |sum, size| match *size {
+            Size::Fixed(pixels) => sum.saturating_add(pixels),
+            Size::Weight(_) => sum,
+        });
+        let total_weight: u32 = self.sizes.iter().map(|size| match *size {
+            Size::Fixed(_) => 0,
+            Size::Weight(weight) => weight as u32,
+        }).sum();
+        let flexible = length.saturating_sub(fixed).saturating_sub(spacing) as u32;
+        let last_weighted = self.sizes.iter().rposition(|size| match *size {
+            Size::Weight(_) => true,
+            Size::Fixed(_) => false,
+        });
+
+        let mut rects = Vec::with_capacity(self.sizes.len());
+        let mut position = start;
+        let mut distributed = 0;
+        for (index, size) in self.sizes.iter().enumerate() {
+            let pixels = match *size {
+                Size::Fixed(pixels) => pixels,
+                Size::Weight(_) if Some(index) == last_weighted => (flexible - distributed) as u16,
+                Size::Weight(weight) if total_weight > 0 => {
+                    let pixels = flexible * weight as u32 / total_weight;
+                    distributed += pixels;
+                    pixels as u16
+                }
+                Size::Weight(_) => 0,
+            };
+
+            let end = position.saturating_add(pixels.max(1) - 1);
+            rects.push(match self.direction {
+                Direction::Row => Rect::new(position, cross_start, end, cross_end),
+                Direction::Column => Rect::new(cross_start, position, cross_end, end),
+            });
+            position = end.saturating_add(1).saturating_add(self.spacing);
+        }
+
+        rects
+    }
+}
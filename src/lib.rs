@@ -14,39 +14,174 @@
 //! * Lines (horizontal, vertical, and diagonal)
 //! * Text (characters)
 //!
+//! For interfaces that are updated piecemeal, the optional [`scene`](scene/index.html) module
+//! keeps track of what is on screen and only repaints the areas that changed.
+//!
+//! # Features
+//!
+//! The driver itself, which sends commands and pixels, is always available. Everything else can
+//! be left out to save flash, the first four features are enabled by default:
+//!
+//! * `alloc`: Drawing of unordered pixels, layouts and scenes. Requires a global allocator.
+//! * `fonts`: Fonts and text rendering, including the scrolling log and, with `alloc`, the status bar.
+//! * `graphics-primitives`: Lines, circles, rectangle borders, patterns and draw queues.
+//! * `framebuffer`: Framebuffers, banded rendering and, with `fonts`, the immediate-mode UI.
+//! * `std`: GIF animations, screenshots, frame recordings, the remote framebuffer and test helpers.
+//! * `serde`: Serialization of the display `Config`.
+//! * `display-interface`: Constructors taking a `display-interface` implementation instead of SPI.
+//! * `ttf`: Anti-aliased text from TrueType fonts in any size, drawn into framebuffers.
+//!
 //! # Examples
 //!
-//! ```
+//! ```ignore
 //! let mut display = ST7734::new_with_spi("/dev/spidev0.0", 25);
 //! display.set_orientation(&Orientation::Portrait);
 //! display.draw_rect(30, 30, 60, 70, &Color::from_default(DefaultColor::Blue));
 //! ```
 #![no_std]
-#![feature(alloc, slice_concat_ext)]
 
 extern crate embedded_hal;
+#[cfg(feature = "graphics")]
+extern crate embedded_graphics_core;
+#[cfg(feature = "critical-section")]
+extern crate critical_section;
+#[cfg(feature = "defmt")]
+extern crate defmt;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "display-interface")]
+extern crate display_interface;
+#[cfg(feature = "ttf")]
+extern crate fontdue;
+#[cfg(feature = "embassy")]
+extern crate embassy_sync;
+#[cfg(feature = "embassy")]
+extern crate embassy_time;
+#[cfg(feature = "embassy")]
+extern crate embedded_hal_1;
+#[cfg(feature = "embassy")]
+extern crate embedded_hal_async;
+#[cfg(feature = "std")]
+extern crate gif;
+#[cfg(feature = "std")]
+extern crate png;
+#[cfg(feature = "linux")]
+extern crate linux_embedded_hal;
+#[cfg(feature = "tga")]
+extern crate tinytga;
+#[cfg(feature = "std")]
+extern crate std;
 #[macro_use]
 extern crate num_derive;
-#[macro_use]
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+pub mod animation;
+pub mod ansi;
+pub mod backlight;
+#[cfg(feature = "framebuffer")]
+pub mod band;
+pub mod bitbang;
 pub mod color;
 pub mod command;
+pub mod config;
+pub mod controller;
+pub mod dma;
+#[cfg(feature = "embassy")]
+pub mod embassy;
+pub mod error;
+#[cfg(feature = "fonts")]
 pub mod fonts;
+#[cfg(feature = "framebuffer")]
+pub mod framebuffer;
+pub mod gamma;
+pub mod geometry;
+#[cfg(feature = "graphics")]
+pub mod graphics;
+pub mod image;
+pub mod input;
+#[cfg(feature = "display-interface")]
+pub mod interface;
+#[cfg(feature = "alloc")]
+pub mod layout;
+#[cfg(feature = "linux")]
+pub mod linux;
+pub mod palette;
+pub mod parallel;
+#[cfg(feature = "graphics-primitives")]
+pub mod pattern;
+pub mod pixel_format;
+#[cfg(feature = "graphics-primitives")]
+pub mod queue;
+pub mod readback;
+#[cfg(feature = "std")]
+pub mod recorder;
+#[cfg(feature = "std")]
+pub mod remote;
+pub mod render_loop;
+#[cfg(feature = "alloc")]
+pub mod scene;
+pub mod screensaver;
+#[cfg(feature = "std")]
+pub mod screenshot;
+#[cfg(feature = "fonts")]
+pub mod scroll;
+#[cfg(all(feature = "critical-section", feature = "graphics-primitives"))]
+pub mod shared;
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod testing;
+#[cfg(feature = "fonts")]
+pub mod text;
+#[cfg(feature = "tga")]
+pub mod tga;
+pub mod tiled;
+pub mod timing;
+pub mod touch;
+#[cfg(feature = "ttf")]
+pub mod ttf;
+#[cfg(all(feature = "framebuffer", feature = "fonts"))]
+pub mod ui;
+pub mod watchdog;
+pub mod widgets;
 
+use crate::backlight::Backlight;
+#[cfg(feature = "framebuffer")]
+use crate::band::Band;
 use crate::color::{Color, DefaultColor};
-use crate::command::{Command, Instruction};
-use crate::fonts::Font;
+use crate::command::{Command, CommandQueue, Instruction};
+use crate::config::Config;
+use crate::controller::{Chip, Controller, ST7735};
+use crate::error::{DisplayError, Error};
+#[cfg(feature = "fonts")]
+use crate::fonts::binary::BinaryFont;
+#[cfg(feature = "fonts")]
+use crate::fonts::font57::Font57;
+#[cfg(feature = "fonts")]
+use crate::fonts::{AlphaFont, Font, CHARACTER_ADVANCE, LINE_ADVANCE};
+use crate::gamma::GammaCurve;
+use crate::geometry::Rect;
+#[cfg(feature = "graphics-primitives")]
+use crate::image::TextureMode;
+use crate::image::{Filter, Image, NinePatch, Transform};
+#[cfg(feature = "graphics-primitives")]
+use crate::pattern::Pattern;
+use crate::pixel_format::{ByteOrder, PixelEncoder, PixelFormat, SliceWriter};
+use crate::stats::Stats;
+#[cfg(feature = "fonts")]
+use crate::text::TextWriter;
+use crate::timing::Timing;
 
-use alloc::prelude::SliceConcatExt;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 use embedded_hal::blocking::spi;
-use embedded_hal::digital::OutputPin;
+use embedded_hal::digital::v2::OutputPin;
 use embedded_hal::blocking::delay::DelayMs;
-use num;
+use embedded_hal::PwmPin;
+#[cfg(feature = "graphics-primitives")]
 use num::integer::sqrt;
-use core::cmp::{max, min};
-use core::mem::transmute;
+use core::cmp::min;
 
 /// ST7735 driver to connect to TFT displays. The driver allows to draw simple shapes,
 /// and reset the display.
@@ -57,33 +192,145 @@ use core::mem::transmute;
 ///
 /// # Examples
 ///
-/// ```
+/// ```ignore
 /// let mut display = ST7734::new_with_spi("/dev/spidev0.0", 25);
 /// display.set_orientation(&Orientation::Portrait);
 /// display.draw_rect(30, 30, 60, 70, &Color::from_default(DefaultColor::Blue));
 /// ```
 ///
-pub struct ST7734<SPI, PIN, DELAY> {
+/// # Thread safety
+///
+/// The driver only owns the SPI, pins, delay and controller it was created with, besides plain
+/// values. It is therefore `Send` and `Sync` exactly if all of them are, and can be moved to
+/// another thread or task if the HAL types allow it. Drawing requires `&mut self`, so concurrent
+/// access needs a lock, see `SharedST7734` in the `shared` module.
+pub struct ST7734<SPI, PIN, DELAY, C = ST7735> {
     /// Reset pin.
     rst: Option<PIN>,
 
+    /// Panel supply enable pin.
+    power: Option<PIN>,
+
+    /// Whether the power pin is active high.
+    power_active_high: bool,
+
+    /// Whether the panel supply is enabled.
+    powered: bool,
+
     /// SPI clock pin.
     clk: Option<PIN>,
 
     /// Data/command pin.
     dc: Option<PIN>,
 
+    /// Chip select pin, active low.
+    cs: Option<PIN>,
+
     /// MOSI pin.
     mosi: Option<PIN>,
 
     /// Hardware SPI
     spi: Option<SPI>,
 
-    delay: DELAY
+    delay: DELAY,
+
+    /// Display controller.
+    controller: C,
+
+    /// Width of the panel in portrait orientation.
+    panel_width: u16,
+
+    /// Height of the panel in portrait orientation.
+    panel_height: u16,
+
+    /// Current display orientation.
+    orientation: Orientation,
+
+    /// Bus traffic counters.
+    stats: Stats,
+
+    /// Fixed areas at the top and bottom of the scrolling area, if set.
+    scroll_area: Option<(u16, u16)>,
+
+    /// Display memory line shown at the top of the scrolling area.
+    scroll_start: u16,
+
+    /// Whether the tearing effect output is enabled.
+    tearing_effect: bool,
+
+    /// Whether the display is turned on.
+    display_enabled: bool,
+
+    /// Reset and initialization delays.
+    timing: Timing,
+
+    /// Current normal or partial display mode.
+    display_mode: DisplayMode,
+
+    /// Whether idle mode with reduced colors is enabled.
+    idle_mode: bool,
+
+    /// Format pixels are transferred in.
+    pixel_format: PixelFormat,
+
+    /// Maximum number of bytes sent in a single SPI write, if limited.
+    max_transfer_size: Option<usize>,
+
+    /// Byte order of `Rgb565` pixels.
+    byte_order: ByteOrder,
+
+    /// Column and row offset of the visible area in portrait orientation, added to the offset
+    /// of the controller.
+    offset: (u16, u16),
+
+    /// Whether colors are inverted.
+    inverted: bool,
+
+    /// Selected gamma curve.
+    gamma_curve: GammaCurve,
+
+    /// Buffer pixels are encoded into for bulk transfers, if provided.
+    transfer_buffer: Option<&'static mut [u8]>,
+}
+
+/// Returns the vertical spans covering a filled circle. Parts of the circle with negative
+/// coordinates are cut off.
+#[cfg(feature = "graphics-primitives")]
+fn circle_columns(x_pos: i32, y_pos: i32, radius: u16) -> Vec<Rect> {
+    let radius = radius as i32;
+    let r2 = radius * radius;
+    let mut columns = Vec::with_capacity(2 * radius as usize);
+
+    for x in 0..radius {
+        let y = sqrt(r2 - x * x);
+        if let Some(column) = Rect::clipped(x_pos + x, y_pos - y, x_pos + x, y_pos + y) {
+            columns.push(column);
+        }
+        if x > 0 {
+            if let Some(column) = Rect::clipped(x_pos - x, y_pos - y, x_pos - x, y_pos + y) {
+                columns.push(column);
+            }
+        }
+    }
+
+    columns
 }
 
+/// Maximum number of pixels transferred at once when filling areas with a single color, unless
+/// a larger transfer buffer was provided.
+const BULK_CHUNK_PIXELS: usize = 128;
+
+/// Size of the buffer on the stack pixels are encoded into before they are transferred, which
+/// holds `BULK_CHUNK_PIXELS` pixels in any pixel format. Used if no transfer buffer was provided
+/// with `set_transfer_buffer`, so that the driver works without an allocator.
+const TRANSFER_BUFFER_BYTES: usize = BULK_CHUNK_PIXELS * 3;
+
+/// Minimum size of a transfer buffer, which holds two pixels in any pixel format.
+pub const MIN_TRANSFER_BUFFER_BYTES: usize = 6;
+
 /// Display orientation.
-#[derive(FromPrimitive, ToPrimitive)]
+#[derive(FromPrimitive, ToPrimitive, Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Orientation {
     Portrait = 0x00,
     Landscape = 0x60,
@@ -91,384 +338,2099 @@ pub enum Orientation {
     LandScapeSwapped = 0xA0,
 }
 
-impl<SPI, PIN, DELAY> ST7734<SPI, PIN, DELAY>
+/// Area of the panel that is driven.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DisplayMode {
+    /// The whole panel shows the display memory.
+    Normal,
+
+    /// Only the panel rows `start` to `end` (inclusive, in portrait orientation) show the display
+    /// memory, the remaining rows are blank. Reduces power consumption, e.g. for a status line.
+    Partial {
+        start: u16,
+        end: u16,
+    },
+}
+
+/// Hardware handed back by `ST7734::release`.
+pub struct Parts<SPI, PIN, DELAY, C = ST7735> {
+    /// Reset pin, if the driver was created with one.
+    pub rst: Option<PIN>,
+
+    /// Panel supply enable pin, if one was set.
+    pub power: Option<PIN>,
+
+    /// SPI clock pin, if software SPI is used.
+    pub clk: Option<PIN>,
+
+    /// Data/command pin.
+    pub dc: Option<PIN>,
+
+    /// Chip select pin, if one was set.
+    pub cs: Option<PIN>,
+
+    /// MOSI pin, if software SPI is used.
+    pub mosi: Option<PIN>,
+
+    /// Hardware SPI, if used.
+    pub spi: Option<SPI>,
+
+    /// Delay provider.
+    pub delay: DELAY,
+
+    /// Display controller.
+    pub controller: C,
+
+    /// Transfer buffer, if one was set.
+    pub transfer_buffer: Option<&'static mut [u8]>,
+}
+
+/// Settings of a released driver, which the display keeps while the driver does not exist.
+/// Passed back to `ST7734::from_parts` so that the driver matches the display again.
+#[derive(Clone, Copy, Debug)]
+pub struct DriverState {
+    power_active_high: bool,
+    powered: bool,
+    panel_width: u16,
+    panel_height: u16,
+    orientation: Orientation,
+    stats: Stats,
+    scroll_area: Option<(u16, u16)>,
+    scroll_start: u16,
+    tearing_effect: bool,
+    display_enabled: bool,
+    timing: Timing,
+    display_mode: DisplayMode,
+    idle_mode: bool,
+    pixel_format: PixelFormat,
+    max_transfer_size: Option<usize>,
+    byte_order: ByteOrder,
+    offset: (u16, u16),
+    inverted: bool,
+    gamma_curve: GammaCurve,
+}
+
+/// Patterns for checking color order, offsets and orientation of a display.
+pub enum TestPattern {
+    /// Vertical bars in white, yellow, cyan, green, magenta, red, blue and black.
+    ColorBars,
+
+    /// Horizontal red, green and blue gradients stacked on top of each other.
+    Gradient,
+
+    /// Black and white squares of 8x8 pixels.
+    Checkerboard,
+
+    /// White grid lines every 16 pixels with a red border around the visible area.
+    Grid,
+}
+
+impl<SPI, PIN, DELAY> ST7734<SPI, PIN, DELAY, ST7735>
 where
     SPI: spi::Write<u8>,
     PIN: OutputPin,
     DELAY: DelayMs<u64> {
 
     /// Creates a new driver instance that uses hardware SPI.
-    pub fn new_with_spi(spi: SPI, dc: PIN, delay: DELAY) -> ST7734<SPI, PIN, DELAY> {
-        let mut display = ST7734 {
-            rst: None,
-            clk: None,
-            dc: Some(dc),
-            mosi: None,
-            spi: Some(spi),
-            delay
-        };
+    pub fn new_with_spi(spi: SPI, dc: PIN, delay: DELAY) -> ST7734<SPI, PIN, DELAY, ST7735> {
+        ST7734::with_controller_spi(ST7735, spi, dc, delay)
+    }
+
+    /// Creates a new driver instance that uses software SPI using the provided pins.
+    pub fn new_with_gpio(rst: Option<PIN>, clk: PIN, dc: PIN, mosi: PIN, delay: DELAY) -> ST7734<SPI, PIN, DELAY, ST7735> {
+        ST7734::with_controller_gpio(ST7735, rst, clk, dc, mosi, delay)
+    }
+
+    /// Creates a new driver instance that uses hardware SPI. Returns the error if initializing
+    /// the display fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut display = ST7734::try_new_with_spi(spi, dc, delay).expect("display not responding");
+    /// ```
+    pub fn try_new_with_spi(
+        spi: SPI,
+        dc: PIN,
+        delay: DELAY,
+    ) -> Result<ST7734<SPI, PIN, DELAY, ST7735>, DisplayError<SPI, PIN>> {
+        ST7734::try_with_controller_spi(ST7735, spi, dc, delay)
+    }
+
+    /// Creates a new driver instance that uses software SPI using the provided pins. Returns
+    /// the error if initializing the display fails.
+    pub fn try_new_with_gpio(
+        rst: Option<PIN>,
+        clk: PIN,
+        dc: PIN,
+        mosi: PIN,
+        delay: DELAY,
+    ) -> Result<ST7734<SPI, PIN, DELAY, ST7735>, DisplayError<SPI, PIN>> {
+        ST7734::try_with_controller_gpio(ST7735, rst, clk, dc, mosi, delay)
+    }
+
+    /// Creates a new driver instance that uses hardware SPI and applies `config`, e.g. settings
+    /// stored in flash. Returns the error if initializing or configuring the display fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let config = Config {
+    ///     orientation: Orientation::Landscape,
+    ///     ..Config::new(Variant::GreenTab)
+    /// };
+    /// let mut display = ST7734::try_new_with_config(spi, dc, delay, &config)?;
+    /// ```
+    pub fn try_new_with_config(
+        spi: SPI,
+        dc: PIN,
+        delay: DELAY,
+        config: &Config,
+    ) -> Result<ST7734<SPI, PIN, DELAY, ST7735>, DisplayError<SPI, PIN>> {
+        let mut display = ST7734::try_new_with_spi(spi, dc, delay)?;
+        display.apply_config(config)?;
+        Ok(display)
+    }
+}
 
-        display.init();
+impl<SPI, PIN, DELAY, C> ST7734<SPI, PIN, DELAY, C>
+where
+    SPI: spi::Write<u8>,
+    PIN: OutputPin,
+    DELAY: DelayMs<u64>,
+    C: Controller {
+
+    /// Creates a new driver instance for the given controller that uses hardware SPI.
+    ///
+    /// Errors while initializing the display are ignored, use `try_with_controller_spi` to
+    /// handle them.
+    pub fn with_controller_spi(controller: C, spi: SPI, dc: PIN, delay: DELAY) -> ST7734<SPI, PIN, DELAY, C> {
+        let mut display = ST7734::with_parts(controller, None, None, Some(dc), None, Some(spi), delay);
+        let _ = display.init();
         display
     }
 
-    /// Creates a new driver instance that uses software SPI using the provided pins.
-    pub fn new_with_gpio(rst: Option<PIN>, clk: PIN, dc: PIN, mosi: PIN, delay: DELAY) -> ST7734<SPI, PIN, DELAY> {
-        let mut display = ST7734 {
+    /// Creates a new driver instance for the given controller that uses hardware SPI. Returns
+    /// the error if initializing the display fails.
+    pub fn try_with_controller_spi(
+        controller: C,
+        spi: SPI,
+        dc: PIN,
+        delay: DELAY,
+    ) -> Result<ST7734<SPI, PIN, DELAY, C>, DisplayError<SPI, PIN>> {
+        let mut display = ST7734::with_parts(controller, None, None, Some(dc), None, Some(spi), delay);
+        display.init()?;
+        Ok(display)
+    }
+
+    /// Creates a new driver instance for the given controller that uses software SPI using the
+    /// provided pins.
+    pub fn with_controller_gpio(
+        controller: C,
+        rst: Option<PIN>,
+        clk: PIN,
+        dc: PIN,
+        mosi: PIN,
+        delay: DELAY,
+    ) -> ST7734<SPI, PIN, DELAY, C> {
+        let mut display = ST7734::with_parts(controller, rst, Some(clk), Some(dc), Some(mosi), None, delay);
+        let _ = display.init();
+        display
+    }
+
+    /// Creates a new driver instance for the given controller that uses software SPI using the
+    /// provided pins. Returns the error if initializing the display fails.
+    pub fn try_with_controller_gpio(
+        controller: C,
+        rst: Option<PIN>,
+        clk: PIN,
+        dc: PIN,
+        mosi: PIN,
+        delay: DELAY,
+    ) -> Result<ST7734<SPI, PIN, DELAY, C>, DisplayError<SPI, PIN>> {
+        let mut display = ST7734::with_parts(controller, rst, Some(clk), Some(dc), Some(mosi), None, delay);
+        display.init()?;
+        Ok(display)
+    }
+
+    /// Creates a driver instance in its default state without initializing the display.
+    fn with_parts(
+        controller: C,
+        rst: Option<PIN>,
+        clk: Option<PIN>,
+        dc: Option<PIN>,
+        mosi: Option<PIN>,
+        spi: Option<SPI>,
+        delay: DELAY,
+    ) -> ST7734<SPI, PIN, DELAY, C> {
+        ST7734 {
             rst,
-            clk: Some(clk),
-            dc: Some(dc),
-            mosi: Some(mosi),
-            spi: None,
-            delay
+            power: None,
+            power_active_high: true,
+            powered: true,
+            clk,
+            dc,
+            cs: None,
+            mosi,
+            spi,
+            delay,
+            panel_width: controller.width(),
+            panel_height: controller.height(),
+            controller,
+            orientation: Orientation::Portrait,
+            stats: Stats::default(),
+            scroll_area: None,
+            scroll_start: 0,
+            tearing_effect: false,
+            display_enabled: true,
+            timing: Timing::default(),
+            display_mode: DisplayMode::Normal,
+            idle_mode: false,
+            pixel_format: PixelFormat::Rgb565,
+            max_transfer_size: None,
+            byte_order: ByteOrder::BigEndian,
+            offset: (0, 0),
+            inverted: false,
+            gamma_curve: GammaCurve::Curve1,
+            transfer_buffer: None,
+        }
+    }
+
+    /// Releases the SPI, pins, delay and controller, e.g. to talk to another device on the same
+    /// bus. The returned state is used to reassemble the driver with `from_parts`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let (parts, state) = display.release();
+    /// let mut sd_card = SdCard::new(parts.spi.unwrap(), cs);
+    /// // ...
+    /// let parts = Parts { spi: Some(sd_card.free()), ..parts };
+    /// let mut display = ST7734::from_parts(parts, state);
+    /// ```
+    pub fn release(self) -> (Parts<SPI, PIN, DELAY, C>, DriverState) {
+        let parts = Parts {
+            rst: self.rst,
+            power: self.power,
+            clk: self.clk,
+            dc: self.dc,
+            cs: self.cs,
+            mosi: self.mosi,
+            spi: self.spi,
+            delay: self.delay,
+            controller: self.controller,
+            transfer_buffer: self.transfer_buffer,
+        };
+        let state = DriverState {
+            power_active_high: self.power_active_high,
+            powered: self.powered,
+            panel_width: self.panel_width,
+            panel_height: self.panel_height,
+            orientation: self.orientation,
+            stats: self.stats,
+            scroll_area: self.scroll_area,
+            scroll_start: self.scroll_start,
+            tearing_effect: self.tearing_effect,
+            display_enabled: self.display_enabled,
+            timing: self.timing,
+            display_mode: self.display_mode,
+            idle_mode: self.idle_mode,
+            pixel_format: self.pixel_format,
+            max_transfer_size: self.max_transfer_size,
+            byte_order: self.byte_order,
+            offset: self.offset,
+            inverted: self.inverted,
+            gamma_curve: self.gamma_curve,
         };
+        (parts, state)
+    }
 
-        display.init();
-        display
+    /// Reassembles a driver released with `release` without initializing the display again,
+    /// so the screen content is kept.
+    pub fn from_parts(parts: Parts<SPI, PIN, DELAY, C>, state: DriverState) -> ST7734<SPI, PIN, DELAY, C> {
+        ST7734 {
+            rst: parts.rst,
+            power: parts.power,
+            power_active_high: state.power_active_high,
+            powered: state.powered,
+            clk: parts.clk,
+            dc: parts.dc,
+            cs: parts.cs,
+            mosi: parts.mosi,
+            spi: parts.spi,
+            delay: parts.delay,
+            controller: parts.controller,
+            panel_width: state.panel_width,
+            panel_height: state.panel_height,
+            orientation: state.orientation,
+            stats: state.stats,
+            scroll_area: state.scroll_area,
+            scroll_start: state.scroll_start,
+            tearing_effect: state.tearing_effect,
+            display_enabled: state.display_enabled,
+            timing: state.timing,
+            display_mode: state.display_mode,
+            idle_mode: state.idle_mode,
+            pixel_format: state.pixel_format,
+            max_transfer_size: state.max_transfer_size,
+            byte_order: state.byte_order,
+            offset: state.offset,
+            inverted: state.inverted,
+            gamma_curve: state.gamma_curve,
+            transfer_buffer: parts.transfer_buffer,
+        }
     }
 
     /// Runs commands to initialize the display.
-    fn init(&mut self) {
-        self.hard_reset();
-
-        let init_commands: Vec<Command> = vec![
-            Command {
-                instruction: Instruction::SWRESET,
-                delay: Some(200),
-                arguments: vec![],
-            },
-            Command {
-                instruction: Instruction::SLPOUT,
-                delay: Some(200),
-                arguments: vec![],
-            },
-            Command {
-                instruction: Instruction::COLMOD,
-                delay: None,
-                arguments: vec![0x05],
-            },
-            Command {
-                instruction: Instruction::FRMCTR1,
-                delay: None,
-                arguments: vec![0x01, 0x2C, 0x2D],
-            },
-            Command {
-                instruction: Instruction::FRMCTR2,
-                delay: None,
-                arguments: vec![0x01, 0x2C, 0x2D],
-            },
-            Command {
-                instruction: Instruction::FRMCTR3,
-                delay: None,
-                arguments: vec![0x01, 0x2C, 0x2D, 0x01, 0x2C, 0x2D],
-            },
-            Command {
-                instruction: Instruction::INVCTR,
-                delay: None,
-                arguments: vec![0x07],
-            },
-            Command {
-                instruction: Instruction::PWCTR1,
-                delay: None,
-                arguments: vec![0xA2, 0x02, 0x84],
-            },
-            Command {
-                instruction: Instruction::PWCTR2,
-                delay: None,
-                arguments: vec![0xC5],
-            },
-            Command {
-                instruction: Instruction::PWCTR3,
-                delay: None,
-                arguments: vec![0x0A, 0x00],
-            },
-            Command {
-                instruction: Instruction::PWCTR4,
-                delay: None,
-                arguments: vec![0x8A, 0x2A],
-            },
-            Command {
-                instruction: Instruction::PWCTR5,
-                delay: None,
-                arguments: vec![0x8A, 0xEE],
-            },
-            Command {
-                instruction: Instruction::VMCTR1,
-                delay: None,
-                arguments: vec![0x0E],
-            },
-            Command {
-                instruction: Instruction::INVOFF,
-                delay: None,
-                arguments: vec![],
-            },
-            Command {
-                instruction: Instruction::MADCTL,
-                delay: None,
-                arguments: vec![0x00],
-            },
-            Command {
-                instruction: Instruction::DISPON,
-                delay: None,
-                arguments: vec![],
-            },
-        ];
-
-        self.execute_commands(init_commands);
-    }
-
-    /// Pulses the clock one time.
-    fn pulse_clock(&mut self) {
-        if let Some(ref mut clk) = self.clk {
-            clk.set_high();
-            clk.set_low();
+    fn init(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        self.hard_reset()?;
+        self.configure()
+    }
+
+    /// Sends the software reset and configuration commands.
+    fn configure(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        let init_commands = self.controller.init_commands(self.pixel_format, &self.timing);
+        for cmd in init_commands.commands() {
+            self.execute_command(cmd)?;
+        }
+        Ok(())
+    }
+
+    /// Resets the display with the software reset command and restores the configuration,
+    /// orientation, scrolling, tearing effect and on/off settings made since the driver was created.
+    ///
+    /// The display memory is lost, so the screen content has to be redrawn afterwards.
+    pub fn soft_reset(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        self.configure()?;
+        self.restore_settings()
+    }
+
+    /// Runs the complete initialization sequence including a hard reset again and restores all
+    /// settings like `soft_reset`.
+    ///
+    /// This recovers panels that locked up, e.g. due to ESD or noise on long cables. The display
+    /// memory is lost, so the screen content has to be redrawn afterwards.
+    pub fn reinit(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        self.hard_reset()?;
+        self.soft_reset()
+    }
+
+    /// Sends the settings tracked by the driver to the display again after a reset.
+    fn restore_settings(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        let orientation = self.orientation;
+        self.set_orientation(&orientation)?;
+        if let Some((top_fixed, bottom_fixed)) = self.scroll_area {
+            self.set_scroll_area(top_fixed, bottom_fixed)?;
+        }
+        if self.scroll_start != 0 {
+            let line = self.scroll_start;
+            self.set_scroll_start(line)?;
+        }
+        if self.tearing_effect {
+            self.set_tearing_effect(true)?;
+        }
+        if self.display_mode != DisplayMode::Normal {
+            let mode = self.display_mode;
+            self.set_display_mode(mode)?;
+        }
+        if self.idle_mode {
+            self.set_idle_mode(true)?;
         }
+        if self.inverted {
+            self.set_inverted(true)?;
+        }
+        if self.gamma_curve != GammaCurve::Curve1 {
+            let curve = self.gamma_curve;
+            self.set_gamma_curve(curve)?;
+        }
+        if !self.display_enabled {
+            self.display_off()?;
+        }
+        Ok(())
     }
 
     /// Resets the display using the rst pin.
-    pub fn hard_reset(&mut self) {
+    ///
+    /// The reset line is active low. It is held low and released for the durations configured
+    /// with `set_timing`, by default 1 ms and 120 ms.
+    pub fn hard_reset(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        if let Some(ref mut rst) = self.rst {
+            rst.set_high().map_err(Error::Pin)?;
+            self.delay.delay_ms(1);
+            rst.set_low().map_err(Error::Pin)?;
+            self.delay.delay_ms(self.timing.reset_pulse);
+            rst.set_high().map_err(Error::Pin)?;
+            self.delay.delay_ms(self.timing.reset_wait);
+        }
+        Ok(())
+    }
+
+    /// Sets the pin that enables the panel supply, for devices that switch off the 3.3V rail of
+    /// the panel to save power. `active_high` is `false` for supplies enabled by pulling the pin
+    /// low, e.g. with a P-channel MOSFET high-side switch.
+    ///
+    /// The constructors initialize the display before the pin is known, so the supply is
+    /// considered off until `power_on` is called. Use `power_off` and `power_on` to cycle it.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut display = ST7734::new_with_spi(spi, dc, delay);
+    /// display.set_power_pin(power_enable, true);
+    /// display.power_on();
+    ///
+    /// // Before entering deep sleep:
+    /// display.power_off();
+    /// ```
+    pub fn set_power_pin(&mut self, pin: PIN, active_high: bool) {
+        self.power = Some(pin);
+        self.power_active_high = active_high;
+        self.powered = false;
+    }
+
+    /// Sets the chip select pin, which is active low, and deselects the display.
+    ///
+    /// Each command is sent together with its arguments while the display is selected, and pixel
+    /// data written after RAMWR is streamed within the same selection, so the display can share
+    /// the bus with other devices. The constructors initialize the display before the pin is
+    /// known, call `reinit` if the display was not selected during initialization.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut display = ST7734::new_with_spi(spi, dc, delay);
+    /// display.set_cs_pin(cs)?;
+    /// display.reinit()?;
+    /// ```
+    pub fn set_cs_pin(&mut self, mut pin: PIN) -> Result<(), DisplayError<SPI, PIN>> {
+        pin.set_high().map_err(Error::Pin)?;
+        self.cs = Some(pin);
+        Ok(())
+    }
+
+    /// Enables the panel supply, waits for it to settle and initializes the display like
+    /// `reinit`, restoring all settings. Does nothing if the supply is already enabled and the
+    /// display was initialized since.
+    ///
+    /// The display memory content is lost, so the screen has to be redrawn afterwards.
+    pub fn power_on(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        if self.powered && self.power.is_some() {
+            return Ok(());
+        }
+
         if let Some(ref mut rst) = self.rst {
-            rst.set_high();
-            rst.set_low();
+            rst.set_low().map_err(Error::Pin)?;
         }
+        self.set_power(true)?;
+        self.delay.delay_ms(self.timing.power_up);
+        self.reinit()
+    }
+
+    /// Puts the display to sleep, holds it in reset and cuts the panel supply, if there is a
+    /// power pin. Holding the reset line low keeps it from back-powering the panel.
+    pub fn power_off(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        // Keep the tracked setting, so that `power_on` restores it.
+        let enabled = self.display_enabled;
+        let result = self.display_off();
+        self.display_enabled = enabled;
+        result?;
+        self.write_command(Instruction::SLPIN, &[])?;
+        self.delay.delay_ms(self.timing.power_down);
+
+        if self.power.is_some() {
+            if let Some(ref mut rst) = self.rst {
+                rst.set_low().map_err(Error::Pin)?;
+            }
+            self.set_power(false)?;
+        }
+        Ok(())
+    }
+
+    /// Returns `false` while the panel supply is cut with `power_off`.
+    pub fn is_powered(&self) -> bool {
+        self.powered || self.power.is_none()
+    }
+
+    /// Sets the power pin to enable or disable the panel supply.
+    fn set_power(&mut self, enabled: bool) -> Result<(), DisplayError<SPI, PIN>> {
+        if let Some(ref mut power) = self.power {
+            if enabled == self.power_active_high {
+                power.set_high().map_err(Error::Pin)?;
+            } else {
+                power.set_low().map_err(Error::Pin)?;
+            }
+        }
+        self.powered = enabled;
+        Ok(())
+    }
+
+    /// Limits the number of bytes sent in a single SPI write. Larger payloads are split into
+    /// several writes. Needed for SPI implementations with a maximum transfer size, such as
+    /// Linux spidev. `None` removes the limit.
+    pub fn set_max_transfer_size(&mut self, size: Option<usize>) {
+        self.max_transfer_size = size;
+    }
+
+    /// Provides the buffer pixels are encoded into before they are sent by all drawing
+    /// operations, instead of a small buffer on the stack. A `static` buffer can be placed in
+    /// memory reachable by DMA, and a larger buffer means fewer, longer SPI writes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is shorter than `MIN_TRANSFER_BUFFER_BYTES`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// static BUFFER: StaticCell<[u8; 4096]> = StaticCell::new();
+    ///
+    /// display.set_transfer_buffer(BUFFER.init([0; 4096]));
+    /// ```
+    pub fn set_transfer_buffer(&mut self, buffer: &'static mut [u8]) {
+        assert!(buffer.len() >= MIN_TRANSFER_BUFFER_BYTES);
+        self.transfer_buffer = Some(buffer);
+    }
+
+    /// Removes the transfer buffer and returns it. Drawing uses the buffer on the stack again.
+    pub fn take_transfer_buffer(&mut self) -> Option<&'static mut [u8]> {
+        self.transfer_buffer.take()
+    }
+
+    /// Changes the delays used by `hard_reset`, `soft_reset` and `reinit`. The constructors
+    /// always use the default timing, call `reinit` to initialize the display again with the
+    /// new delays.
+    pub fn set_timing(&mut self, timing: Timing) {
+        self.timing = timing;
+    }
+
+    /// Returns the delays used when resetting the display.
+    pub fn timing(&self) -> Timing {
+        self.timing
     }
 
     /// Writes one byte to the display which can either be a command or data.
-    fn write_byte(&mut self, value: u8, data: bool) {
+    fn write_byte(&mut self, value: u8, data: bool) -> Result<(), DisplayError<SPI, PIN>> {
         if let Some(ref mut dc) = self.dc {
             match data {
                 false => dc.set_low(),
                 true => dc.set_high(),
             }
+            .map_err(Error::Pin)?;
         }
 
+        self.write_bytes(&[value])
+    }
+
+    /// Writes bytes to the display without changing the data/command pin.
+    fn write_bytes(&mut self, data: &[u8]) -> Result<(), DisplayError<SPI, PIN>> {
         if let Some(ref mut spi) = self.spi {
-            let _ = spi.write(&[value]);
-        } else {
-            let mask = 0x80;
-            for bit in 0..8 {
-                if let Some(ref mut mosi) = self.mosi {
-                    match value & (mask >> bit) {
-                        0 => mosi.set_low(),
-                        _ => mosi.set_high()
+            let chunk_size = self.max_transfer_size.unwrap_or(data.len()).max(1);
+            for chunk in data.chunks(chunk_size) {
+                self.stats.bytes_transmitted += chunk.len() as u64;
+                self.stats.transactions += 1;
+                spi.write(chunk).map_err(Error::Spi)?;
+            }
+        } else if let (Some(clk), Some(mosi)) = (self.clk.as_mut(), self.mosi.as_mut()) {
+            self.stats.bytes_transmitted += data.len() as u64;
+            self.stats.transactions += 1;
+
+            // Tight loop without per-byte calls, which dominate the cost of software SPI.
+            for byte in data {
+                for bit in 0..8 {
+                    if byte & (0x80 >> bit) != 0 {
+                        mosi.set_high().map_err(Error::Pin)?;
+                    } else {
+                        mosi.set_low().map_err(Error::Pin)?;
                     }
+                    clk.set_high().map_err(Error::Pin)?;
+                    clk.set_low().map_err(Error::Pin)?;
                 }
-                self.pulse_clock();
             }
         }
+        Ok(())
     }
 
-    /// Writes a bulk of pixels to the display.
-    fn write_bulk(&mut self, color: &Color, repetitions: u16, count: u16) {
-        if let Some(ref mut dc) = self.dc {
-            dc.set_low();
-        }
-
-        self.write_byte(num::ToPrimitive::to_u8(&Instruction::RAMWR).unwrap(), false);
+    /// Writes `count` pixels of the same `color` to the display, transferring as many pixels at
+    /// once as the transfer buffer holds.
+    fn write_bulk(&mut self, color: &Color, count: u32) -> Result<(), DisplayError<SPI, PIN>> {
+        self.with_transfer_buffer(|display, buffer| {
+            // An even number of pixels, so packed pixel pairs are not split between writes.
+            let chunk = min(count as usize, (buffer.len() / 3) & !1);
+            let format = display.pixel_format;
+            let mut encoder = PixelEncoder::new(format, display.byte_order);
+            let mut writer = SliceWriter::new(buffer);
+            for _ in 0..chunk {
+                encoder.push(color, &mut writer);
+            }
+            encoder.finish(&mut writer);
+            let encoded = writer.written();
 
-        for _ in 0..=count {
-            if let Some(ref mut spi) = self.spi {
-                if let Some(ref mut dc) = self.dc {
-                    dc.set_high();
+            display.stats.pixels_written += count as u64;
+            display.framed(|display| {
+                display.write_byte(num::ToPrimitive::to_u8(&Instruction::RAMWR).unwrap(), false)?;
+                let mut remaining = count as usize;
+                while remaining > 0 {
+                    let length = min(remaining, chunk);
+                    display.write_data(&encoded[..format.bytes(length)])?;
+                    remaining -= length;
                 }
+                Ok(())
+            })
+        })
+    }
 
-                let bytes: [u8; 2] = unsafe { transmute(color.hex.to_be()) };
-                let mut byte_array = vec![bytes[0], bytes[1]];
+    /// Runs `transfer` with the transfer buffer set with `set_transfer_buffer`, or a buffer on
+    /// the stack if there is none.
+    fn with_transfer_buffer<F>(&mut self, transfer: F) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        F: FnOnce(&mut Self, &mut [u8]) -> Result<(), DisplayError<SPI, PIN>>,
+    {
+        let mut stack = [0; TRANSFER_BUFFER_BYTES];
+        let mut provided = self.transfer_buffer.take();
+        let buffer: &mut [u8] = match provided {
+            Some(ref mut buffer) => buffer,
+            None => &mut stack,
+        };
+        let result = transfer(self, buffer);
+        self.transfer_buffer = provided;
+        result
+    }
 
-                for _ in 0..=repetitions {
-                    byte_array = [&byte_array[..], &bytes[..]].concat()
-                }
-                let _ = spi.write(&byte_array);
-            } else {
-                for _ in 0..=repetitions {
-                    self.write_color(color);
-                }
+    /// Sends a command with its arguments while the display is selected.
+    fn write_command(
+        &mut self,
+        instruction: Instruction,
+        arguments: &[u8],
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        self.framed(|display| {
+            display.write_byte(num::ToPrimitive::to_u8(&instruction).unwrap(), false)?;
+            if arguments.is_empty() {
+                return Ok(());
             }
+            display.write_data(arguments)
+        })
+    }
+
+    /// Selects the display with the chip select pin, runs `transfer` and deselects it again,
+    /// even if `transfer` failed. Returns the first error.
+    fn framed<F>(&mut self, transfer: F) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        F: FnOnce(&mut Self) -> Result<(), DisplayError<SPI, PIN>>,
+    {
+        self.select()?;
+        let result = transfer(self);
+        result.and(self.deselect())
+    }
+
+    /// Pulls the chip select pin low, if there is one.
+    fn select(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        if let Some(ref mut cs) = self.cs {
+            cs.set_low().map_err(Error::Pin)?;
+        }
+        Ok(())
+    }
+
+    /// Releases the chip select pin, if there is one.
+    fn deselect(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        if let Some(ref mut cs) = self.cs {
+            cs.set_high().map_err(Error::Pin)?;
         }
+        Ok(())
     }
 
-    /// Writes a data word to the display.
-    fn write_word(&mut self, value: u16) {
-        let bytes: [u8; 2] = unsafe { transmute(value.to_be()) };
-        self.write_byte(bytes[0], true);
-        self.write_byte(bytes[1], true);
+    /// Writes a slice of data bytes to the display.
+    fn write_data(&mut self, data: &[u8]) -> Result<(), DisplayError<SPI, PIN>> {
+        if let Some(ref mut dc) = self.dc {
+            dc.set_high().map_err(Error::Pin)?;
+        }
+
+        self.write_bytes(data)
+    }
+
+    /// Fills `area` with the colors returned by `pixel` for each of its coordinates. The area is
+    /// clipped to the display bounds and `pixel` is only called for visible coordinates.
+    ///
+    /// The address window is set once and pixels are transferred one row at a time, which makes
+    /// this the fastest way to draw gradients and procedurally generated content.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let screen = display.bounds();
+    /// display.fill_region(&screen, |x, y| Color::from_hsv(x + y, 255, 255));
+    /// ```
+    pub fn fill_region<F>(&mut self, area: &Rect, mut pixel: F) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        F: FnMut(u16, u16) -> Color,
+    {
+        let area = match area.intersection(&self.bounds()) {
+            Some(a) => a,
+            None => return Ok(()),
+        };
+
+        self.set_address_window(area.x0, area.y0, area.x1, area.y1)?;
+        let pixels = (area.y0..=area.y1)
+            .flat_map(|y| (area.x0..=area.x1).map(move |x| (x, y)))
+            .map(|(x, y)| pixel(x, y));
+        self.write_memory(pixels)
+    }
+
+    /// Renders the whole screen in horizontal bands using `buffer` as a reusable framebuffer.
+    ///
+    /// Each band is as high as `buffer` holds full rows. It is cleared to black, passed to `draw`
+    /// and then transferred to the display, so `draw` is called once per band and should draw
+    /// the complete scene; drawing outside of the current band is ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` can't hold at least one row of the display.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut buffer = [Color::from_hex(0); 128 * 16];
+    /// display.render_banded(&mut buffer, |band| {
+    ///     band.fill_rect(10, 10, 100, 100, &Color::from_default(DefaultColor::Blue));
+    ///     band.draw_image(40, 40, &sprite);
+    /// });
+    /// ```
+    #[cfg(feature = "framebuffer")]
+    pub fn render_banded<F>(
+        &mut self,
+        buffer: &mut [Color],
+        mut draw: F,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        F: FnMut(&mut Band),
+    {
+        let width = self.width();
+        let height = self.height();
+        let rows = (buffer.len() / width as usize) as u16;
+        assert!(rows > 0);
+
+        let mut y = 0;
+        while y < height {
+            let area = Rect::new(0, y, width - 1, min(y + rows, height) - 1);
+            let pixels = &mut buffer[..area.width() as usize * area.height() as usize];
+
+            let mut band = Band::new(pixels, area);
+            band.clear(&Color::from_default(DefaultColor::Black));
+            draw(&mut band);
+
+            self.fill_region(&area, |x, y| {
+                pixels[(y - area.y0) as usize * area.width() as usize + x as usize]
+            })?;
+            y = area.y1 + 1;
+        }
+        Ok(())
     }
 
     /// Sends a list of commands to the display.
-    fn execute_commands(&mut self, commands: Vec<Command>) {
-        for cmd in &commands {
-            self.execute_command(cmd);
+    pub fn execute_commands(&mut self, commands: &[Command]) -> Result<(), DisplayError<SPI, PIN>> {
+        for cmd in commands {
+            self.execute_command(cmd)?;
         }
+        Ok(())
+    }
+
+    /// Sends all commands of `queue` to the display.
+    pub fn execute_queue<const N: usize>(
+        &mut self,
+        queue: &CommandQueue<N>,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        self.execute_commands(queue.commands())
     }
 
     /// Sends a single command to the display.
-    fn execute_command(&mut self, cmd: &Command) {
-        self.write_byte(num::ToPrimitive::to_u8(&cmd.instruction).unwrap(), false);
+    fn execute_command(&mut self, cmd: &Command) -> Result<(), DisplayError<SPI, PIN>> {
+        self.write_command(cmd.instruction, &cmd.arguments)?;
 
-        match cmd.delay {
-            Some(d) => {
-                if cmd.arguments.len() > 0 {
-                    self.delay.delay_ms(d);
+        if let Some(d) = cmd.delay {
+            self.delay.delay_ms(d);
+        }
+        Ok(())
+    }
+
+    /// Writes a sequence of pixels to the display memory in the current pixel format, starting
+    /// at the address window. Pixels are encoded into the transfer buffer, which is transferred
+    /// whenever it is full.
+    fn write_memory<I>(&mut self, colors: I) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        I: IntoIterator<Item = Color>,
+    {
+        self.with_transfer_buffer(|display, buffer| {
+            let capacity = buffer.len();
+            let mut encoder = PixelEncoder::new(display.pixel_format, display.byte_order);
+            let mut writer = SliceWriter::new(buffer);
+            display.framed(|display| {
+                display.write_byte(num::ToPrimitive::to_u8(&Instruction::RAMWR).unwrap(), false)?;
+                for color in colors {
+                    encoder.push(&color, &mut writer);
+                    display.stats.pixels_written += 1;
+                    // Leave room for the largest encoded pixel.
+                    if writer.len() > capacity - 3 {
+                        display.write_data(writer.written())?;
+                        writer.clear();
+                    }
                 }
-            }
-            None => {
-                for argument in &cmd.arguments {
-                    self.write_byte(*argument, true);
+                encoder.finish(&mut writer);
+                if writer.len() == 0 {
+                    return Ok(());
                 }
-            }
+                display.write_data(writer.written())
+            })
+        })
+    }
+
+    /// Sets the address window for the display.
+    fn set_address_window(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        self.stats.address_windows += 1;
+        let (x0, y0, x1, y1) = self.memory_window(x0, y0, x1, y1);
+        let (x0, x1) = (x0.to_be_bytes(), x1.to_be_bytes());
+        let (y0, y1) = (y0.to_be_bytes(), y1.to_be_bytes());
+        self.write_command(Instruction::CASET, &[x0[0], x0[1], x1[0], x1[1]])?;
+        self.write_command(Instruction::RASET, &[y0[0], y0[1], y1[0], y1[1]])
+    }
+
+    /// Converts display coordinates into controller memory coordinates.
+    fn memory_window(&self, x0: u16, y0: u16, x1: u16, y1: u16) -> (u16, u16, u16, u16) {
+        let (x_offset, y_offset) = self.controller.offset(self.orientation);
+        let (column, row) = self.offset;
+        let (x_offset, y_offset) = match self.orientation {
+            Orientation::Portrait | Orientation::PortraitSwapped => (x_offset + column, y_offset + row),
+            Orientation::Landscape | Orientation::LandScapeSwapped => (x_offset + row, y_offset + column),
+        };
+        (x0 + x_offset, y0 + y_offset, x1 + x_offset, y1 + y_offset)
+    }
+
+    /// Sends the commands that start writing to `area` of the display memory and sets the
+    /// data/command pin for data, so that the pixel data can be sent by other means, e.g. DMA.
+    ///
+    /// The display stays selected until `end_memory_write` is called after the transfer.
+    pub fn begin_memory_write(&mut self, area: &Rect) -> Result<(), DisplayError<SPI, PIN>> {
+        self.set_address_window(area.x0, area.y0, area.x1, area.y1)?;
+        self.select()?;
+        self.write_byte(num::ToPrimitive::to_u8(&Instruction::RAMWR).unwrap(), false)?;
+        if let Some(ref mut dc) = self.dc {
+            dc.set_high().map_err(Error::Pin)?;
         }
+        Ok(())
     }
 
-    /// Sets the color to be used.
-    fn write_color(&mut self, color: &Color) {
-        let bytes: [u8; 2] = unsafe { transmute(color.hex.to_be()) };
+    /// Deselects the display after the pixel data of `begin_memory_write` was transferred.
+    pub fn end_memory_write(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        self.deselect()
+    }
 
-        if let Some(ref mut spi) = self.spi {
-            if let Some(ref mut dc) = self.dc {
-                dc.set_high();
-            }
+    /// Returns the commands that start writing to `area` of the display memory: CASET and RASET
+    /// with the address window, followed by RAMWR. Together with `encode_region` this allows
+    /// transferring pixels with custom DMA code.
+    ///
+    /// Instruction bytes are sent with the data/command pin low, arguments and pixel data with
+    /// the pin high.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let area = Rect::new(0, 0, 127, 15);
+    /// let mut payload = [0; 128 * 16 * 2];
+    /// let length = display.encode_region(&area, &mut payload, |x, y| Color::from_hsv(x, 255, 255));
+    ///
+    /// for command in display.memory_write_commands(&area).iter() {
+    ///     send_command(command.instruction as u8, &command.arguments);
+    /// }
+    /// start_dma(&payload[..length]);
+    /// ```
+    pub fn memory_write_commands(&self, area: &Rect) -> [Command; 3] {
+        let (x0, y0, x1, y1) = self.memory_window(area.x0, area.y0, area.x1, area.y1);
+        let x0 = x0.to_be_bytes();
+        let x1 = x1.to_be_bytes();
+        let y0 = y0.to_be_bytes();
+        let y1 = y1.to_be_bytes();
+        [
+            Command::new(Instruction::CASET, &[x0[0], x0[1], x1[0], x1[1]], None),
+            Command::new(Instruction::RASET, &[y0[0], y0[1], y1[0], y1[1]], None),
+            Command::new(Instruction::RAMWR, &[], None),
+        ]
+    }
 
-            let _ = spi.write(&[bytes[0], bytes[1]]);
-        } else {
-            self.write_byte(bytes[0], true);
-            self.write_byte(bytes[1], true);
+    /// Writes the pixel data of `area` as sent after RAMWR into `buffer`, encoded in the current
+    /// pixel format and byte order. Returns the number of bytes written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is too small, see `PixelFormat::bytes`.
+    pub fn encode_region<F>(&self, area: &Rect, buffer: &mut [u8], mut pixel: F) -> usize
+    where
+        F: FnMut(u16, u16) -> Color,
+    {
+        let mut encoder = PixelEncoder::new(self.pixel_format, self.byte_order);
+        let mut writer = SliceWriter::new(buffer);
+        for y in area.y0..=area.y1 {
+            for x in area.x0..=area.x1 {
+                encoder.push(&pixel(x, y), &mut writer);
+            }
         }
+        encoder.finish(&mut writer);
+        writer.len()
     }
 
-    /// Sets the address window for the display.
-    fn set_address_window(&mut self, x0: u16, y0: u16, x1: u16, y1: u16) {
-        self.write_byte(num::ToPrimitive::to_u8(&Instruction::CASET).unwrap(), false);
-        self.write_word(x0);
-        self.write_word(x1);
-        self.write_byte(num::ToPrimitive::to_u8(&Instruction::RASET).unwrap(), false);
-        self.write_word(y0);
-        self.write_word(y1);
+    /// Returns the bus traffic counters accumulated since they were last reset.
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Resets all bus traffic counters to zero.
+    pub fn reset_stats(&mut self) {
+        self.stats = Stats::default();
     }
 
     /// Changes the display orientation.
-    pub fn set_orientation(&mut self, orientation: &Orientation) {
-        let command = Command {
-            instruction: Instruction::MADCTL,
-            delay: None,
-            arguments: vec![num::ToPrimitive::to_u8(orientation).unwrap()],
-        };
-        self.execute_command(&command);
+    pub fn set_orientation(&mut self, orientation: &Orientation) -> Result<(), DisplayError<SPI, PIN>> {
+        let command = Command::new(Instruction::MADCTL, &[self.controller.madctl(*orientation)], None);
+        self.execute_command(&command)?;
+        self.orientation = *orientation;
+        Ok(())
+    }
+
+    /// Returns the current display orientation.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Sets the size of the panel in portrait orientation. Defaults to 128x160 pixels.
+    pub fn set_panel_size(&mut self, width: u16, height: u16) {
+        self.panel_width = width;
+        self.panel_height = height;
+    }
+
+    /// Sets the column and row of the controller memory shown at the top left corner of the
+    /// panel in portrait orientation, for modules whose panel doesn't start at the first memory
+    /// column or row. The offset is added to the one of the controller and swapped in the
+    /// landscape orientations.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // 1.8" modules with a green tab
+    /// display.set_offset(2, 1);
+    /// ```
+    pub fn set_offset(&mut self, column: u16, row: u16) {
+        self.offset = (column, row);
+    }
+
+    /// Returns the offset set with `set_offset`.
+    pub fn offset(&self) -> (u16, u16) {
+        self.offset
+    }
+
+    /// Returns the width of the display in pixels for the current orientation.
+    pub fn width(&self) -> u16 {
+        match self.orientation {
+            Orientation::Portrait | Orientation::PortraitSwapped => self.panel_width,
+            Orientation::Landscape | Orientation::LandScapeSwapped => self.panel_height,
+        }
+    }
+
+    /// Returns the height of the display in pixels for the current orientation.
+    pub fn height(&self) -> u16 {
+        match self.orientation {
+            Orientation::Portrait | Orientation::PortraitSwapped => self.panel_height,
+            Orientation::Landscape | Orientation::LandScapeSwapped => self.panel_width,
+        }
+    }
+
+    /// Returns the area covering the whole display in the current orientation.
+    pub fn bounds(&self) -> Rect {
+        Rect::new(0, 0, self.width() - 1, self.height() - 1)
+    }
+
+    /// Splits the display vertically into a fixed area of `top_fixed` lines, a scrolling area and
+    /// a fixed area of `bottom_fixed` lines at the bottom. The areas are counted in lines of the
+    /// controller memory, which may have more lines than the panel shows, and the scrolling area
+    /// spans the remaining memory lines. Returns `Error::OutOfBounds` if the fixed areas are
+    /// higher than the controller memory.
+    pub fn set_scroll_area(
+        &mut self,
+        top_fixed: u16,
+        bottom_fixed: u16,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        let scroll_height = self
+            .controller
+            .memory_height()
+            .checked_sub(top_fixed)
+            .and_then(|height| height.checked_sub(bottom_fixed))
+            .ok_or(Error::OutOfBounds)?;
+        let top = top_fixed.to_be_bytes();
+        let height = scroll_height.to_be_bytes();
+        let bottom = bottom_fixed.to_be_bytes();
+        self.write_command(Instruction::VSCRDEF, &[top[0], top[1], height[0], height[1], bottom[0], bottom[1]])?;
+        self.scroll_area = Some((top_fixed, bottom_fixed));
+        Ok(())
+    }
+
+    /// Sets the display memory line that is shown at the top of the scrolling area.
+    pub fn set_scroll_start(&mut self, line: u16) -> Result<(), DisplayError<SPI, PIN>> {
+        self.write_command(Instruction::VSCRSADD, &line.to_be_bytes())?;
+        self.scroll_start = line;
+        Ok(())
     }
 
     /// Draws a single pixel with the specified `color` at the defined coordinates on the display.
-    pub fn draw_pixel(&mut self, x: u16, y: u16, color: &Color) {
-        self.set_address_window(x, y, x, y);
-        self.write_byte(num::ToPrimitive::to_u8(&Instruction::RAMWR).unwrap(), false);
-        self.write_color(color);
+    /// Pixels outside of the display are ignored.
+    pub fn draw_pixel(&mut self, x: u16, y: u16, color: &Color) -> Result<(), DisplayError<SPI, PIN>> {
+        if !self.bounds().contains(x, y) {
+            return Ok(());
+        }
+
+        self.set_address_window(x, y, x, y)?;
+        self.write_memory(Some(*color))
     }
 
-    /// Draws a filled rectangle with the specified `color` on the display.
-    pub fn draw_filled_rect(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, color: &Color) {
-        let width = x1 - x0 + 1;
-        let height = y1 - y0 + 1;
-        self.set_address_window(x0, y0, x1, y1);
-        self.write_bulk(color, width, height);
+    /// Draws a single pixel at signed coordinates. Pixels outside of the display are ignored.
+    pub fn draw_pixel_signed(&mut self, x: i32, y: i32, color: &Color) -> Result<(), DisplayError<SPI, PIN>> {
+        if x >= 0 && y >= 0 && x <= u16::MAX as i32 && y <= u16::MAX as i32 {
+            self.draw_pixel(x as u16, y as u16, color)?;
+        }
+        Ok(())
+    }
+
+    /// Draws a set of individually colored pixels on the display.
+    ///
+    /// Pixels are sorted and grouped into horizontal runs, so that each run of adjacent pixels
+    /// only needs a single address window and transfer. If a position occurs more than once, the
+    /// last occurrence wins. Pixels outside of the display are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let red = Color::from_default(DefaultColor::Red);
+    /// display.draw_pixels((0..100).map(|i| (i, i / 2, red)));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn draw_pixels<I>(&mut self, pixels: I) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        I: IntoIterator<Item = (u16, u16, Color)>,
+    {
+        let bounds = self.bounds();
+        let mut pixels: Vec<(u16, u16, Color)> = pixels
+            .into_iter()
+            .filter(|&(x, y, _)| bounds.contains(x, y))
+            .collect();
+        pixels.sort_by_key(|&(x, y, _)| (y, x));
+
+        let mut run: Vec<Color> = Vec::new();
+        let mut start = 0;
+        for i in 0..pixels.len() {
+            let (x, y, color) = pixels[i];
+            if i > start {
+                let (previous_x, previous_y, _) = pixels[i - 1];
+                if previous_x == x && previous_y == y {
+                    let length = run.len();
+                    run[length - 1] = color;
+                    continue;
+                }
+
+                if previous_y != y || previous_x + 1 != x {
+                    let (start_x, start_y, _) = pixels[start];
+                    self.set_address_window(start_x, start_y, previous_x, previous_y)?;
+                    self.write_memory(run.iter().cloned())?;
+                    run.clear();
+                    start = i;
+                }
+            }
+
+            run.push(color);
+        }
+
+        if let Some(&(end_x, end_y, _)) = pixels.last() {
+            let (start_x, start_y, _) = pixels[start];
+            self.set_address_window(start_x, start_y, end_x, end_y)?;
+            self.write_memory(run.iter().cloned())?;
+        }
+        Ok(())
+    }
+
+    /// Draws a filled rectangle with the specified `color` on the display. The rectangle is
+    /// clipped to the display bounds.
+    pub fn draw_filled_rect(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        let area = match Rect::new(x0, y0, x1, y1).intersection(&self.bounds()) {
+            Some(a) => a,
+            None => return Ok(()),
+        };
+
+        self.set_address_window(area.x0, area.y0, area.x1, area.y1)?;
+        self.write_bulk(color, area.width() as u32 * area.height() as u32)
+    }
+
+    /// Draws a filled rectangle at signed coordinates, so it may be partially off-screen.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Slide a block in from the left edge.
+    /// for x in -16..0 {
+    ///     display.draw_filled_rect_signed(x, 40, x + 15, 55, &Color::from_default(DefaultColor::Red));
+    /// }
+    /// ```
+    pub fn draw_filled_rect_signed(
+        &mut self,
+        x0: i32,
+        y0: i32,
+        x1: i32,
+        y1: i32,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        if let Some(area) = Rect::clipped(x0, y0, x1, y1) {
+            self.draw_filled_rect(area.x0, area.y0, area.x1, area.y1, color)?;
+        }
+        Ok(())
     }
 
     /// Draws a rectangle with the specified `color` as border color on the display.
-    pub fn draw_rect(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, color: &Color) {
-        self.draw_horizontal_line(x0, x1, y0, color);
-        self.draw_horizontal_line(x0, x1, y1, color);
-        self.draw_vertical_line(x0, y0, y1, color);
-        self.draw_vertical_line(x1, y0, y1, color);
+    #[cfg(feature = "graphics-primitives")]
+    pub fn draw_rect(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        self.draw_horizontal_line(x0, x1, y0, color)?;
+        self.draw_horizontal_line(x0, x1, y1, color)?;
+        self.draw_vertical_line(x0, y0, y1, color)?;
+        self.draw_vertical_line(x1, y0, y1, color)
+    }
+
+    /// Draws a rectangle border at signed coordinates, so it may be partially off-screen.
+    #[cfg(feature = "graphics-primitives")]
+    pub fn draw_rect_signed(
+        &mut self,
+        x0: i32,
+        y0: i32,
+        x1: i32,
+        y1: i32,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        self.draw_filled_rect_signed(x0, y0, x1, y0, color)?;
+        self.draw_filled_rect_signed(x0, y1, x1, y1, color)?;
+        self.draw_filled_rect_signed(x0, y0, x0, y1, color)?;
+        self.draw_filled_rect_signed(x1, y0, x1, y1, color)
     }
 
     /// Draws a horizontal with the specified `color` between the provided coordinates on the display.
-    pub fn draw_horizontal_line(&mut self, x0: u16, x1: u16, y: u16, color: &Color) {
-        let length = x1 - x0 + 1;
-        self.set_address_window(x0, y, x1, y);
-        self.write_bulk(color, length, 1);
+    #[cfg(feature = "graphics-primitives")]
+    pub fn draw_horizontal_line(
+        &mut self,
+        x0: u16,
+        x1: u16,
+        y: u16,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        self.draw_filled_rect(x0, y, x1, y, color)
     }
 
     /// Draws a vertical with the specified `color` between the provided coordinates on the display.
-    pub fn draw_vertical_line(&mut self, x: u16, y0: u16, y1: u16, color: &Color) {
-        let length = y1 - y0 + 1;
-        self.set_address_window(x, y0, x, y1);
-        self.write_bulk(color, length, 1);
+    #[cfg(feature = "graphics-primitives")]
+    pub fn draw_vertical_line(
+        &mut self,
+        x: u16,
+        y0: u16,
+        y1: u16,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        self.draw_filled_rect(x, y0, x, y1, color)
     }
 
     /// Draws a line with the specified `color` between the provided coordinates on the display.
-    pub fn draw_line(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, color: &Color) {
-        if x0 == x1 {
-            self.draw_vertical_line(x0, y0, y1, color);
-        } else if y0 == y1 {
-            self.draw_horizontal_line(x0, x1, y1, color);
+    /// The endpoints can be given in any order.
+    #[cfg(feature = "graphics-primitives")]
+    pub fn draw_line(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        self.draw_line_signed(x0 as i32, y0 as i32, x1 as i32, y1 as i32, color)
+    }
+
+    /// Draws a line between signed coordinates. Parts of the line outside of the display are ignored.
+    #[cfg(feature = "graphics-primitives")]
+    pub fn draw_line_signed(
+        &mut self,
+        x0: i32,
+        y0: i32,
+        x1: i32,
+        y1: i32,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        if x0 == x1 || y0 == y1 {
+            self.draw_filled_rect_signed(x0, y0, x1, y1, color)?;
         } else {
-            let m = ((max(y1, y0) - min(y0, y1)) as f32) / ((max(x1, x0) - min(x0, x1)) as f32);
+            // Bresenham's line algorithm.
+            let (mut x, mut y) = (x0, y0);
+            let dx = (x1 - x).abs();
+            let dy = -(y1 - y).abs();
+            let step_x = if x < x1 { 1 } else { -1 };
+            let step_y = if y < y1 { 1 } else { -1 };
+            let mut error = dx + dy;
 
-            if m < 1.0 {
-                for x in x0..=x1 {
-                    let y = ((x - x0) as f32) * m + (y0 as f32);
-                    self.draw_pixel(x, y as u16, color);
+            let mut pixels = Vec::with_capacity(dx.max(-dy) as usize + 1);
+            loop {
+                if x >= 0 && y >= 0 && x <= u16::MAX as i32 && y <= u16::MAX as i32 {
+                    pixels.push((x as u16, y as u16, *color));
                 }
-            } else {
-                for y in y0..=y1 {
-                    let x = ((y - y0) as f32) / m + (x0 as f32);
-                    self.draw_pixel(x as u16, y, color);
+                if x == x1 && y == y1 {
+                    break;
+                }
+
+                let e2 = 2 * error;
+                if e2 >= dy {
+                    error += dy;
+                    x += step_x;
+                }
+                if e2 <= dx {
+                    error += dx;
+                    y += step_y;
                 }
             }
+            self.draw_pixels(pixels)?;
         }
+        Ok(())
     }
 
     /// Draws a circle whose border has the specified `color` around the provided coordinates on the display.
-    pub fn draw_circle(&mut self, x_pos: u16, y_pos: u16, radius: u16, color: &Color) {
-        let x_end = ((core::f32::consts::FRAC_1_SQRT_2 * (radius as f32)) + 1.0) as u16;
+    #[cfg(feature = "graphics-primitives")]
+    pub fn draw_circle(
+        &mut self,
+        x_pos: u16,
+        y_pos: u16,
+        radius: u16,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        self.draw_circle_signed(x_pos as i32, y_pos as i32, radius, color)
+    }
+
+    /// Draws a circle border around signed center coordinates. Parts of the circle outside of the
+    /// display are ignored.
+    #[cfg(feature = "graphics-primitives")]
+    pub fn draw_circle_signed(
+        &mut self,
+        cx: i32,
+        cy: i32,
+        radius: u16,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        let x_end = ((core::f32::consts::FRAC_1_SQRT_2 * (radius as f32)) + 1.0) as i32;
+        let r2 = (radius as i32) * (radius as i32);
 
+        let mut pixels = Vec::new();
         for x in 0..x_end {
-            let y = sqrt(radius * radius - x * x) as u16;
-            let u_x = x as u16;
-            self.draw_pixel(x_pos + u_x, y_pos + y, color);
-            self.draw_pixel(x_pos + u_x, y_pos - y, color);
-            self.draw_pixel(x_pos - u_x, y_pos + y, color);
-            self.draw_pixel(x_pos - u_x, y_pos - y, color);
-            self.draw_pixel(x_pos + y, y_pos + u_x, color);
-            self.draw_pixel(x_pos + y, y_pos - u_x, color);
-            self.draw_pixel(x_pos - y, y_pos + u_x, color);
-            self.draw_pixel(x_pos - y, y_pos - u_x, color);
+            let y = sqrt(r2 - x * x);
+            let octants = [
+                (cx + x, cy + y),
+                (cx + x, cy - y),
+                (cx - x, cy + y),
+                (cx - x, cy - y),
+                (cx + y, cy + x),
+                (cx + y, cy - x),
+                (cx - y, cy + x),
+                (cx - y, cy - x),
+            ];
+
+            for &(px, py) in octants.iter() {
+                if px >= 0 && py >= 0 && px <= u16::MAX as i32 && py <= u16::MAX as i32 {
+                    pixels.push((px as u16, py as u16, *color));
+                }
+            }
         }
+        self.draw_pixels(pixels)
     }
 
     /// Draws a circle filled with the specified `color` around the provided coordinates on the display.
-    pub fn draw_filled_circle(&mut self, x_pos: u16, y_pos: u16, radius: u16, color: &Color) {
-        let r2 = radius * radius;
-        for x in 0..radius {
-            let y = sqrt(r2 - x * x);
-            let y0 = y_pos - y;
-            let y1 = y_pos + y;
-            self.draw_vertical_line(x_pos + x, y0, y1, color);
-            self.draw_vertical_line(x_pos - x, y0, y1, color);
+    #[cfg(feature = "graphics-primitives")]
+    pub fn draw_filled_circle(
+        &mut self,
+        x_pos: u16,
+        y_pos: u16,
+        radius: u16,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        self.draw_filled_circle_signed(x_pos as i32, y_pos as i32, radius, color)
+    }
+
+    /// Draws a filled circle around signed center coordinates. Parts of the circle outside of the
+    /// display are ignored.
+    #[cfg(feature = "graphics-primitives")]
+    pub fn draw_filled_circle_signed(
+        &mut self,
+        x_pos: i32,
+        y_pos: i32,
+        radius: u16,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        for column in circle_columns(x_pos, y_pos, radius) {
+            self.draw_filled_rect(column.x0, column.y0, column.x1, column.y1, color)?;
+        }
+        Ok(())
+    }
+
+    /// Draws a rectangle filled with the specified `pattern` on the display.
+    #[cfg(feature = "graphics-primitives")]
+    pub fn draw_patterned_rect(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        pattern: &Pattern,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        self.fill_region(&Rect::new(x0, y0, x1, y1), |x, y| pattern.color_at(x, y))
+    }
+
+    /// Draws a circle filled with the specified `pattern` around the provided coordinates on the display.
+    #[cfg(feature = "graphics-primitives")]
+    pub fn draw_patterned_circle(
+        &mut self,
+        x_pos: u16,
+        y_pos: u16,
+        radius: u16,
+        pattern: &Pattern,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        for column in circle_columns(x_pos as i32, y_pos as i32, radius) {
+            self.fill_region(&column, |x, y| pattern.color_at(x, y))?;
+        }
+        Ok(())
+    }
+
+    /// Draws an image with its top left corner at the provided coordinates on the display.
+    pub fn draw_image(&mut self, x: u16, y: u16, image: &Image) -> Result<(), DisplayError<SPI, PIN>> {
+        if image.width() == 0 || image.height() == 0 {
+            return Ok(());
+        }
+
+        let area = Rect::new(x, y, x.saturating_add(image.width() - 1), y.saturating_add(image.height() - 1));
+        self.fill_region(&area, |px, py| image.pixel(px - x, py - y))
+    }
+
+    /// Draws 8-bit grayscale levels stored row by row with their top left corner at the provided
+    /// coordinates. The levels are converted while they are sent, so no RGB565 buffer is needed.
+    /// Returns `Error::OutOfBounds` if `levels` holds fewer than `width * height` values.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let frame = camera.capture();
+    /// display.draw_grayscale(24, 50, 80, 60, &frame);
+    /// ```
+    pub fn draw_grayscale(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        levels: &[u8],
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        if levels.len() < width as usize * height as usize {
+            return Err(Error::OutOfBounds);
+        }
+
+        let area = Rect::new(x, y, x.saturating_add(width - 1), y.saturating_add(height - 1));
+        self.fill_region(&area, |px, py| {
+            Color::gray(levels[(py - y) as usize * width as usize + (px - x) as usize])
+        })
+    }
+
+    /// Draws an image with its top left corner at signed coordinates, so it may be partially
+    /// off-screen.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Let a sprite enter from the left edge.
+    /// for x in -8..0 {
+    ///     display.draw_image_signed(x, 20, &sprite);
+    /// }
+    /// ```
+    pub fn draw_image_signed(&mut self, x: i32, y: i32, image: &Image) -> Result<(), DisplayError<SPI, PIN>> {
+        if image.width() == 0 || image.height() == 0 {
+            return Ok(());
+        }
+
+        let x1 = x + image.width() as i32 - 1;
+        let y1 = y + image.height() as i32 - 1;
+        if let Some(visible) = Rect::clipped(x, y, x1, y1) {
+            let source = Rect::new(
+                (visible.x0 as i32 - x) as u16,
+                (visible.y0 as i32 - y) as u16,
+                (visible.x1 as i32 - x) as u16,
+                (visible.y1 as i32 - y) as u16,
+            );
+            self.draw_image_region(visible.x0, visible.y0, image, &source)?;
+        }
+        Ok(())
+    }
+
+    /// Draws the part of `image` covered by `source` with its top left corner at the provided
+    /// coordinates on the display. Returns `Error::OutOfBounds` if `source` exceeds the image.
+    pub fn draw_image_region(
+        &mut self,
+        x: u16,
+        y: u16,
+        image: &Image,
+        source: &Rect,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        if source.x1 >= image.width() || source.y1 >= image.height() {
+            return Err(Error::OutOfBounds);
+        }
+        self.draw_image(x, y, &image.sub_image(source))
+    }
+
+    /// Draws an image rotated and/or flipped by `transform` with its top left corner at the provided
+    /// coordinates on the display.
+    pub fn draw_image_transformed(
+        &mut self,
+        x: u16,
+        y: u16,
+        image: &Image,
+        transform: &Transform,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        let (width, height) = image.transformed_size(transform);
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        let area = Rect::new(x, y, x.saturating_add(width - 1), y.saturating_add(height - 1));
+        self.fill_region(&area, |px, py| image.transformed_pixel(transform, px - x, py - y))
+    }
+
+    /// Draws an image scaled to `width` x `height` pixels with its top left corner at the provided
+    /// coordinates on the display.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// display.draw_image_scaled(10, 10, &icon, 32, 32, Filter::Nearest);
+    /// ```
+    pub fn draw_image_scaled(
+        &mut self,
+        x: u16,
+        y: u16,
+        image: &Image,
+        width: u16,
+        height: u16,
+        filter: Filter,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        if width == 0 || height == 0 || image.width() == 0 || image.height() == 0 {
+            return Ok(());
+        }
+
+        let area = Rect::new(x, y, x.saturating_add(width - 1), y.saturating_add(height - 1));
+        self.fill_region(&area, |px, py| image.scaled_pixel(filter, width, height, px - x, py - y))
+    }
+
+    /// Draws `patch` scaled to `width` x `height` pixels with its top left corner at the provided
+    /// coordinates.
+    pub fn draw_nine_patch(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        patch: &NinePatch,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        let area = Rect::new(x, y, x.saturating_add(width - 1), y.saturating_add(height - 1));
+        self.fill_region(&area, |px, py| patch.pixel(width, height, px - x, py - y))
+    }
+
+    /// Draws a rectangle filled with the specified `image` on the display. Nothing is drawn if
+    /// the image is empty.
+    #[cfg(feature = "graphics-primitives")]
+    pub fn draw_textured_rect(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        image: &Image,
+        mode: TextureMode,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        if image.width() == 0 || image.height() == 0 {
+            return Ok(());
+        }
+
+        let area = Rect::new(x0, y0, x1, y1);
+        self.fill_region(&area, |x, y| {
+            image.sample(mode, area.width(), area.height(), x - area.x0, y - area.y0)
+        })
+    }
+
+    /// Draws a circle filled with the specified `image` around the provided coordinates on the display.
+    /// The texture is mapped onto the bounding box of the circle. Nothing is drawn if the image is empty.
+    #[cfg(feature = "graphics-primitives")]
+    pub fn draw_textured_circle(
+        &mut self,
+        x_pos: u16,
+        y_pos: u16,
+        radius: u16,
+        image: &Image,
+        mode: TextureMode,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        if image.width() == 0 || image.height() == 0 {
+            return Ok(());
+        }
+
+        // Larger circles cover the whole coordinate space, clamping keeps the size in a `u16`.
+        let radius = radius.min((u16::MAX - 1) / 2);
+        let size = 2 * radius + 1;
+        for column in circle_columns(x_pos as i32, y_pos as i32, radius) {
+            self.fill_region(&column, |x, y| {
+                // The offsets within the bounding box fit, even if the intermediate sums don't.
+                let dx = x.wrapping_add(radius).wrapping_sub(x_pos);
+                let dy = y.wrapping_add(radius).wrapping_sub(y_pos);
+                image.sample(mode, size, size, dx, dy)
+            })?;
         }
+        Ok(())
     }
 
     /// Draws a character filled with the specified `color` and the defined font on the display.
-    pub fn draw_character<F: Font>(&mut self, c: char, x: u16, y: u16, color: &Color, _font: F) {
+    #[cfg(feature = "fonts")]
+    pub fn draw_character<F: Font>(
+        &mut self,
+        c: char,
+        x: u16,
+        y: u16,
+        color: &Color,
+        _font: F,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
         let character_data = <F as Font>::get_char(c);
 
         let mask = 0x01;
 
         for row in 0..7 {
-            for col in 0..5 {
-                let bit = character_data[col] & (mask << row);
+            for (col, bits) in character_data.iter().take(5).enumerate() {
+                let bit = bits & (mask << row);
 
-                if bit != 0 {
-                    self.draw_pixel(x - (col as u16), y - (row as u16), color);
+                if bit != 0 && x >= col as u16 && y >= row as u16 {
+                    self.draw_pixel(x - (col as u16), y - (row as u16), color)?;
                 }
             }
         }
+        Ok(())
+    }
+
+    /// Turns the display on.
+    pub fn display_on(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        self.write_command(Instruction::DISPON, &[])?;
+        self.display_enabled = true;
+        Ok(())
+    }
+
+    /// Changes the format pixels are transferred in. Drawing methods encode pixels in the new
+    /// format from now on, content already on the display is not affected.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Fast updates while animating, full colors for the final frame.
+    /// display.set_pixel_format(PixelFormat::Rgb444);
+    /// animate(&mut display);
+    /// display.set_pixel_format(PixelFormat::Rgb565);
+    /// ```
+    pub fn set_pixel_format(&mut self, format: PixelFormat) -> Result<(), DisplayError<SPI, PIN>> {
+        let command = Command::new(Instruction::COLMOD, &[self.controller.colmod(format)], None);
+        self.execute_command(&command)?;
+        self.pixel_format = format;
+        Ok(())
+    }
+
+    /// Returns the format pixels are transferred in.
+    pub fn pixel_format(&self) -> PixelFormat {
+        self.pixel_format
+    }
+
+    /// Changes the order in which the bytes of `Rgb565` pixels are sent.
+    pub fn set_byte_order(&mut self, byte_order: ByteOrder) {
+        self.byte_order = byte_order;
+    }
+
+    /// Returns the order in which the bytes of `Rgb565` pixels are sent.
+    pub fn byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+
+    /// Switches between normal and partial display mode.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Only keep the top 16 rows with the clock visible.
+    /// display.set_display_mode(DisplayMode::Partial { start: 0, end: 15 });
+    /// display.set_idle_mode(true);
+    /// ```
+    pub fn set_display_mode(&mut self, mode: DisplayMode) -> Result<(), DisplayError<SPI, PIN>> {
+        match mode {
+            DisplayMode::Normal => {
+                self.write_command(Instruction::NORON, &[])?;
+            }
+            DisplayMode::Partial { start, end } => {
+                let (start, end) = (start.to_be_bytes(), end.to_be_bytes());
+                self.write_command(Instruction::PTLAR, &[start[0], start[1], end[0], end[1]])?;
+                self.write_command(Instruction::PTLON, &[])?;
+            }
+        }
+        self.display_mode = mode;
+        Ok(())
+    }
+
+    /// Returns the current normal or partial display mode.
+    pub fn display_mode(&self) -> DisplayMode {
+        self.display_mode
+    }
+
+    /// Enables or disables idle mode, in which only 8 colors are shown to reduce power consumption.
+    /// Idle mode can be combined with both normal and partial display mode.
+    pub fn set_idle_mode(&mut self, enabled: bool) -> Result<(), DisplayError<SPI, PIN>> {
+        let instruction = if enabled { Instruction::IDMON } else { Instruction::IDMOFF };
+        self.write_command(instruction, &[])?;
+        self.idle_mode = enabled;
+        Ok(())
+    }
+
+    /// Returns `true` if idle mode is enabled.
+    pub fn idle_mode(&self) -> bool {
+        self.idle_mode
+    }
+
+    /// Enables or disables color inversion, which some panels need to show colors correctly.
+    pub fn set_inverted(&mut self, inverted: bool) -> Result<(), DisplayError<SPI, PIN>> {
+        let instruction = if inverted { Instruction::INVON } else { Instruction::INVOFF };
+        self.write_command(instruction, &[])?;
+        self.inverted = inverted;
+        Ok(())
+    }
+
+    /// Returns `true` if colors are inverted.
+    pub fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Selects one of the predefined gamma curves of the controller.
+    pub fn set_gamma_curve(&mut self, curve: GammaCurve) -> Result<(), DisplayError<SPI, PIN>> {
+        self.write_command(Instruction::GAMSET, &[curve as u8])?;
+        self.gamma_curve = curve;
+        Ok(())
+    }
+
+    /// Returns the selected gamma curve.
+    pub fn gamma_curve(&self) -> GammaCurve {
+        self.gamma_curve
+    }
+
+    /// Applies the panel size, offset, orientation, inversion and gamma curve of `config`. For
+    /// chips other than the ST7735R, which the constructors of the ST7735 initialize, the
+    /// frame rate, power and gamma settings of the [`Chip`](controller/enum.Chip.html) are sent
+    /// as well. The SPI speed is only a hint for configuring the bus and not used by the driver.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let config = Config::new(Variant::GreenTab);
+    /// display.apply_config(&config)?;
+    /// ```
+    pub fn apply_config(&mut self, config: &Config) -> Result<(), DisplayError<SPI, PIN>> {
+        if config.chip != Chip::ST7735R {
+            self.execute_commands(config.chip.tuning_commands().commands())?;
+        }
+        let (width, height) = config.variant.size();
+        self.set_panel_size(width, height);
+        // Variant offsets are relative to the first memory column and row, while controllers
+        // like `Chip::ST7735S` already add the offset of their panel.
+        let (column, row) = config.variant.offset();
+        let (base_column, base_row) = self.controller.offset(Orientation::Portrait);
+        let column = (column + config.offset.0).saturating_sub(base_column);
+        let row = (row + config.offset.1).saturating_sub(base_row);
+        self.set_offset(column, row);
+        self.set_orientation(&config.orientation)?;
+        self.set_inverted(config.inverted)?;
+        self.set_gamma_curve(config.gamma)
+    }
+
+    /// Enables or disables the tearing effect output. While enabled, the TE pin of the display is
+    /// high during vertical blanking, which can be used to time updates with `Framebuffer::flush_vsync`.
+    pub fn set_tearing_effect(&mut self, enabled: bool) -> Result<(), DisplayError<SPI, PIN>> {
+        if enabled {
+            // Only signal vertical blanking.
+            self.write_command(Instruction::TEON, &[0x00])?;
+        } else {
+            self.write_command(Instruction::TEOFF, &[])?;
+        }
+        self.tearing_effect = enabled;
+        Ok(())
+    }
+
+    /// Turns the display off. The display memory is retained and shown again after `display_on`.
+    pub fn display_off(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        self.write_command(Instruction::DISPOFF, &[])?;
+        self.display_enabled = false;
+        Ok(())
+    }
+
+    /// Gradually changes the brightness of `backlight` to `brightness` over `duration` milliseconds
+    /// using the delay provider of the driver.
+    pub fn fade_backlight<PWM>(&mut self, backlight: &mut Backlight<PWM>, brightness: u8, duration: u64)
+    where
+        PWM: PwmPin<Duty = u16>,
+    {
+        backlight.fade_to(brightness, duration, &mut self.delay);
+    }
+
+    /// Fills the entire screen with a test pattern.
+    pub fn draw_test_pattern(&mut self, pattern: TestPattern) -> Result<(), DisplayError<SPI, PIN>> {
+        let screen = self.bounds();
+        let width = screen.width();
+        let height = screen.height();
+
+        match pattern {
+            TestPattern::ColorBars => {
+                let bars = [
+                    Color::from_default(DefaultColor::White),
+                    Color::from_default(DefaultColor::Yellow),
+                    Color::from_default(DefaultColor::Cyan),
+                    Color::from_default(DefaultColor::Lime),
+                    Color::from_default(DefaultColor::Magenta),
+                    Color::from_default(DefaultColor::Red),
+                    Color::from_default(DefaultColor::Blue),
+                    Color::from_default(DefaultColor::Black),
+                ];
+                self.fill_region(&screen, |x, _| bars[(x as usize) * bars.len() / (width as usize)])?;
+            }
+            TestPattern::Gradient => {
+                self.fill_region(&screen, |x, y| {
+                    let level = (x as u32) * 255 / ((width - 1) as u32);
+                    match (y as u32) * 3 / (height as u32) {
+                        0 => Color::from_rgb((level >> 3) as u16, 0, 0),
+                        1 => Color::from_rgb(0, (level >> 2) as u16, 0),
+                        _ => Color::from_rgb(0, 0, (level >> 3) as u16),
+                    }
+                })?;
+            }
+            TestPattern::Checkerboard => {
+                self.fill_region(&screen, |x, y| {
+                    if (x / 8 + y / 8) % 2 == 0 {
+                        Color::from_default(DefaultColor::White)
+                    } else {
+                        Color::from_default(DefaultColor::Black)
+                    }
+                })?;
+            }
+            TestPattern::Grid => {
+                self.fill_region(&screen, |x, y| {
+                    if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+                        Color::from_default(DefaultColor::Red)
+                    } else if x % 16 == 0 || y % 16 == 0 {
+                        Color::from_default(DefaultColor::White)
+                    } else {
+                        Color::from_default(DefaultColor::Black)
+                    }
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws character `c` with its top left corner at the provided coordinates, each pixel
+    /// enlarged to `scale` x `scale` pixels. If `background` is set, the whole character cell
+    /// including spacing is filled, otherwise only the pixels of the character are drawn.
+    #[cfg(feature = "fonts")]
+    fn draw_glyph<F: Font>(
+        &mut self,
+        c: char,
+        x: u16,
+        y: u16,
+        scale: u16,
+        color: &Color,
+        background: Option<&Color>,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        let character_data = <F as Font>::get_char(c);
+        let is_set = |col: u16, row: u16| {
+            col < 5 && row < 8 && character_data.get(col as usize).is_some_and(|bits| bits & (0x01 << row) != 0)
+        };
+
+        match background {
+            Some(background) => {
+                let cell = Rect::new(
+                    x,
+                    y,
+                    x.saturating_add(CHARACTER_ADVANCE.saturating_mul(scale) - 1),
+                    y.saturating_add(LINE_ADVANCE.saturating_mul(scale) - 1),
+                );
+                self.fill_region(&cell, |px, py| {
+                    if is_set((px - x) / scale, (py - y) / scale) {
+                        *color
+                    } else {
+                        *background
+                    }
+                })?;
+            }
+            None => {
+                // Each horizontal run of set pixels is drawn at once.
+                for row in 0..7 {
+                    let mut col = 0;
+                    while col < 5 {
+                        if !is_set(col, row) {
+                            col += 1;
+                            continue;
+                        }
+                        let start = col;
+                        while is_set(col, row) {
+                            col += 1;
+                        }
+                        let x0 = x.saturating_add(start.saturating_mul(scale));
+                        let y0 = y.saturating_add(row.saturating_mul(scale));
+                        let x1 = x.saturating_add(col.saturating_mul(scale) - 1);
+                        self.draw_filled_rect(x0, y0, x1, y0.saturating_add(scale - 1), color)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a writer for formatted text starting at the provided coordinates. Text is drawn
+    /// in white using `Font57` unless changed on the writer.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use core::fmt::Write;
+    ///
+    /// write!(display.text_at(0, 0), "T = {}°C", 21).unwrap();
+    /// ```
+    #[cfg(feature = "fonts")]
+    pub fn text_at(&mut self, x: u16, y: u16) -> TextWriter<'_, SPI, PIN, DELAY, C, Font57> {
+        TextWriter::new(self, x, y, Color::from_default(DefaultColor::White))
+    }
+
+    /// Draws text with an anti-aliased font. Glyph edges are blended between `color` and
+    /// `background`, which should match whatever is behind the text. The top left corner of the
+    /// first glyph is placed at the provided coordinates and glyphs are spaced using the kerning
+    /// of the font.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let white = Color::from_default(DefaultColor::White);
+    /// let black = Color::from_default(DefaultColor::Black);
+    /// display.draw_text_antialiased("12:30", 10, 40, &white, &black, MyAlphaFont {});
+    /// ```
+    #[cfg(feature = "fonts")]
+    pub fn draw_text_antialiased<F: AlphaFont>(
+        &mut self,
+        text: &str,
+        x: u16,
+        y: u16,
+        color: &Color,
+        background: &Color,
+        _font: F,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        let right = self.bounds().x1;
+        let mut x_pos = x;
+        let mut previous = None;
+        for c in text.chars() {
+            if let Some(previous) = previous {
+                let kerned = x_pos as i32 + <F as AlphaFont>::kerning(previous, c) as i32;
+                x_pos = kerned.max(x as i32).min(u16::MAX as i32) as u16;
+            }
+            previous = Some(c);
+            if x_pos > right {
+                break;
+            }
+
+            let glyph = <F as AlphaFont>::get_glyph(c);
+            if glyph.width > 0 && glyph.height > 0 {
+                let x1 = x_pos.saturating_add(glyph.width - 1);
+                let area = Rect::new(x_pos, y, x1, y.saturating_add(glyph.height - 1));
+                self.fill_region(&area, |px, py| {
+                    background.blend(color, glyph.coverage(px - x_pos, py - y))
+                })?;
+            }
+            x_pos = x_pos.saturating_add(glyph.advance);
+        }
+        Ok(())
+    }
+
+    /// Draws text with a font loaded at runtime, with the top left corner of the first glyph at
+    /// the provided coordinates. Line breaks start a new line. If `background` is set, the
+    /// whole cell of each character including spacing is filled, otherwise only the pixels of
+    /// the glyphs are drawn. Characters missing from the font are drawn blank.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let font = BinaryFont::from_bytes(&data).expect("invalid font");
+    /// let black = Color::from_default(DefaultColor::Black);
+    /// display.draw_text_binary("21:45", 10, 40, &font, &white, Some(&black))?;
+    /// ```
+    #[cfg(feature = "fonts")]
+    pub fn draw_text_binary(
+        &mut self,
+        text: &str,
+        x: u16,
+        y: u16,
+        font: &BinaryFont,
+        color: &Color,
+        background: Option<&Color>,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        let bounds = self.bounds();
+        let (mut x_pos, mut y_pos) = (x, y);
+        for c in text.chars() {
+            if c == '\n' {
+                x_pos = x;
+                y_pos = y_pos.saturating_add(font.line_advance());
+                continue;
+            }
+            // Characters right of or below the display are skipped.
+            if x_pos > bounds.x1 || y_pos > bounds.y1 {
+                continue;
+            }
+
+            let glyph = font.glyph(c);
+            let is_set = |col: u16, row: u16| {
+                let inside = col < font.width() && row < font.height();
+                inside && matches!(glyph, Some(glyph) if glyph.is_set(col, row))
+            };
+            match background {
+                Some(background) if font.advance() > 0 && font.line_advance() > 0 => {
+                    let cell = Rect::new(
+                        x_pos,
+                        y_pos,
+                        x_pos.saturating_add(font.advance() - 1),
+                        y_pos.saturating_add(font.line_advance() - 1),
+                    );
+                    let (cell_x, cell_y) = (x_pos, y_pos);
+                    self.fill_region(&cell, |px, py| {
+                        if is_set(px - cell_x, py - cell_y) {
+                            *color
+                        } else {
+                            *background
+                        }
+                    })?;
+                }
+                _ if glyph.is_some() => {
+                    // Each horizontal run of set pixels is drawn at once.
+                    for row in 0..font.height() {
+                        let mut col = 0;
+                        while col < font.width() {
+                            if !is_set(col, row) {
+                                col += 1;
+                                continue;
+                            }
+                            let start = col;
+                            while is_set(col, row) {
+                                col += 1;
+                            }
+                            let (x0, y0) = (x_pos.saturating_add(start), y_pos.saturating_add(row));
+                            self.draw_filled_rect(x0, y0, x_pos.saturating_add(col - 1), y0, color)?;
+                        }
+                    }
+                }
+                _ => {}
+            }
+            x_pos = x_pos.saturating_add(font.advance());
+        }
+        Ok(())
     }
 
     /// Fills the entire screen with the specified `color`.
-    pub fn fill_screen(&mut self, color: &Color) {
-        self.draw_filled_rect(0, 0, 127, 159, color);
+    pub fn fill_screen(&mut self, color: &Color) -> Result<(), DisplayError<SPI, PIN>> {
+        let screen = self.bounds();
+        self.draw_filled_rect(screen.x0, screen.y0, screen.x1, screen.y1, color)
     }
 
     /// Fills the entire screen black.
-    pub fn clear_screen(&mut self) {
-        self.draw_filled_rect(0, 0, 127, 159, &Color::from_default(DefaultColor::Black));
+    pub fn clear_screen(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        self.fill_screen(&Color::from_default(DefaultColor::Black))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "graphics-primitives")]
+    fn circle_columns_cover_the_circle() {
+        let columns = circle_columns(10, 10, 3);
+        assert_eq!(
+            columns,
+            [
+                Rect::new(10, 7, 10, 13),
+                Rect::new(11, 8, 11, 12),
+                Rect::new(9, 8, 9, 12),
+                Rect::new(12, 8, 12, 12),
+                Rect::new(8, 8, 8, 12),
+            ]
+        );
+
+        let columns = circle_columns(0, 0, 3);
+        assert_eq!(columns, [Rect::new(0, 0, 0, 3), Rect::new(1, 0, 1, 2), Rect::new(2, 0, 2, 2)]);
+        assert!(circle_columns(5, 5, 0).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn lines_are_the_same_in_both_directions() {
+        use crate::testing::Panel;
+
+        let white = Color::from_default(DefaultColor::White);
+        let lit = |panel: &Panel| {
+            let pixels = panel.pixels();
+            let mut lit = Vec::new();
+            for y in 0..pixels.height() {
+                for x in 0..pixels.width() {
+                    if pixels.pixel(x, y) == white {
+                        lit.push((x, y));
+                    }
+                }
+            }
+            lit
+        };
+
+        let panel = Panel::new(8, 8);
+        panel.display().draw_line(0, 0, 3, 1, &white).unwrap();
+        assert_eq!(lit(&panel), [(0, 0), (1, 0), (2, 1), (3, 1)]);
+
+        let panel = Panel::new(8, 8);
+        panel.display().draw_line(3, 1, 0, 0, &white).unwrap();
+        assert_eq!(lit(&panel), [(0, 0), (1, 0), (2, 1), (3, 1)]);
+
+        let panel = Panel::new(8, 8);
+        panel.display().draw_line_signed(-1, 3, 3, -1, &white).unwrap();
+        assert_eq!(lit(&panel), [(2, 0), (1, 1), (0, 2)]);
     }
 }
@@ -0,0 +1,87 @@
+//! Convenience constructor for Linux hosts such as the Raspberry Pi.
+//!
+//! This module is available with the `linux` feature. It wires the generic driver to the
+//! `spidev`, `sysfs_gpio` and delay implementations of `linux-embedded-hal`, so there is no
+//! separate code path for Linux.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use st7735::linux::SpiConfig;
+//! let config = SpiConfig {
+//!     max_speed_hz: 4_000_000,
+//!     ..SpiConfig::default()
+//! };
+//! let mut display = st7735::linux::open("/dev/spidev0.0", 25, &config).expect("error opening display");
+//! display.clear_screen().expect("error clearing display");
+//! ```
+
+use crate::ST7734;
+
+use linux_embedded_hal::spidev::{SpiModeFlags, SpidevOptions};
+use linux_embedded_hal::sysfs_gpio::Direction;
+use linux_embedded_hal::{Delay, Pin, Spidev};
+use std::fs;
+use std::io;
+
+/// Default spidev buffer size, used if the actual size can't be determined.
+const DEFAULT_BUFSIZ: usize = 4096;
+
+/// Driver using Linux spidev, sysfs GPIOs and thread sleeps.
+pub type LinuxST7734 = ST7734<Spidev, Pin, Delay>;
+
+/// Settings applied to the spidev device.
+pub struct SpiConfig {
+    /// Maximum SPI clock frequency in Hz.
+    pub max_speed_hz: u32,
+
+    /// SPI mode.
+    pub mode: SpiModeFlags,
+
+    /// Number of bits per word.
+    pub bits_per_word: u8,
+}
+
+impl Default for SpiConfig {
+    fn default() -> SpiConfig {
+        SpiConfig {
+            max_speed_hz: 20_000_000,
+            mode: SpiModeFlags::SPI_MODE_0,
+            bits_per_word: 8,
+        }
+    }
+}
+
+/// Opens the spidev device at `path`, exports GPIO `dc` as data/command pin and initializes
+/// the display. Writes are split to stay within the spidev transfer size limit.
+pub fn open(path: &str, dc: u64, config: &SpiConfig) -> io::Result<LinuxST7734> {
+    let mut spi = Spidev::open(path)?;
+    let options = SpidevOptions::new()
+        .bits_per_word(config.bits_per_word)
+        .max_speed_hz(config.max_speed_hz)
+        .mode(config.mode)
+        .build();
+    spi.configure(&options)?;
+
+    let dc = Pin::new(dc);
+    dc.export().map_err(gpio_error)?;
+    dc.set_direction(Direction::Out).map_err(gpio_error)?;
+
+    let mut display = ST7734::new_with_spi(spi, dc, Delay);
+    display.set_max_transfer_size(Some(spidev_bufsiz()));
+    Ok(display)
+}
+
+/// Returns the maximum transfer size of spidev, which is configured by the `bufsiz` module parameter.
+/// Larger writes fail.
+fn spidev_bufsiz() -> usize {
+    fs::read_to_string("/sys/module/spidev/parameters/bufsiz")
+        .ok()
+        .and_then(|bufsiz| bufsiz.trim().parse().ok())
+        .unwrap_or(DEFAULT_BUFSIZ)
+}
+
+/// Converts a sysfs GPIO error into an I/O error.
+fn gpio_error(error: linux_embedded_hal::sysfs_gpio::Error) -> io::Error {
+    io::Error::other(error)
+}
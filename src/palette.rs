@@ -0,0 +1,626 @@
+//! Named colors of CSS, converted to RGB565.
+//!
+//! Note that CSS calls the full intensity green `LIME`, while `GREEN` is a darker shade.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use st7735::palette;
+//!
+//! display.fill_screen(&palette::MIDNIGHT_BLUE);
+//! let warning = palette::from_name("darkorange").unwrap();
+//! ```
+
+use crate::color::Color;
+
+/// `#F0F8FF`
+pub const ALICE_BLUE: Color = Color::from_hex(0xF7DF);
+
+/// `#FAEBD7`
+pub const ANTIQUE_WHITE: Color = Color::from_hex(0xFF5A);
+
+/// `#00FFFF`
+pub const AQUA: Color = Color::from_hex(0x07FF);
+
+/// `#7FFFD4`
+pub const AQUAMARINE: Color = Color::from_hex(0x7FFA);
+
+/// `#F0FFFF`
+pub const AZURE: Color = Color::from_hex(0xF7FF);
+
+/// `#F5F5DC`
+pub const BEIGE: Color = Color::from_hex(0xF7BB);
+
+/// `#FFE4C4`
+pub const BISQUE: Color = Color::from_hex(0xFF38);
+
+/// `#000000`
+pub const BLACK: Color = Color::from_hex(0x0000);
+
+/// `#FFEBCD`
+pub const BLANCHED_ALMOND: Color = Color::from_hex(0xFF59);
+
+/// `#0000FF`
+pub const BLUE: Color = Color::from_hex(0x001F);
+
+/// `#8A2BE2`
+pub const BLUE_VIOLET: Color = Color::from_hex(0x895C);
+
+/// `#A52A2A`
+pub const BROWN: Color = Color::from_hex(0xA145);
+
+/// `#DEB887`
+pub const BURLYWOOD: Color = Color::from_hex(0xDDD0);
+
+/// `#5F9EA0`
+pub const CADET_BLUE: Color = Color::from_hex(0x5CF4);
+
+/// `#7FFF00`
+pub const CHARTREUSE: Color = Color::from_hex(0x7FE0);
+
+/// `#D2691E`
+pub const CHOCOLATE: Color = Color::from_hex(0xD343);
+
+/// `#FF7F50`
+pub const CORAL: Color = Color::from_hex(0xFBEA);
+
+/// `#6495ED`
+pub const CORNFLOWER_BLUE: Color = Color::from_hex(0x64BD);
+
+/// `#FFF8DC`
+pub const CORNSILK: Color = Color::from_hex(0xFFDB);
+
+/// `#DC143C`
+pub const CRIMSON: Color = Color::from_hex(0xD8A7);
+
+/// `#00FFFF`
+pub const CYAN: Color = Color::from_hex(0x07FF);
+
+/// `#00008B`
+pub const DARK_BLUE: Color = Color::from_hex(0x0011);
+
+/// `#008B8B`
+pub const DARK_CYAN: Color = Color::from_hex(0x0451);
+
+/// `#B8860B`
+pub const DARK_GOLDENROD: Color = Color::from_hex(0xBC21);
+
+/// `#A9A9A9`
+pub const DARK_GRAY: Color = Color::from_hex(0xAD55);
+
+/// `#006400`
+pub const DARK_GREEN: Color = Color::from_hex(0x0320);
+
+/// `#A9A9A9`
+pub const DARK_GREY: Color = Color::from_hex(0xAD55);
+
+/// `#BDB76B`
+pub const DARK_KHAKI: Color = Color::from_hex(0xBDAD);
+
+/// `#8B008B`
+pub const DARK_MAGENTA: Color = Color::from_hex(0x8811);
+
+/// `#556B2F`
+pub const DARK_OLIVE_GREEN: Color = Color::from_hex(0x5345);
+
+/// `#FF8C00`
+pub const DARK_ORANGE: Color = Color::from_hex(0xFC60);
+
+/// `#9932CC`
+pub const DARK_ORCHID: Color = Color::from_hex(0x9999);
+
+/// `#8B0000`
+pub const DARK_RED: Color = Color::from_hex(0x8800);
+
+/// `#E9967A`
+pub const DARK_SALMON: Color = Color::from_hex(0xECAF);
+
+/// `#8FBC8F`
+pub const DARK_SEA_GREEN: Color = Color::from_hex(0x8DF1);
+
+/// `#483D8B`
+pub const DARK_SLATE_BLUE: Color = Color::from_hex(0x49F1);
+
+/// `#2F4F4F`
+pub const DARK_SLATE_GRAY: Color = Color::from_hex(0x2A69);
+
+/// `#2F4F4F`
+pub const DARK_SLATE_GREY: Color = Color::from_hex(0x2A69);
+
+/// `#00CED1`
+pub const DARK_TURQUOISE: Color = Color::from_hex(0x067A);
+
+/// `#9400D3`
+pub const DARK_VIOLET: Color = Color::from_hex(0x901A);
+
+/// `#FF1493`
+pub const DEEP_PINK: Color = Color::from_hex(0xF8B2);
+
+/// `#00BFFF`
+pub const DEEP_SKY_BLUE: Color = Color::from_hex(0x05FF);
+
+/// `#696969`
+pub const DIM_GRAY: Color = Color::from_hex(0x6B4D);
+
+/// `#696969`
+pub const DIM_GREY: Color = Color::from_hex(0x6B4D);
+
+/// `#1E90FF`
+pub const DODGER_BLUE: Color = Color::from_hex(0x1C9F);
+
+/// `#B22222`
+pub const FIREBRICK: Color = Color::from_hex(0xB104);
+
+/// `#FFFAF0`
+pub const FLORAL_WHITE: Color = Color::from_hex(0xFFDE);
+
+/// `#228B22`
+pub const FOREST_GREEN: Color = Color::from_hex(0x2444);
+
+/// `#FF00FF`
+pub const FUCHSIA: Color = Color::from_hex(0xF81F);
+
+/// `#DCDCDC`
+pub const GAINSBORO: Color = Color::from_hex(0xDEFB);
+
+/// `#F8F8FF`
+pub const GHOST_WHITE: Color = Color::from_hex(0xFFDF);
+
+/// `#FFD700`
+pub const GOLD: Color = Color::from_hex(0xFEA0);
+
+/// `#DAA520`
+pub const GOLDENROD: Color = Color::from_hex(0xDD24);
+
+/// `#808080`
+pub const GRAY: Color = Color::from_hex(0x8410);
+
+/// `#008000`
+pub const GREEN: Color = Color::from_hex(0x0400);
+
+/// `#ADFF2F`
+pub const GREEN_YELLOW: Color = Color::from_hex(0xAFE5);
+
+/// `#808080`
+pub const GREY: Color = Color::from_hex(0x8410);
+
+/// `#F0FFF0`
+pub const HONEYDEW: Color = Color::from_hex(0xF7FE);
+
+/// `#FF69B4`
+pub const HOT_PINK: Color = Color::from_hex(0xFB56);
+
+/// `#CD5C5C`
+pub const INDIAN_RED: Color = Color::from_hex(0xCAEB);
+
+/// `#4B0082`
+pub const INDIGO: Color = Color::from_hex(0x4810);
+
+/// `#FFFFF0`
+pub const IVORY: Color = Color::from_hex(0xFFFE);
+
+/// `#F0E68C`
+pub const KHAKI: Color = Color::from_hex(0xF731);
+
+/// `#E6E6FA`
+pub const LAVENDER: Color = Color::from_hex(0xE73F);
+
+/// `#FFF0F5`
+pub const LAVENDER_BLUSH: Color = Color::from_hex(0xFF9E);
+
+/// `#7CFC00`
+pub const LAWN_GREEN: Color = Color::from_hex(0x7FE0);
+
+/// `#FFFACD`
+pub const LEMON_CHIFFON: Color = Color::from_hex(0xFFD9);
+
+/// `#ADD8E6`
+pub const LIGHT_BLUE: Color = Color::from_hex(0xAEDC);
+
+/// `#F08080`
+pub const LIGHT_CORAL: Color = Color::from_hex(0xF410);
+
+/// `#E0FFFF`
+pub const LIGHT_CYAN: Color = Color::from_hex(0xE7FF);
+
+/// `#FAFAD2`
+pub const LIGHT_GOLDENROD_YELLOW: Color = Color::from_hex(0xFFDA);
+
+/// `#D3D3D3`
+pub const LIGHT_GRAY: Color = Color::from_hex(0xD69A);
+
+/// `#90EE90`
+pub const LIGHT_GREEN: Color = Color::from_hex(0x9772);
+
+/// `#D3D3D3`
+pub const LIGHT_GREY: Color = Color::from_hex(0xD69A);
+
+/// `#FFB6C1`
+pub const LIGHT_PINK: Color = Color::from_hex(0xFDB8);
+
+/// `#FFA07A`
+pub const LIGHT_SALMON: Color = Color::from_hex(0xFD0F);
+
+/// `#20B2AA`
+pub const LIGHT_SEA_GREEN: Color = Color::from_hex(0x2595);
+
+/// `#87CEFA`
+pub const LIGHT_SKY_BLUE: Color = Color::from_hex(0x867F);
+
+/// `#778899`
+pub const LIGHT_SLATE_GRAY: Color = Color::from_hex(0x7453);
+
+/// `#778899`
+pub const LIGHT_SLATE_GREY: Color = Color::from_hex(0x7453);
+
+/// `#B0C4DE`
+pub const LIGHT_STEEL_BLUE: Color = Color::from_hex(0xB63B);
+
+/// `#FFFFE0`
+pub const LIGHT_YELLOW: Color = Color::from_hex(0xFFFC);
+
+/// `#00FF00`
+pub const LIME: Color = Color::from_hex(0x07E0);
+
+/// `#32CD32`
+pub const LIME_GREEN: Color = Color::from_hex(0x3666);
+
+/// `#FAF0E6`
+pub const LINEN: Color = Color::from_hex(0xFF9C);
+
+/// `#FF00FF`
+pub const MAGENTA: Color = Color::from_hex(0xF81F);
+
+/// `#800000`
+pub const MAROON: Color = Color::from_hex(0x8000);
+
+/// `#66CDAA`
+pub const MEDIUM_AQUAMARINE: Color = Color::from_hex(0x6675);
+
+/// `#0000CD`
+pub const MEDIUM_BLUE: Color = Color::from_hex(0x0019);
+
+/// `#BA55D3`
+pub const MEDIUM_ORCHID: Color = Color::from_hex(0xBABA);
+
+/// `#9370DB`
+pub const MEDIUM_PURPLE: Color = Color::from_hex(0x939B);
+
+/// `#3CB371`
+pub const MEDIUM_SEA_GREEN: Color = Color::from_hex(0x3D8E);
+
+/// `#7B68EE`
+pub const MEDIUM_SLATE_BLUE: Color = Color::from_hex(0x7B5D);
+
+/// `#00FA9A`
+pub const MEDIUM_SPRING_GREEN: Color = Color::from_hex(0x07D3);
+
+/// `#48D1CC`
+pub const MEDIUM_TURQUOISE: Color = Color::from_hex(0x4E99);
+
+/// `#C71585`
+pub const MEDIUM_VIOLET_RED: Color = Color::from_hex(0xC0B0);
+
+/// `#191970`
+pub const MIDNIGHT_BLUE: Color = Color::from_hex(0x18CE);
+
+/// `#F5FFFA`
+pub const MINT_CREAM: Color = Color::from_hex(0xF7FF);
+
+/// `#FFE4E1`
+pub const MISTY_ROSE: Color = Color::from_hex(0xFF3C);
+
+/// `#FFE4B5`
+pub const MOCCASIN: Color = Color::from_hex(0xFF36);
+
+/// `#FFDEAD`
+pub const NAVAJO_WHITE: Color = Color::from_hex(0xFEF5);
+
+/// `#000080`
+pub const NAVY: Color = Color::from_hex(0x0010);
+
+/// `#FDF5E6`
+pub const OLD_LACE: Color = Color::from_hex(0xFFBC);
+
+/// `#808000`
+pub const OLIVE: Color = Color::from_hex(0x8400);
+
+/// `#6B8E23`
+pub const OLIVE_DRAB: Color = Color::from_hex(0x6C64);
+
+/// `#FFA500`
+pub const ORANGE: Color = Color::from_hex(0xFD20);
+
+/// `#FF4500`
+pub const ORANGE_RED: Color = Color::from_hex(0xFA20);
+
+/// `#DA70D6`
+pub const ORCHID: Color = Color::from_hex(0xDB9A);
+
+/// `#EEE8AA`
+pub const PALE_GOLDENROD: Color = Color::from_hex(0xEF55);
+
+/// `#98FB98`
+pub const PALE_GREEN: Color = Color::from_hex(0x9FD3);
+
+/// `#AFEEEE`
+pub const PALE_TURQUOISE: Color = Color::from_hex(0xAF7D);
+
+/// `#DB7093`
+pub const PALE_VIOLET_RED: Color = Color::from_hex(0xDB92);
+
+/// `#FFEFD5`
+pub const PAPAYA_WHIP: Color = Color::from_hex(0xFF7A);
+
+/// `#FFDAB9`
+pub const PEACH_PUFF: Color = Color::from_hex(0xFED7);
+
+/// `#CD853F`
+pub const PERU: Color = Color::from_hex(0xCC27);
+
+/// `#FFC0CB`
+pub const PINK: Color = Color::from_hex(0xFE19);
+
+/// `#DDA0DD`
+pub const PLUM: Color = Color::from_hex(0xDD1B);
+
+/// `#B0E0E6`
+pub const POWDER_BLUE: Color = Color::from_hex(0xB71C);
+
+/// `#800080`
+pub const PURPLE: Color = Color::from_hex(0x8010);
+
+/// `#663399`
+pub const REBECCA_PURPLE: Color = Color::from_hex(0x6193);
+
+/// `#FF0000`
+pub const RED: Color = Color::from_hex(0xF800);
+
+/// `#BC8F8F`
+pub const ROSY_BROWN: Color = Color::from_hex(0xBC71);
+
+/// `#4169E1`
+pub const ROYAL_BLUE: Color = Color::from_hex(0x435C);
+
+/// `#8B4513`
+pub const SADDLE_BROWN: Color = Color::from_hex(0x8A22);
+
+/// `#FA8072`
+pub const SALMON: Color = Color::from_hex(0xFC0E);
+
+/// `#F4A460`
+pub const SANDY_BROWN: Color = Color::from_hex(0xF52C);
+
+/// `#2E8B57`
+pub const SEA_GREEN: Color = Color::from_hex(0x2C4A);
+
+/// `#FFF5EE`
+pub const SEASHELL: Color = Color::from_hex(0xFFBD);
+
+/// `#A0522D`
+pub const SIENNA: Color = Color::from_hex(0xA285);
+
+/// `#C0C0C0`
+pub const SILVER: Color = Color::from_hex(0xC618);
+
+/// `#87CEEB`
+pub const SKY_BLUE: Color = Color::from_hex(0x867D);
+
+/// `#6A5ACD`
+pub const SLATE_BLUE: Color = Color::from_hex(0x6AD9);
+
+/// `#708090`
+pub const SLATE_GRAY: Color = Color::from_hex(0x7412);
+
+/// `#708090`
+pub const SLATE_GREY: Color = Color::from_hex(0x7412);
+
+/// `#FFFAFA`
+pub const SNOW: Color = Color::from_hex(0xFFDF);
+
+/// `#00FF7F`
+pub const SPRING_GREEN: Color = Color::from_hex(0x07EF);
+
+/// `#4682B4`
+pub const STEEL_BLUE: Color = Color::from_hex(0x4416);
+
+/// `#D2B48C`
+pub const TAN: Color = Color::from_hex(0xD5B1);
+
+/// `#008080`
+pub const TEAL: Color = Color::from_hex(0x0410);
+
+/// `#D8BFD8`
+pub const THISTLE: Color = Color::from_hex(0xDDFB);
+
+/// `#FF6347`
+pub const TOMATO: Color = Color::from_hex(0xFB08);
+
+/// `#40E0D0`
+pub const TURQUOISE: Color = Color::from_hex(0x471A);
+
+/// `#EE82EE`
+pub const VIOLET: Color = Color::from_hex(0xEC1D);
+
+/// `#F5DEB3`
+pub const WHEAT: Color = Color::from_hex(0xF6F6);
+
+/// `#FFFFFF`
+pub const WHITE: Color = Color::from_hex(0xFFFF);
+
+/// `#F5F5F5`
+pub const WHITE_SMOKE: Color = Color::from_hex(0xF7BE);
+
+/// `#FFFF00`
+pub const YELLOW: Color = Color::from_hex(0xFFE0);
+
+/// `#9ACD32`
+pub const YELLOW_GREEN: Color = Color::from_hex(0x9E66);
+
+/// Returns the color with the given CSS name, ignoring case and spaces, e.g. `"DarkOrange"` or
+/// `"dark orange"`.
+pub fn from_name(name: &str) -> Option<Color> {
+    // Long enough for the longest name, "light goldenrod yellow" without spaces.
+    let mut buffer = [0u8; 20];
+    let mut length = 0;
+    for byte in name.bytes().filter(|&b| b != b' ') {
+        if length == buffer.len() {
+            return None;
+        }
+        buffer[length] = byte.to_ascii_lowercase();
+        length += 1;
+    }
+
+    let color = match core::str::from_utf8(&buffer[..length]).ok()? {
+        "aliceblue" => ALICE_BLUE,
+        "antiquewhite" => ANTIQUE_WHITE,
+        "aqua" => AQUA,
+        "aquamarine" => AQUAMARINE,
+        "azure" => AZURE,
+        "beige" => BEIGE,
+        "bisque" => BISQUE,
+        "black" => BLACK,
+        "blanchedalmond" => BLANCHED_ALMOND,
+        "blue" => BLUE,
+        "blueviolet" => BLUE_VIOLET,
+        "brown" => BROWN,
+        "burlywood" => BURLYWOOD,
+        "cadetblue" => CADET_BLUE,
+        "chartreuse" => CHARTREUSE,
+        "chocolate" => CHOCOLATE,
+        "coral" => CORAL,
+        "cornflowerblue" => CORNFLOWER_BLUE,
+        "cornsilk" => CORNSILK,
+        "crimson" => CRIMSON,
+        "cyan" => CYAN,
+        "darkblue" => DARK_BLUE,
+        "darkcyan" => DARK_CYAN,
+        "darkgoldenrod" => DARK_GOLDENROD,
+        "darkgray" => DARK_GRAY,
+        "darkgreen" => DARK_GREEN,
+        "darkgrey" => DARK_GREY,
+        "darkkhaki" => DARK_KHAKI,
+        "darkmagenta" => DARK_MAGENTA,
+        "darkolivegreen" => DARK_OLIVE_GREEN,
+        "darkorange" => DARK_ORANGE,
+        "darkorchid" => DARK_ORCHID,
+        "darkred" => DARK_RED,
+        "darksalmon" => DARK_SALMON,
+        "darkseagreen" => DARK_SEA_GREEN,
+        "darkslateblue" => DARK_SLATE_BLUE,
+        "darkslategray" => DARK_SLATE_GRAY,
+        "darkslategrey" => DARK_SLATE_GREY,
+        "darkturquoise" => DARK_TURQUOISE,
+        "darkviolet" => DARK_VIOLET,
+        "deeppink" => DEEP_PINK,
+        "deepskyblue" => DEEP_SKY_BLUE,
+        "dimgray" => DIM_GRAY,
+        "dimgrey" => DIM_GREY,
+        "dodgerblue" => DODGER_BLUE,
+        "firebrick" => FIREBRICK,
+        "floralwhite" => FLORAL_WHITE,
+        "forestgreen" => FOREST_GREEN,
+        "fuchsia" => FUCHSIA,
+        "gainsboro" => GAINSBORO,
+        "ghostwhite" => GHOST_WHITE,
+        "gold" => GOLD,
+        "goldenrod" => GOLDENROD,
+        "gray" => GRAY,
+        "green" => GREEN,
+        "greenyellow" => GREEN_YELLOW,
+        "grey" => GREY,
+        "honeydew" => HONEYDEW,
+        "hotpink" => HOT_PINK,
+        "indianred" => INDIAN_RED,
+        "indigo" => INDIGO,
+        "ivory" => IVORY,
+        "khaki" => KHAKI,
+        "lavender" => LAVENDER,
+        "lavenderblush" => LAVENDER_BLUSH,
+        "lawngreen" => LAWN_GREEN,
+        "lemonchiffon" => LEMON_CHIFFON,
+        "lightblue" => LIGHT_BLUE,
+        "lightcoral" => LIGHT_CORAL,
+        "lightcyan" => LIGHT_CYAN,
+        "lightgoldenrodyellow" => LIGHT_GOLDENROD_YELLOW,
+        "lightgray" => LIGHT_GRAY,
+        "lightgreen" => LIGHT_GREEN,
+        "lightgrey" => LIGHT_GREY,
+        "lightpink" => LIGHT_PINK,
+        "lightsalmon" => LIGHT_SALMON,
+        "lightseagreen" => LIGHT_SEA_GREEN,
+        "lightskyblue" => LIGHT_SKY_BLUE,
+        "lightslategray" => LIGHT_SLATE_GRAY,
+        "lightslategrey" => LIGHT_SLATE_GREY,
+        "lightsteelblue" => LIGHT_STEEL_BLUE,
+        "lightyellow" => LIGHT_YELLOW,
+        "lime" => LIME,
+        "limegreen" => LIME_GREEN,
+        "linen" => LINEN,
+        "magenta" => MAGENTA,
+        "maroon" => MAROON,
+        "mediumaquamarine" => MEDIUM_AQUAMARINE,
+        "mediumblue" => MEDIUM_BLUE,
+        "mediumorchid" => MEDIUM_ORCHID,
+        "mediumpurple" => MEDIUM_PURPLE,
+        "mediumseagreen" => MEDIUM_SEA_GREEN,
+        "mediumslateblue" => MEDIUM_SLATE_BLUE,
+        "mediumspringgreen" => MEDIUM_SPRING_GREEN,
+        "mediumturquoise" => MEDIUM_TURQUOISE,
+        "mediumvioletred" => MEDIUM_VIOLET_RED,
+        "midnightblue" => MIDNIGHT_BLUE,
+        "mintcream" => MINT_CREAM,
+        "mistyrose" => MISTY_ROSE,
+        "moccasin" => MOCCASIN,
+        "navajowhite" => NAVAJO_WHITE,
+        "navy" => NAVY,
+        "oldlace" => OLD_LACE,
+        "olive" => OLIVE,
+        "olivedrab" => OLIVE_DRAB,
+        "orange" => ORANGE,
+        "orangered" => ORANGE_RED,
+        "orchid" => ORCHID,
+        "palegoldenrod" => PALE_GOLDENROD,
+        "palegreen" => PALE_GREEN,
+        "paleturquoise" => PALE_TURQUOISE,
+        "palevioletred" => PALE_VIOLET_RED,
+        "papayawhip" => PAPAYA_WHIP,
+        "peachpuff" => PEACH_PUFF,
+        "peru" => PERU,
+        "pink" => PINK,
+        "plum" => PLUM,
+        "powderblue" => POWDER_BLUE,
+        "purple" => PURPLE,
+        "rebeccapurple" => REBECCA_PURPLE,
+        "red" => RED,
+        "rosybrown" => ROSY_BROWN,
+        "royalblue" => ROYAL_BLUE,
+        "saddlebrown" => SADDLE_BROWN,
+        "salmon" => SALMON,
+        "sandybrown" => SANDY_BROWN,
+        "seagreen" => SEA_GREEN,
+        "seashell" => SEASHELL,
+        "sienna" => SIENNA,
+        "silver" => SILVER,
+        "skyblue" => SKY_BLUE,
+        "slateblue" => SLATE_BLUE,
+        "slategray" => SLATE_GRAY,
+        "slategrey" => SLATE_GREY,
+        "snow" => SNOW,
+        "springgreen" => SPRING_GREEN,
+        "steelblue" => STEEL_BLUE,
+        "tan" => TAN,
+        "teal" => TEAL,
+        "thistle" => THISTLE,
+        "tomato" => TOMATO,
+        "turquoise" => TURQUOISE,
+        "violet" => VIOLET,
+        "wheat" => WHEAT,
+        "white" => WHITE,
+        "whitesmoke" => WHITE_SMOKE,
+        "yellow" => YELLOW,
+        "yellowgreen" => YELLOW_GREEN,
+        _ => return None,
+    };
+    Some(color)
+}
@@ -0,0 +1,119 @@
+//! 8-bit parallel bus in 8080 mode.
+//!
+//! Besides SPI, the controller accepts commands and data on an 8-bit 8080-style parallel bus,
+//! which transfers a byte with a single write strobe instead of eight clock pulses. The display
+//! module has to be strapped for the 8-bit parallel interface with its IM pins, and RD tied high.
+//!
+//! The buses in this module are passed to `ST7734::new_with_spi` like an SPI bus; the driver
+//! still controls the data/command pin and, if set with `set_cs_pin`, the chip select pin.
+//! [`ParallelBus`](struct.ParallelBus.html) drives the data and WR pins through `OutputPin`,
+//! [`FastParallel`](struct.FastParallel.html) writes whole bytes to a port register through
+//! [`ParallelPort`](trait.ParallelPort.html). Implementations of the `display-interface` traits,
+//! like `display-interface-parallel-gpio`, can be used with the `interface` module instead.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let bus = ParallelBus::new([d0, d1, d2, d3, d4, d5, d6, d7], wr)?;
+//! let mut display = ST7734::new_with_spi(bus, dc, delay);
+//! ```
+
+use core::convert::Infallible;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Parallel bus driving eight data pins and the write strobe through `OutputPin`.
+pub struct ParallelBus<P> {
+    /// Data pins D0 to D7.
+    data: [P; 8],
+
+    /// Write strobe, active low. Data is latched on its rising edge.
+    wr: P,
+
+    /// Byte currently on the data pins, `None` before the first write.
+    current: Option<u8>,
+}
+
+impl<P: OutputPin> ParallelBus<P> {
+    /// Creates a bus from the data pins D0 to D7 and the write strobe, which is set high.
+    pub fn new(data: [P; 8], mut wr: P) -> Result<ParallelBus<P>, P::Error> {
+        wr.set_high()?;
+        Ok(ParallelBus {
+            data,
+            wr,
+            current: None,
+        })
+    }
+
+    /// Releases the data pins and the write strobe.
+    pub fn release(self) -> ([P; 8], P) {
+        (self.data, self.wr)
+    }
+
+    /// Puts `byte` on the data pins, only changing the pins whose level differs.
+    fn set_data(&mut self, byte: u8) -> Result<(), P::Error> {
+        let changed = self.current.map_or(0xFF, |current| current ^ byte);
+        for (bit, pin) in self.data.iter_mut().enumerate() {
+            if changed & (1 << bit) == 0 {
+                continue;
+            }
+            if byte & (1 << bit) != 0 {
+                pin.set_high()?;
+            } else {
+                pin.set_low()?;
+            }
+        }
+        self.current = Some(byte);
+        Ok(())
+    }
+}
+
+impl<P: OutputPin> spi::Write<u8> for ParallelBus<P> {
+    type Error = P::Error;
+
+    fn write(&mut self, words: &[u8]) -> Result<(), P::Error> {
+        for word in words {
+            self.set_data(*word)?;
+            self.wr.set_low()?;
+            self.wr.set_high()?;
+        }
+        Ok(())
+    }
+}
+
+/// Data and write strobe pins of a parallel bus that are driven without `OutputPin`, e.g.
+/// eight consecutive pins of a GPIO port.
+pub trait ParallelPort {
+    /// Puts `byte` on D0 to D7 and pulses the write strobe low. Data has to be stable on the
+    /// rising edge of the strobe.
+    fn write_byte(&mut self, byte: u8);
+}
+
+/// Parallel bus built on `ParallelPort`, usable wherever the driver expects hardware SPI.
+pub struct FastParallel<P> {
+    /// Port the data is written to.
+    port: P,
+}
+
+impl<P: ParallelPort> FastParallel<P> {
+    /// Creates a bus writing data to `port`.
+    pub fn new(port: P) -> FastParallel<P> {
+        FastParallel { port }
+    }
+
+    /// Releases the port.
+    pub fn release(self) -> P {
+        self.port
+    }
+}
+
+impl<P: ParallelPort> spi::Write<u8> for FastParallel<P> {
+    type Error = Infallible;
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Infallible> {
+        for word in words {
+            self.port.write_byte(*word);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use crate::color::Color;
+
+/// Repeating 8x8 two-color pattern used to fill shapes.
+///
+/// Each byte of `rows` describes one row of the pattern, the most significant bit being the
+/// leftmost pixel. Set bits are drawn in `foreground`, cleared bits in `background`. Patterns are
+/// aligned to the display origin, so adjacent shapes filled with the same pattern line up.
+///
+/// # Examples
+///
+/// ```ignore
+/// let hatch = Pattern::diagonal_hatch(Color::from_default(DefaultColor::Red), Color::from_default(DefaultColor::Black));
+/// display.draw_patterned_rect(10, 10, 60, 40, &hatch);
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Pattern {
+    /// Bit rows of the pattern.
+    pub rows: [u8; 8],
+
+    /// Color of set bits.
+    pub foreground: Color,
+
+    /// Color of cleared bits.
+    pub background: Color,
+}
+
+impl Pattern {
+    /// Creates a pattern from custom bit rows.
+    pub const fn new(rows: [u8; 8], foreground: Color, background: Color) -> Pattern {
+        Pattern {
+            rows,
+            foreground,
+            background,
+        }
+    }
+
+    /// Alternating single pixels.
+    pub const fn checkerboard(foreground: Color, background: Color) -> Pattern {
+        Pattern::new([0xAA, 0x55, 0xAA, 0x55, 0xAA, 0x55, 0xAA, 0x55], foreground, background)
+    }
+
+    /// Diagonal lines running from the bottom left to the top right.
+    pub const fn diagonal_hatch(foreground: Color, background: Color) -> Pattern {
+        Pattern::new([0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80], foreground, background)
+    }
+
+    /// Horizontal and vertical lines forming a grid.
+    pub const fn cross_hatch(foreground: Color, background: Color) -> Pattern {
+        Pattern::new([0xFF, 0x80, 0x80, 0x80, 0xFF, 0x80, 0x80, 0x80], foreground, background)
+    }
+
+    /// Horizontal lines every other row.
+    pub const fn horizontal_lines(foreground: Color, background: Color) -> Pattern {
+        Pattern::new([0xFF, 0x00, 0xFF, 0x00, 0xFF, 0x00, 0xFF, 0x00], foreground, background)
+    }
+
+    /// Sparse dots, commonly used to indicate disabled elements.
+    pub const fn stipple(foreground: Color, background: Color) -> Pattern {
+        Pattern::new([0x88, 0x00, 0x22, 0x00, 0x88, 0x00, 0x22, 0x00], foreground, background)
+    }
+
+    /// Returns the color of the pattern at the given display coordinates.
+    pub fn color_at(&self, x: u16, y: u16) -> Color {
+        if self.rows[(y % 8) as usize] & (0x80 >> (x % 8)) != 0 {
+            self.foreground
+        } else {
+            self.background
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::color::Color;
+
+
+/// Format in which pixels are transferred to the display memory.
+#[derive(FromPrimitive, ToPrimitive, Clone, Copy, PartialEq, Debug)]
+pub enum PixelFormat {
+    /// 12 bits per pixel, two pixels are packed into three bytes. The fastest format, at the
+    /// cost of reducing every color component to 4 bits.
+    Rgb444 = 0x03,
+
+    /// 16 bits per pixel. Colors are transferred unchanged.
+    Rgb565 = 0x05,
+
+    /// 18 bits per pixel, one byte per color component. Takes 50% longer to transfer than
+    /// `Rgb565` without adding detail to colors, but matches what some panels expect.
+    Rgb666 = 0x06,
+}
+
+impl PixelFormat {
+    /// Number of bytes needed to transfer `count` pixels.
+    pub fn bytes(&self, count: usize) -> usize {
+        match self {
+            PixelFormat::Rgb444 => (count * 3).div_ceil(2),
+            PixelFormat::Rgb565 => count * 2,
+            PixelFormat::Rgb666 => count * 3,
+        }
+    }
+}
+
+/// Order in which the two bytes of an `Rgb565` pixel are sent.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ByteOrder {
+    /// High byte first, as expected by the display.
+    BigEndian,
+
+    /// Low byte first. Compensates for SPI peripherals and DMA engines that transmit 16-bit
+    /// words least significant byte first, which otherwise shows up as garbled colors.
+    LittleEndian,
+}
+
+/// Encodes a stream of pixels in a pixel format. With `Rgb444` pixel pairs share a byte, so
+/// pixels of a single memory write have to be pushed to the same encoder.
+pub(crate) struct PixelEncoder {
+    /// Format pixels are encoded in.
+    format: PixelFormat,
+
+    /// Byte order of `Rgb565` pixels.
+    byte_order: ByteOrder,
+
+    /// First pixel of an incomplete `Rgb444` pair.
+    pending: Option<Color>,
+}
+
+impl PixelEncoder {
+    /// Creates an encoder for a new memory write.
+    pub(crate) fn new(format: PixelFormat, byte_order: ByteOrder) -> PixelEncoder {
+        PixelEncoder {
+            format,
+            byte_order,
+            pending: None,
+        }
+    }
+
+    /// Appends the encoding of `color` to `out`.
+    pub(crate) fn push<E: Extend<u8>>(&mut self, color: &Color, out: &mut E) {
+        match self.format {
+            PixelFormat::Rgb565 => match self.byte_order {
+                ByteOrder::BigEndian => out.extend(color.to_be_bytes().iter().cloned()),
+                ByteOrder::LittleEndian => out.extend(color.hex.to_le_bytes().iter().cloned()),
+            },
+            PixelFormat::Rgb666 => {
+                let (red, green, blue) = rgb666(color);
+                out.extend([red << 2, green << 2, blue << 2].iter().cloned());
+            }
+            PixelFormat::Rgb444 => match self.pending.take() {
+                None => self.pending = Some(*color),
+                Some(first) => {
+                    let (r0, g0, b0) = rgb444(&first);
+                    let (r1, g1, b1) = rgb444(color);
+                    out.extend([r0 << 4 | g0, b0 << 4 | r1, g1 << 4 | b1].iter().cloned());
+                }
+            },
+        }
+    }
+
+    /// Appends the remaining half of an incomplete `Rgb444` pair, padded with zero bits.
+    pub(crate) fn finish<E: Extend<u8>>(&mut self, out: &mut E) {
+        if let Some(last) = self.pending.take() {
+            let (red, green, blue) = rgb444(&last);
+            out.extend([red << 4 | green, blue << 4].iter().cloned());
+        }
+    }
+}
+
+/// Splits a color into 6-bit components.
+fn rgb666(color: &Color) -> (u8, u8, u8) {
+    let red = ((color.hex >> 11) & 0x1F) as u8;
+    let green = ((color.hex >> 5) & 0x3F) as u8;
+    let blue = (color.hex & 0x1F) as u8;
+    (red << 1 | red >> 4, green, blue << 1 | blue >> 4)
+}
+
+/// Splits a color into 4-bit components.
+fn rgb444(color: &Color) -> (u8, u8, u8) {
+    (
+        ((color.hex >> 12) & 0x0F) as u8,
+        ((color.hex >> 7) & 0x0F) as u8,
+        ((color.hex >> 1) & 0x0F) as u8,
+    )
+}
+
+/// Writes bytes sequentially into a caller-provided buffer.
+pub(crate) struct SliceWriter<'a> {
+    /// Buffer written to.
+    buffer: &'a mut [u8],
+
+    /// Number of bytes written.
+    length: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    /// Creates a writer starting at the beginning of `buffer`.
+    pub(crate) fn new(buffer: &'a mut [u8]) -> SliceWriter<'a> {
+        SliceWriter { buffer, length: 0 }
+    }
+
+    /// Number of bytes written.
+    pub(crate) fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the bytes written.
+    pub(crate) fn written(&self) -> &[u8] {
+        &self.buffer[..self.length]
+    }
+
+    /// Starts writing at the beginning of the buffer again.
+    pub(crate) fn clear(&mut self) {
+        self.length = 0;
+    }
+}
+
+impl<'a> Extend<u8> for SliceWriter<'a> {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, bytes: I) {
+        for byte in bytes {
+            self.buffer[self.length] = byte;
+            self.length += 1;
+        }
+    }
+}
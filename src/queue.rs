@@ -0,0 +1,183 @@
+//! Deferred drawing through a lock-free queue.
+//!
+//! In applications with interrupt-driven tasks, such as with RTIC, sharing the whole driver as a
+//! resource serializes rendering with time-critical tasks. Instead, a [`DrawQueue`](struct.DrawQueue.html)
+//! is split into a producer that records draw operations without locking and a consumer that
+//! owns the driver and performs the operations from a low-priority task.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! static mut QUEUE: DrawQueue<32> = DrawQueue::new();
+//!
+//! let (mut producer, mut consumer) = unsafe { QUEUE.split() };
+//!
+//! // High-priority task, e.g. a sensor interrupt.
+//! producer
+//!     .enqueue(DrawOp::FilledRect {
+//!         x0: 0,
+//!         y0: 0,
+//!         x1: level,
+//!         y1: 7,
+//!         color: Color::from_default(DefaultColor::Green),
+//!     })
+//!     .ok();
+//!
+//! // Low-priority task owning the display.
+//! consumer.drain(&mut display);
+//! ```
+
+use crate::color::Color;
+use crate::controller::Controller;
+use crate::error::DisplayError;
+use crate::ST7734;
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+use heapless::spsc::{Consumer, Producer, Queue};
+
+/// Recorded draw operation, executed with the driver method of the same name.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DrawOp {
+    /// See `ST7734::draw_pixel`.
+    Pixel { x: u16, y: u16, color: Color },
+
+    /// See `ST7734::draw_line`.
+    Line { x0: u16, y0: u16, x1: u16, y1: u16, color: Color },
+
+    /// See `ST7734::draw_rect`.
+    Rect { x0: u16, y0: u16, x1: u16, y1: u16, color: Color },
+
+    /// See `ST7734::draw_filled_rect`.
+    FilledRect { x0: u16, y0: u16, x1: u16, y1: u16, color: Color },
+
+    /// See `ST7734::draw_circle`.
+    Circle { x: u16, y: u16, radius: u16, color: Color },
+
+    /// See `ST7734::draw_filled_circle`.
+    FilledCircle { x: u16, y: u16, radius: u16, color: Color },
+
+    /// See `ST7734::fill_screen`.
+    FillScreen { color: Color },
+}
+
+impl DrawOp {
+    /// Performs the operation on `display`.
+    pub fn execute<SPI, PIN, DELAY, C>(
+        &self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        match *self {
+            DrawOp::Pixel { x, y, color } => display.draw_pixel(x, y, &color),
+            DrawOp::Line { x0, y0, x1, y1, color } => display.draw_line(x0, y0, x1, y1, &color),
+            DrawOp::Rect { x0, y0, x1, y1, color } => display.draw_rect(x0, y0, x1, y1, &color),
+            DrawOp::FilledRect { x0, y0, x1, y1, color } => display.draw_filled_rect(x0, y0, x1, y1, &color),
+            DrawOp::Circle { x, y, radius, color } => display.draw_circle(x, y, radius, &color),
+            DrawOp::FilledCircle { x, y, radius, color } => display.draw_filled_circle(x, y, radius, &color),
+            DrawOp::FillScreen { color } => display.fill_screen(&color),
+        }
+    }
+}
+
+/// Queue of up to `N - 1` draw operations shared between a producer and a consumer.
+pub struct DrawQueue<const N: usize> {
+    /// Recorded operations.
+    queue: Queue<DrawOp, N>,
+}
+
+impl<const N: usize> DrawQueue<N> {
+    /// Creates an empty queue. Usable in `static` items.
+    pub const fn new() -> DrawQueue<N> {
+        DrawQueue { queue: Queue::new() }
+    }
+
+    /// Splits the queue into its producer and consumer handles, which can be moved to different
+    /// tasks or interrupt handlers.
+    pub fn split(&mut self) -> (DrawProducer<'_, N>, DrawConsumer<'_, N>) {
+        let (producer, consumer) = self.queue.split();
+        (DrawProducer { producer }, DrawConsumer { consumer })
+    }
+}
+
+impl<const N: usize> Default for DrawQueue<N> {
+    fn default() -> DrawQueue<N> {
+        DrawQueue::new()
+    }
+}
+
+/// Handle recording draw operations. Never blocks.
+pub struct DrawProducer<'a, const N: usize> {
+    /// Producing end of the queue.
+    producer: Producer<'a, DrawOp, N>,
+}
+
+impl<'a, const N: usize> DrawProducer<'a, N> {
+    /// Records an operation. Returns it back if the queue is full.
+    pub fn enqueue(&mut self, op: DrawOp) -> Result<(), DrawOp> {
+        self.producer.enqueue(op)
+    }
+
+    /// Returns `true` if another operation can be recorded.
+    pub fn ready(&self) -> bool {
+        self.producer.ready()
+    }
+}
+
+/// Handle performing recorded draw operations on the display.
+pub struct DrawConsumer<'a, const N: usize> {
+    /// Consuming end of the queue.
+    consumer: Consumer<'a, DrawOp, N>,
+}
+
+impl<'a, const N: usize> DrawConsumer<'a, N> {
+    /// Performs all recorded operations in order and returns how many were performed. Stops at
+    /// the first operation that fails, which is not performed again.
+    pub fn drain<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<usize, DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        self.drain_max(display, usize::MAX)
+    }
+
+    /// Performs up to `max` recorded operations in order and returns how many were performed.
+    /// Bounds the time spent in a single call.
+    pub fn drain_max<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        max: usize,
+    ) -> Result<usize, DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let mut count = 0;
+        while count < max {
+            match self.consumer.dequeue() {
+                Some(op) => op.execute(display)?,
+                None => break,
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Returns `true` if operations are waiting to be performed.
+    pub fn ready(&self) -> bool {
+        self.consumer.ready()
+    }
+}
@@ -0,0 +1,168 @@
+//! Reading registers and display memory over software SPI.
+//!
+//! The controller answers read commands on its serial data output, which some modules bring
+//! out as SDO or MISO. Drivers using software SPI clock the answer in on an input pin passed to
+//! the read methods, which helps diagnosing wiring problems on boards without a hardware SPI
+//! bus. Drivers using hardware SPI only write and return `Error::Unsupported`.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let id = display.read_display_id(&miso)?;
+//! if id.manufacturer == 0x00 || id.manufacturer == 0xFF {
+//!     log("display not answering, check the wiring");
+//! }
+//! ```
+
+use crate::color::Color;
+use crate::command::Instruction;
+use crate::controller::Controller;
+use crate::error::{DisplayError, Error};
+use crate::geometry::Rect;
+use crate::ST7734;
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::{InputPin, OutputPin};
+
+/// Identification returned by RDDID.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DisplayId {
+    /// Manufacturer ID of the module.
+    pub manufacturer: u8,
+
+    /// Module or driver version.
+    pub version: u8,
+
+    /// Module or driver ID.
+    pub driver: u8,
+}
+
+impl<SPI, PIN, DELAY, C> ST7734<SPI, PIN, DELAY, C>
+where
+    SPI: spi::Write<u8>,
+    PIN: OutputPin,
+    DELAY: DelayMs<u64>,
+    C: Controller,
+{
+    /// Reads the manufacturer, version and driver ID of the display with RDDID. The fields read
+    /// as `0x00` or `0xFF` if the display doesn't answer.
+    pub fn read_display_id<MISO>(&mut self, miso: &MISO) -> Result<DisplayId, DisplayError<SPI, PIN>>
+    where
+        MISO: InputPin<Error = PIN::Error>,
+    {
+        let mut id = [0; 3];
+        self.read_command(Instruction::RDDID, 1, id.len(), miso, |index, byte| id[index] = byte)?;
+        Ok(DisplayId {
+            manufacturer: id[0],
+            version: id[1],
+            driver: id[2],
+        })
+    }
+
+    /// Reads the 32 status bits of the display with RDDST, which report e.g. the memory access
+    /// order, pixel format, sleep mode and whether the display is on.
+    pub fn read_display_status<MISO>(&mut self, miso: &MISO) -> Result<u32, DisplayError<SPI, PIN>>
+    where
+        MISO: InputPin<Error = PIN::Error>,
+    {
+        let mut status = 0;
+        self.read_command(Instruction::RDDST, 1, 4, miso, |_, byte| {
+            status = status << 8 | byte as u32;
+        })?;
+        Ok(status)
+    }
+
+    /// Reads the pixels of `area` from the display memory with RAMRD into `pixels`, row by row.
+    /// The area is clipped to the display bounds. The controller returns 6 bits per color
+    /// component, independent of the pixel format. Returns `Error::OutOfBounds` if `pixels`
+    /// can't hold the clipped area.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut pixels = [Color::from_hex(0); 16 * 16];
+    /// display.read_memory(&Rect::new(0, 0, 15, 15), &miso, &mut pixels)?;
+    /// ```
+    pub fn read_memory<MISO>(
+        &mut self,
+        area: &Rect,
+        miso: &MISO,
+        pixels: &mut [Color],
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        MISO: InputPin<Error = PIN::Error>,
+    {
+        let area = match area.intersection(&self.bounds()) {
+            Some(area) => area,
+            None => return Ok(()),
+        };
+        let count = area.width() as usize * area.height() as usize;
+        if pixels.len() < count {
+            return Err(Error::OutOfBounds);
+        }
+        if self.clk.is_none() {
+            return Err(Error::Unsupported);
+        }
+
+        self.set_address_window(area.x0, area.y0, area.x1, area.y1)?;
+        let mut components = [0; 3];
+        // The first byte after RAMRD is a dummy byte.
+        self.read_command(Instruction::RAMRD, 8, 3 * count, miso, |index, byte| {
+            components[index % 3] = byte;
+            if index % 3 == 2 {
+                let [red, green, blue] = components;
+                pixels[index / 3] = Color::from_rgb888(red, green, blue);
+            }
+        })
+    }
+
+    /// Sends `instruction`, skips `dummy_clocks` clock cycles and clocks in `count` bytes on
+    /// `miso`, which are passed to `byte` with their index.
+    fn read_command<MISO, F>(
+        &mut self,
+        instruction: Instruction,
+        dummy_clocks: u8,
+        count: usize,
+        miso: &MISO,
+        mut byte: F,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        MISO: InputPin<Error = PIN::Error>,
+        F: FnMut(usize, u8),
+    {
+        if self.clk.is_none() {
+            return Err(Error::Unsupported);
+        }
+
+        self.framed(|display| {
+            display.write_byte(num::ToPrimitive::to_u8(&instruction).unwrap(), false)?;
+            for _ in 0..dummy_clocks {
+                display.clock_in(miso)?;
+            }
+            for index in 0..count {
+                let mut value = 0;
+                for _ in 0..8 {
+                    value = value << 1 | display.clock_in(miso)? as u8;
+                }
+                byte(index, value);
+            }
+            Ok(())
+        })
+    }
+
+    /// Reads one bit on `miso` while the clock is high. The controller changes its output on
+    /// the falling edge.
+    fn clock_in<MISO>(&mut self, miso: &MISO) -> Result<bool, DisplayError<SPI, PIN>>
+    where
+        MISO: InputPin<Error = PIN::Error>,
+    {
+        // Only called after checking that software SPI is used.
+        let clk = self.clk.as_mut().unwrap();
+        clk.set_high().map_err(Error::Pin)?;
+        let bit = miso.is_high().map_err(Error::Pin)?;
+        clk.set_low().map_err(Error::Pin)?;
+        Ok(bit)
+    }
+}
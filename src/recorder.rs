@@ -0,0 +1,220 @@
+//! Recording of displayed frames as animated GIF or PNG.
+//!
+//! This module is available with the `std` feature. A [`Recorder`](struct.Recorder.html) keeps
+//! a copy of every frame passed to it together with how long it was shown, and writes the
+//! recording as GIF or APNG. This is handy for documenting the behavior of a user interface or
+//! attaching an animation glitch to a bug report, on the device as well as with the emulated
+//! panel of the `testing` module.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let mut recorder = Recorder::new();
+//!
+//! for _ in 0..100 {
+//!     draw_frame(&mut frame);
+//!     frame.flush(&mut display)?;
+//!     recorder.capture(&frame.as_image());
+//! }
+//!
+//! recorder.save("ui.gif").expect("error saving recording");
+//! ```
+
+use crate::color::Color;
+use crate::image::Image;
+use crate::screenshot::rgb888;
+
+use gif::SetParameter;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use std::vec::Vec;
+
+/// Time the last frame of a recording is shown, as no later frame ends it.
+const LAST_FRAME_DELAY: Duration = Duration::from_millis(100);
+
+/// Frame of a recording.
+struct RecordedFrame {
+    /// Pixels of the frame, row by row.
+    pixels: Vec<Color>,
+
+    /// Time the frame is shown.
+    delay: Duration,
+}
+
+/// Recorder of displayed frames.
+pub struct Recorder {
+    /// Width of the frames, set by the first frame.
+    width: u16,
+
+    /// Height of the frames, set by the first frame.
+    height: u16,
+
+    /// Frames recorded so far.
+    frames: Vec<RecordedFrame>,
+
+    /// Time the last frame was captured with `capture`.
+    shown: Option<Instant>,
+}
+
+impl Default for Recorder {
+    fn default() -> Recorder {
+        Recorder::new()
+    }
+}
+
+impl Recorder {
+    /// Creates an empty recording.
+    pub fn new() -> Recorder {
+        Recorder {
+            width: 0,
+            height: 0,
+            frames: Vec::new(),
+            shown: None,
+        }
+    }
+
+    /// Number of frames recorded. Consecutive identical frames count as one.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if no frame was recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Total time of the recording.
+    pub fn duration(&self) -> Duration {
+        self.frames.iter().map(|frame| frame.delay).sum()
+    }
+
+    /// Records `image` as the frame shown from now on, ending the previous frame. Call it right
+    /// after flushing a frame to the display.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image has a different size than the first frame.
+    pub fn capture(&mut self, image: &Image) {
+        let now = Instant::now();
+        if let (Some(shown), Some(last)) = (self.shown, self.frames.last_mut()) {
+            last.delay = now - shown;
+        }
+        if self.push(image, LAST_FRAME_DELAY) {
+            self.shown = Some(now);
+        }
+    }
+
+    /// Records `image` as a frame shown for `delay`, independent of the actual time. This makes
+    /// recordings reproducible, e.g. in tests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image has a different size than the first frame.
+    pub fn capture_with_delay(&mut self, image: &Image, delay: Duration) {
+        self.shown = None;
+        if !self.push(image, delay) {
+            if let Some(last) = self.frames.last_mut() {
+                last.delay += delay;
+            }
+        }
+    }
+
+    /// Removes all frames.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.shown = None;
+    }
+
+    /// Writes the recording to `path` as APNG if the file name ends with `.png` or `.apng`, and
+    /// as GIF otherwise.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let file = BufWriter::new(File::create(path)?);
+        let png = path.extension().is_some_and(|extension| {
+            extension.eq_ignore_ascii_case("png") || extension.eq_ignore_ascii_case("apng")
+        });
+        if png {
+            self.write_apng(file)
+        } else {
+            self.write_gif(file)
+        }
+    }
+
+    /// Writes the recording as GIF repeating forever. Colors are reduced to a palette of 256
+    /// colors per frame, and delays are rounded to the 10 ms steps of GIF.
+    pub fn write_gif<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.check_frames()?;
+        let mut encoder = gif::Encoder::new(writer, self.width, self.height, &[])?;
+        encoder.set(gif::Repeat::Infinite)?;
+        for frame in &self.frames {
+            let data = rgb888(&self.image(frame));
+            let mut gif_frame = gif::Frame::from_rgb_speed(self.width, self.height, &data, 10);
+            // Most viewers replace delays below 20 ms by 100 ms.
+            let centiseconds = (frame.delay.as_millis() + 5) / 10;
+            gif_frame.delay = centiseconds.max(2).min(u16::MAX as u128) as u16;
+            encoder.write_frame(&gif_frame)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the recording as APNG repeating forever, keeping all colors exactly.
+    pub fn write_apng<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.check_frames()?;
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_animated(self.frames.len() as u32, 0)?;
+        let mut writer = encoder.write_header()?;
+        for frame in &self.frames {
+            let milliseconds = frame.delay.as_millis().min(u16::MAX as u128) as u16;
+            writer.set_frame_delay(milliseconds, 1000)?;
+            writer.write_image_data(&rgb888(&self.image(frame)))?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Appends `image` as a frame shown for `delay`. Returns `false` without appending if it is
+    /// identical to the last frame.
+    fn push(&mut self, image: &Image, delay: Duration) -> bool {
+        if self.frames.is_empty() {
+            self.width = image.width();
+            self.height = image.height();
+        }
+        assert!(
+            image.width() == self.width && image.height() == self.height,
+            "frame of {}x{} pixels in a recording of {}x{} pixels",
+            image.width(),
+            image.height(),
+            self.width,
+            self.height
+        );
+
+        let mut pixels = Vec::with_capacity(self.width as usize * self.height as usize);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                pixels.push(image.pixel(x, y));
+            }
+        }
+        if self.frames.last().is_some_and(|last| last.pixels == pixels) {
+            return false;
+        }
+        self.frames.push(RecordedFrame { pixels, delay });
+        true
+    }
+
+    /// Returns a recorded frame as image.
+    fn image<'a>(&self, frame: &'a RecordedFrame) -> Image<'a> {
+        Image::new(&frame.pixels, self.width, self.height)
+    }
+
+    /// Returns an error if there are no frames to write.
+    fn check_frames(&self) -> io::Result<()> {
+        if self.frames.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "no frames recorded"));
+        }
+        Ok(())
+    }
+}
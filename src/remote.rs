@@ -0,0 +1,95 @@
+//! Mirroring of the screen content to a remote viewer over TCP.
+//!
+//! This module is available with the `std` feature. A [`RemoteServer`](struct.RemoteServer.html)
+//! accepts viewers and sends them every frame that is drawn into a framebuffer, so devices
+//! without a local monitor can be observed from a desktop. The `remote_viewer` example shows
+//! the frames in a window.
+//!
+//! Each frame is sent as the bytes `ST77`, the width and height as big endian `u16` and the
+//! pixels row by row as big endian RGB565.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let mut server = RemoteServer::bind("0.0.0.0:7735").expect("error binding server");
+//!
+//! loop {
+//!     draw_ui(&mut framebuffer);
+//!     framebuffer.flush(&mut display);
+//!     server.send_frame(&framebuffer);
+//! }
+//! ```
+
+use crate::color::Color;
+use crate::framebuffer::rgb::Framebuffer;
+use crate::framebuffer::storage::Storage;
+use crate::image::Image;
+
+use std::io::{self, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::vec::Vec;
+
+/// Bytes starting every frame.
+pub const FRAME_MAGIC: &[u8; 4] = b"ST77";
+
+/// Server sending frames to all connected viewers.
+pub struct RemoteServer {
+    /// Listener accepting viewers, in non-blocking mode.
+    listener: TcpListener,
+
+    /// Connected viewers.
+    clients: Vec<TcpStream>,
+}
+
+impl RemoteServer {
+    /// Listens for viewers on `address`.
+    pub fn bind<A: ToSocketAddrs>(address: A) -> io::Result<RemoteServer> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        Ok(RemoteServer {
+            listener,
+            clients: Vec::new(),
+        })
+    }
+
+    /// Returns the number of connected viewers.
+    pub fn clients(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Sends the content of `framebuffer` to all viewers.
+    pub fn send_frame<S: Storage<Color>>(&mut self, framebuffer: &Framebuffer<S>) {
+        self.send_image(&framebuffer.as_image());
+    }
+
+    /// Sends `image` as a frame to all viewers. Viewers that connected since the last frame
+    /// are added first, viewers that disconnected are dropped.
+    pub fn send_image(&mut self, image: &Image) {
+        self.accept_clients();
+        if self.clients.is_empty() {
+            return;
+        }
+
+        let mut frame = Vec::with_capacity(8 + 2 * image.width() as usize * image.height() as usize);
+        frame.extend_from_slice(FRAME_MAGIC);
+        frame.extend_from_slice(&image.width().to_be_bytes());
+        frame.extend_from_slice(&image.height().to_be_bytes());
+        for y in 0..image.height() {
+            for x in 0..image.width() {
+                frame.extend_from_slice(&image.pixel(x, y).to_be_bytes());
+            }
+        }
+
+        self.clients.retain_mut(|client| client.write_all(&frame).is_ok());
+    }
+
+    /// Adds all pending viewers.
+    fn accept_clients(&mut self) {
+        while let Ok((client, _)) = self.listener.accept() {
+            if client.set_nonblocking(false).is_ok() {
+                let _ = client.set_nodelay(true);
+                self.clients.push(client);
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Pacing of animations at a fixed frame rate.
+//!
+//! `RenderLoop` calls a render closure once per frame and waits for the next frame with the
+//! delay provider of the driver. If rendering takes longer than a frame, the frames that are
+//! already late are skipped instead of being rendered in a burst, so animations keep their
+//! speed. Time is measured with a timestamp source in microseconds provided by the
+//! application, like for `FrameTimes`.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let start = Instant::now();
+//! let mut render_loop = RenderLoop::new(30, || start.elapsed().as_micros() as u64);
+//!
+//! render_loop.run(&mut display, |display, frame| {
+//!     let x = (frame.time / 20_000 % 128) as u16;
+//!     display.draw_filled_rect(x, 60, x + 7, 67, &Color::from_default(DefaultColor::Red))?;
+//!     Ok(frame.number < 300)
+//! })?;
+//!
+//! println!("{} frames skipped", render_loop.skipped());
+//! ```
+
+use crate::controller::Controller;
+use crate::error::DisplayError;
+use crate::stats::FrameTimes;
+use crate::ST7734;
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Timing of the frame being rendered.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Frame {
+    /// Number of the frame since the loop started, counting skipped frames.
+    pub number: u64,
+
+    /// Microseconds since the loop started.
+    pub time: u64,
+
+    /// Microseconds since the previous frame was rendered.
+    pub delta: u64,
+
+    /// Number of frames skipped right before this one.
+    pub skipped: u64,
+}
+
+/// Calls a render closure at a target frame rate.
+pub struct RenderLoop<N> {
+    /// Timestamp source in microseconds.
+    now: N,
+
+    /// Duration of a frame in microseconds.
+    period: u64,
+
+    /// Timestamp of the first frame, once started.
+    start: Option<u64>,
+
+    /// Number of the next frame.
+    next: u64,
+
+    /// Timestamp of the previous frame.
+    previous: u64,
+
+    /// Total number of skipped frames.
+    skipped: u64,
+
+    /// Durations of the render closure.
+    times: FrameTimes,
+}
+
+impl<N: FnMut() -> u64> RenderLoop<N> {
+    /// Creates a loop rendering `fps` frames per second, measuring time with `now`.
+    pub fn new(fps: u32, now: N) -> RenderLoop<N> {
+        RenderLoop {
+            now,
+            period: 1_000_000 / fps.max(1) as u64,
+            start: None,
+            next: 0,
+            previous: 0,
+            skipped: 0,
+            times: FrameTimes::new(),
+        }
+    }
+
+    /// Total number of frames skipped because rendering overran.
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Durations of the render closure in microseconds.
+    pub fn frame_times(&self) -> &FrameTimes {
+        &self.times
+    }
+
+    /// Waits until the next frame is due and renders it. Frames whose time already passed are
+    /// skipped. Returns the result of `render`.
+    pub fn run_frame<SPI, PIN, DELAY, C, F>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        render: F,
+    ) -> Result<bool, DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+        F: FnOnce(&mut ST7734<SPI, PIN, DELAY, C>, &Frame) -> Result<bool, DisplayError<SPI, PIN>>,
+    {
+        let now = (self.now)();
+        let start = *self.start.get_or_insert(now);
+        let due = start + self.next * self.period;
+
+        let now = if now < due {
+            let wait = due - now;
+            if wait >= 1000 {
+                display.delay.delay_ms(wait / 1000);
+            }
+            (self.now)()
+        } else {
+            now
+        };
+
+        // Skip the frames that are late by a whole period or more.
+        let late = now.saturating_sub(due) / self.period;
+        self.skipped += late;
+        self.next += late;
+
+        let frame = Frame {
+            number: self.next,
+            time: now - start,
+            delta: if self.next == 0 { 0 } else { now - self.previous },
+            skipped: late,
+        };
+        self.previous = now;
+        self.next += 1;
+
+        let result = render(display, &frame);
+        self.times.record((self.now)().saturating_sub(now));
+        result
+    }
+
+    /// Renders frames until the render closure returns `false` or fails.
+    pub fn run<SPI, PIN, DELAY, C, F>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        mut render: F,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+        F: FnMut(&mut ST7734<SPI, PIN, DELAY, C>, &Frame) -> Result<bool, DisplayError<SPI, PIN>>,
+    {
+        while self.run_frame(display, &mut render)? {}
+        Ok(())
+    }
+}
@@ -0,0 +1,429 @@
+//! Retained-mode drawing layer.
+//!
+//! A [`Scene`](struct.Scene.html) owns a set of nodes (shapes, text and images) with a position
+//! and a z-order. Adding, changing or removing a node marks the area it covers as damaged, and
+//! [`Scene::render`](struct.Scene.html#method.render) repaints only the damaged areas. Every pixel
+//! within a damaged area is resolved against all overlapping nodes, so overlapping nodes are
+//! composed correctly without flicker.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let mut scene = Scene::new(128, 160, Color::from_default(DefaultColor::Black));
+//! let label = scene.add(Node::new(10, 20, Shape::text::<Font57>("Hi", Color::from_default(DefaultColor::White))));
+//! scene.render(&mut display);
+//!
+//! scene.modify(label, |node| node.x += 10);
+//! scene.render(&mut display);
+//! ```
+
+use crate::color::Color;
+#[cfg(feature = "fonts")]
+use crate::fonts::{Font, CHARACTER_ADVANCE, LINE_ADVANCE};
+use crate::geometry::Rect;
+use crate::controller::Controller;
+use crate::error::DisplayError;
+use crate::ST7734;
+
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Width of a single glyph in pixels, the character advance without the spacing column.
+#[cfg(feature = "fonts")]
+const GLYPH_WIDTH: u16 = CHARACTER_ADVANCE - 1;
+
+/// Height of a single glyph in pixels, the line advance without the spacing row.
+#[cfg(feature = "fonts")]
+const GLYPH_HEIGHT: u16 = LINE_ADVANCE - 1;
+
+/// Content of a scene node.
+pub enum Shape {
+    /// Rectangle border of the given size.
+    Rect { width: u16, height: u16, color: Color },
+
+    /// Filled rectangle of the given size.
+    FilledRect { width: u16, height: u16, color: Color },
+
+    /// Circle border around the node position.
+    Circle { radius: u16, color: Color },
+
+    /// Filled circle around the node position.
+    FilledCircle { radius: u16, color: Color },
+
+    /// Line of text. `glyphs` holds five column bytes per character as returned by a `Font`.
+    #[cfg(feature = "fonts")]
+    Text { glyphs: Vec<u8>, color: Color },
+
+    /// Image whose pixels are stored row by row.
+    Image { width: u16, height: u16, pixels: Vec<Color> },
+}
+
+impl Shape {
+    /// Creates a text shape rendered with font `F`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let text = Shape::text::<Font57>("Hello", Color::from_default(DefaultColor::White));
+    /// ```
+    #[cfg(feature = "fonts")]
+    pub fn text<F: Font>(text: &str, color: Color) -> Shape {
+        let mut glyphs = Vec::new();
+        for c in text.chars() {
+            let character_data = <F as Font>::get_char(c);
+            glyphs.extend_from_slice(&character_data[..GLYPH_WIDTH as usize]);
+        }
+
+        Shape::Text { glyphs, color }
+    }
+}
+
+/// Positioned element of a scene.
+pub struct Node {
+    /// Horizontal position. Circles are centered here, text starts here, everything else
+    /// has its top left corner here.
+    pub x: u16,
+
+    /// Vertical position.
+    pub y: u16,
+
+    /// Stacking order. Nodes with a higher value are drawn on top.
+    pub z: i16,
+
+    /// Hidden nodes are not drawn.
+    pub visible: bool,
+
+    /// Content of the node.
+    pub shape: Shape,
+}
+
+impl Node {
+    /// Creates a visible node at the given position with a z-order of `0`.
+    pub fn new(x: u16, y: u16, shape: Shape) -> Node {
+        Node {
+            x,
+            y,
+            z: 0,
+            visible: true,
+            shape,
+        }
+    }
+
+    /// Returns the area covered by the node.
+    pub fn bounds(&self) -> Rect {
+        match self.shape {
+            Shape::Rect { width, height, .. }
+            | Shape::FilledRect { width, height, .. }
+            | Shape::Image { width, height, .. } => Rect::new(
+                self.x,
+                self.y,
+                self.x.saturating_add(width.saturating_sub(1)),
+                self.y.saturating_add(height.saturating_sub(1)),
+            ),
+            Shape::Circle { radius, .. } | Shape::FilledCircle { radius, .. } => Rect::new(
+                self.x.saturating_sub(radius),
+                self.y.saturating_sub(radius),
+                self.x.saturating_add(radius),
+                self.y.saturating_add(radius),
+            ),
+            #[cfg(feature = "fonts")]
+            Shape::Text { ref glyphs, .. } => {
+                let characters = (glyphs.len() as u16 / GLYPH_WIDTH).max(1);
+                Rect::new(
+                    self.x.saturating_sub(GLYPH_WIDTH - 1),
+                    self.y.saturating_sub(GLYPH_HEIGHT - 1),
+                    self.x.saturating_add((characters - 1) * CHARACTER_ADVANCE),
+                    self.y,
+                )
+            }
+        }
+    }
+
+    /// Returns the color of the node at the given display coordinates, or `None` if the node
+    /// does not cover that pixel. Empty rectangles and images cover no pixels.
+    fn color_at(&self, x: u16, y: u16) -> Option<&Color> {
+        match self.shape {
+            Shape::Rect {
+                width,
+                height,
+                ref color,
+            } => {
+                let dx = x.wrapping_sub(self.x);
+                let dy = y.wrapping_sub(self.y);
+                if dx >= width || dy >= height {
+                    return None;
+                }
+
+                if dx == 0 || dy == 0 || dx == width - 1 || dy == height - 1 {
+                    Some(color)
+                } else {
+                    None
+                }
+            }
+            Shape::FilledRect {
+                width,
+                height,
+                ref color,
+            } => {
+                if x.wrapping_sub(self.x) < width && y.wrapping_sub(self.y) < height {
+                    Some(color)
+                } else {
+                    None
+                }
+            }
+            Shape::Circle { radius, ref color } => {
+                let d2 = self.distance_squared(x, y);
+                let r = radius as i32;
+                if d2 <= r * r && d2 > (r - 1) * (r - 1) {
+                    Some(color)
+                } else {
+                    None
+                }
+            }
+            Shape::FilledCircle { radius, ref color } => {
+                let r = radius as i32;
+                if self.distance_squared(x, y) <= r * r {
+                    Some(color)
+                } else {
+                    None
+                }
+            }
+            #[cfg(feature = "fonts")]
+            Shape::Text {
+                ref glyphs,
+                ref color,
+            } => {
+                // Glyphs extend to the left of and above their anchor, as in `draw_character`.
+                let dx = (x as i32) - (self.x as i32) + (GLYPH_WIDTH as i32 - 1);
+                let row = (self.y as i32) - (y as i32);
+                if dx < 0 || row < 0 || row >= GLYPH_HEIGHT as i32 {
+                    return None;
+                }
+
+                let advance = CHARACTER_ADVANCE as i32;
+                let character = dx / advance;
+                let col = (GLYPH_WIDTH as i32 - 1) - dx % advance;
+                if col < 0 {
+                    return None;
+                }
+
+                match glyphs.get((character * GLYPH_WIDTH as i32 + col) as usize) {
+                    Some(bits) if bits & (0x01 << row) != 0 => Some(color),
+                    _ => None,
+                }
+            }
+            Shape::Image {
+                width,
+                height,
+                ref pixels,
+            } => {
+                let dx = x.wrapping_sub(self.x);
+                let dy = y.wrapping_sub(self.y);
+                if dx < width && dy < height {
+                    pixels.get(dy as usize * width as usize + dx as usize)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Squared distance between the node position and the given point.
+    fn distance_squared(&self, x: u16, y: u16) -> i32 {
+        let dx = (x as i32) - (self.x as i32);
+        let dy = (y as i32) - (self.y as i32);
+        dx * dx + dy * dy
+    }
+}
+
+/// Handle referring to a node within a `Scene`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NodeId(usize);
+
+/// Collection of nodes that tracks which areas of the display need to be repainted.
+pub struct Scene {
+    /// Nodes of the scene. Removed nodes leave an empty slot so that ids stay valid.
+    nodes: Vec<Option<Node>>,
+
+    /// Areas that changed since the last render.
+    damage: Vec<Rect>,
+
+    /// Color of pixels not covered by any node.
+    background: Color,
+
+    /// Visible area of the display, `None` for a display without pixels.
+    screen: Option<Rect>,
+}
+
+impl Scene {
+    /// Creates an empty scene for a display of the given size. The whole display is marked
+    /// as damaged so that the first render paints the background. Nothing is ever drawn if the
+    /// width or height is `0`.
+    pub fn new(width: u16, height: u16, background: Color) -> Scene {
+        let screen = if width == 0 || height == 0 {
+            None
+        } else {
+            Some(Rect::new(0, 0, width - 1, height - 1))
+        };
+
+        Scene {
+            nodes: Vec::new(),
+            damage: screen.into_iter().collect(),
+            background,
+            screen,
+        }
+    }
+
+    /// Adds a node to the scene and returns its id.
+    pub fn add(&mut self, node: Node) -> NodeId {
+        self.invalidate(node.bounds());
+
+        let id = match self.nodes.iter().position(|n| n.is_none()) {
+            Some(index) => {
+                self.nodes[index] = Some(node);
+                index
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+
+        NodeId(id)
+    }
+
+    /// Removes a node from the scene and returns it.
+    pub fn remove(&mut self, id: NodeId) -> Option<Node> {
+        let node = self.nodes.get_mut(id.0).and_then(|n| n.take());
+        if let Some(ref n) = node {
+            self.invalidate(n.bounds());
+        }
+        node
+    }
+
+    /// Returns the node with the given id.
+    pub fn get(&self, id: NodeId) -> Option<&Node> {
+        self.nodes.get(id.0).and_then(|n| n.as_ref())
+    }
+
+    /// Changes a node. The areas covered by the node before and after the change are marked
+    /// as damaged.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// scene.modify(id, |node| {
+    ///     node.x += 5;
+    ///     node.z = 2;
+    /// });
+    /// ```
+    pub fn modify<F>(&mut self, id: NodeId, change: F)
+    where
+        F: FnOnce(&mut Node),
+    {
+        let (before, after) = match self.nodes.get_mut(id.0) {
+            Some(Some(node)) => {
+                let before = node.bounds();
+                change(node);
+                (before, node.bounds())
+            }
+            _ => return,
+        };
+
+        self.invalidate(before);
+        self.invalidate(after);
+    }
+
+    /// Moves a node to a new position.
+    pub fn set_position(&mut self, id: NodeId, x: u16, y: u16) {
+        self.modify(id, |node| {
+            node.x = x;
+            node.y = y;
+        });
+    }
+
+    /// Changes the stacking order of a node.
+    pub fn set_z(&mut self, id: NodeId, z: i16) {
+        self.modify(id, |node| node.z = z);
+    }
+
+    /// Shows or hides a node.
+    pub fn set_visible(&mut self, id: NodeId, visible: bool) {
+        self.modify(id, |node| node.visible = visible);
+    }
+
+    /// Changes the background color and marks the whole display as damaged.
+    pub fn set_background(&mut self, background: Color) {
+        self.background = background;
+        self.invalidate_all();
+    }
+
+    /// Marks an area as damaged so that it gets repainted on the next render.
+    pub fn invalidate(&mut self, area: Rect) {
+        let mut area = match self.screen.and_then(|screen| area.intersection(&screen)) {
+            Some(a) => a,
+            None => return,
+        };
+
+        // Merge overlapping areas so that no pixel is transferred twice.
+        while let Some(index) = self.damage.iter().position(|d| d.intersects(&area)) {
+            area = area.union(&self.damage.swap_remove(index));
+        }
+
+        self.damage.push(area);
+    }
+
+    /// Marks the whole display as damaged.
+    pub fn invalidate_all(&mut self) {
+        self.damage.clear();
+        self.damage.extend(self.screen);
+    }
+
+    /// Returns `true` if there are areas that need to be repainted.
+    pub fn is_dirty(&self) -> bool {
+        !self.damage.is_empty()
+    }
+
+    /// Repaints all damaged areas on the display. If drawing fails, the remaining damage is
+    /// discarded, use `invalidate_all` to repaint everything.
+    pub fn render<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        // Topmost nodes first so that the first hit determines the pixel color.
+        let mut order: Vec<&Node> = self
+            .nodes
+            .iter()
+            .filter_map(|n| n.as_ref())
+            .filter(|n| n.visible)
+            .collect();
+        order.sort_by_key(|n| Reverse(n.z));
+
+        let background = &self.background;
+        for area in self.damage.drain(..) {
+            let candidates: Vec<&Node> = order
+                .iter()
+                .cloned()
+                .filter(|n| n.bounds().intersects(&area))
+                .collect();
+
+            display.fill_region(&area, |x, y| {
+                *candidates
+                    .iter()
+                    .filter_map(|n| n.color_at(x, y))
+                    .next()
+                    .unwrap_or(background)
+            })?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+//! Burn-in mitigation for always-on displays.
+//!
+//! The [`ScreenSaver`](struct.ScreenSaver.html) dims or blanks the display after a configurable
+//! idle period and can periodically move static content by a pixel. The screen saver does not own
+//! a clock; the application passes the current time in milliseconds to every call.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let mut saver = ScreenSaver::new(60_000, IdleAction::Blank);
+//! saver.set_pixel_shift(Some(300_000));
+//!
+//! loop {
+//!     if button_pressed() {
+//!         saver.activity(now());
+//!     }
+//!
+//!     if saver.update(&mut display, now())? {
+//!         let (dx, dy) = saver.offset();
+//!         redraw(&mut display, dx, dy);
+//!     }
+//! }
+//! ```
+
+use crate::backlight::Backlight;
+use crate::controller::Controller;
+use crate::error::DisplayError;
+use crate::ST7734;
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+use embedded_hal::PwmPin;
+
+/// Offsets cycled through when shifting content. Content never moves more than one pixel away
+/// from its original position.
+const SHIFT_OFFSETS: [(u16, u16); 4] = [(0, 0), (1, 0), (1, 1), (0, 1)];
+
+/// Duration of backlight fades when dimming or waking up in milliseconds.
+const DIM_FADE_MS: u64 = 250;
+
+/// Action taken once the display has been idle for the configured period.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum IdleAction {
+    /// Turns the display off. The display memory is retained.
+    Blank,
+
+    /// Dims the backlight to the given brightness. Falls back to blanking if no backlight
+    /// is available.
+    Dim(u8),
+}
+
+/// Change of the screen saver state.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ScreenSaverEvent {
+    /// The idle period elapsed.
+    Idle,
+
+    /// Activity was reported while idle.
+    Wake,
+
+    /// Content should be moved to the new `offset()`.
+    Shift,
+}
+
+/// Dims or blanks the display after an idle period and shifts static content.
+pub struct ScreenSaver {
+    /// Idle period in milliseconds after which `action` is taken.
+    idle_timeout: u64,
+
+    /// Action taken once idle.
+    action: IdleAction,
+
+    /// Interval in milliseconds in which content is shifted.
+    shift_interval: Option<u64>,
+
+    /// Time of the last reported activity.
+    last_activity: u64,
+
+    /// Time of the last content shift.
+    last_shift: u64,
+
+    /// Whether the idle action is currently applied.
+    idle: bool,
+
+    /// Whether activity was reported since the last update.
+    woken: bool,
+
+    /// Index into `SHIFT_OFFSETS`.
+    shift_index: usize,
+
+    /// Brightness to restore after dimming.
+    saved_brightness: Option<u8>,
+}
+
+impl ScreenSaver {
+    /// Creates a screen saver that takes `action` after `idle_timeout` milliseconds without
+    /// activity. Pixel shifting is disabled.
+    pub fn new(idle_timeout: u64, action: IdleAction) -> ScreenSaver {
+        ScreenSaver {
+            idle_timeout,
+            action,
+            shift_interval: None,
+            last_activity: 0,
+            last_shift: 0,
+            idle: false,
+            woken: false,
+            shift_index: 0,
+            saved_brightness: None,
+        }
+    }
+
+    /// Enables shifting content every `interval` milliseconds, or disables it with `None`.
+    pub fn set_pixel_shift(&mut self, interval: Option<u64>) {
+        self.shift_interval = interval;
+    }
+
+    /// Reports user activity at time `now`, which restarts the idle period.
+    pub fn activity(&mut self, now: u64) {
+        self.last_activity = now;
+        if self.idle {
+            self.woken = true;
+        }
+    }
+
+    /// Returns `true` if the idle action is currently applied.
+    pub fn is_idle(&self) -> bool {
+        self.idle
+    }
+
+    /// Returns the offset by which content should currently be moved.
+    pub fn offset(&self) -> (u16, u16) {
+        SHIFT_OFFSETS[self.shift_index]
+    }
+
+    /// Advances the screen saver state to time `now` and returns what changed, if anything.
+    /// Use this to apply the idle action manually; `update` applies it to the display.
+    pub fn poll(&mut self, now: u64) -> Option<ScreenSaverEvent> {
+        if self.woken {
+            self.woken = false;
+            self.idle = false;
+            return Some(ScreenSaverEvent::Wake);
+        }
+
+        if !self.idle && now.saturating_sub(self.last_activity) >= self.idle_timeout {
+            self.idle = true;
+            return Some(ScreenSaverEvent::Idle);
+        }
+
+        if let Some(interval) = self.shift_interval {
+            if now.saturating_sub(self.last_shift) >= interval {
+                self.last_shift = now;
+                self.shift_index = (self.shift_index + 1) % SHIFT_OFFSETS.len();
+                return Some(ScreenSaverEvent::Shift);
+            }
+        }
+
+        None
+    }
+
+    /// Advances the screen saver to time `now` and blanks or unblanks the display as needed.
+    /// Returns `true` if content has to be redrawn at the new `offset()`.
+    pub fn update<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        now: u64,
+    ) -> Result<bool, DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        match self.poll(now) {
+            Some(ScreenSaverEvent::Idle) => {
+                display.display_off()?;
+                Ok(false)
+            }
+            Some(ScreenSaverEvent::Wake) => {
+                display.display_on()?;
+                Ok(false)
+            }
+            Some(ScreenSaverEvent::Shift) => Ok(true),
+            None => Ok(false),
+        }
+    }
+
+    /// Same as `update` but dims `backlight` instead of blanking the display if the idle action
+    /// is `IdleAction::Dim`.
+    pub fn update_with_backlight<SPI, PIN, DELAY, C, PWM>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        backlight: &mut Backlight<PWM>,
+        now: u64,
+    ) -> Result<bool, DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+        PWM: PwmPin<Duty = u16>,
+    {
+        let level = match self.action {
+            IdleAction::Dim(level) => level,
+            IdleAction::Blank => return self.update(display, now),
+        };
+
+        match self.poll(now) {
+            Some(ScreenSaverEvent::Idle) => {
+                self.saved_brightness = Some(backlight.brightness());
+                display.fade_backlight(backlight, level, DIM_FADE_MS);
+                Ok(false)
+            }
+            Some(ScreenSaverEvent::Wake) => {
+                let brightness = self.saved_brightness.take().unwrap_or(255);
+                display.fade_backlight(backlight, brightness, DIM_FADE_MS);
+                Ok(false)
+            }
+            Some(ScreenSaverEvent::Shift) => Ok(true),
+            None => Ok(false),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Saving of screen content as image files.
+//!
+//! This module is available with the `std` feature. Images are written as binary PPM, which
+//! needs no encoder and is read by most image tools, or as PNG.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! framebuffer.save_screenshot("screenshot.png").expect("error saving screenshot");
+//! ```
+
+use crate::image::Image;
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::vec::Vec;
+
+/// Writes `image` to `path` as PNG if the file name ends with `.png`, and as PPM otherwise.
+pub fn save_image<P: AsRef<Path>>(path: P, image: &Image) -> io::Result<()> {
+    let path = path.as_ref();
+    let file = BufWriter::new(File::create(path)?);
+    let png = path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+    if png {
+        write_png(file, image)
+    } else {
+        write_ppm(file, image)
+    }
+}
+
+/// Writes `image` as binary PPM with 8 bits per color component.
+pub fn write_ppm<W: Write>(mut writer: W, image: &Image) -> io::Result<()> {
+    write!(writer, "P6\n{} {}\n255\n", image.width(), image.height())?;
+    writer.write_all(&rgb888(image))?;
+    writer.flush()
+}
+
+/// Writes `image` as 8-bit RGB PNG.
+pub fn write_png<W: Write>(writer: W, image: &Image) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(writer, image.width() as u32, image.height() as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(png_error)?;
+    writer.write_image_data(&rgb888(image)).map_err(png_error)
+}
+
+/// Returns the pixels of `image` as 8-bit RGB triples, row by row.
+pub(crate) fn rgb888(image: &Image) -> Vec<u8> {
+    let mut data = Vec::with_capacity(3 * image.width() as usize * image.height() as usize);
+    for y in 0..image.height() {
+        for x in 0..image.width() {
+            let (red, green, blue) = image.pixel(x, y).to_rgb888();
+            data.extend_from_slice(&[red, green, blue]);
+        }
+    }
+    data
+}
+
+/// Converts a PNG encoding error into an I/O error.
+fn png_error(error: png::EncodingError) -> io::Error {
+    match error {
+        png::EncodingError::IoError(error) => error,
+        error => io::Error::other(error),
+    }
+}
@@ -0,0 +1,226 @@
+use crate::ansi::{Action, Parser};
+use crate::color::Color;
+use crate::fonts::font57::Font57;
+use crate::fonts::{CHARACTER_ADVANCE, LINE_ADVANCE};
+use crate::geometry::Rect;
+use crate::text::DEFAULT_TAB_WIDTH;
+use crate::controller::Controller;
+use crate::error::{DisplayError, Error};
+use crate::{Orientation, ST7734};
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Scrolling log view using the hardware scrolling of the display.
+///
+/// New lines are drawn into the display memory line that just scrolled out of view and the
+/// scroll start address is advanced, so the content moves up by one line without redrawing the
+/// rest of the region. The areas above and below the region stay fixed. Hardware scrolling moves
+/// content along the memory rows, so the region works as expected in the portrait orientations.
+///
+/// # Examples
+///
+/// ```ignore
+/// let white = Color::from_default(DefaultColor::White);
+/// let black = Color::from_default(DefaultColor::Black);
+/// let mut log = ScrollRegion::new(&mut display, 16, 0, white, black)?;
+/// log.push_line(&mut display, "booting...")?;
+/// log.push_line(&mut display, "network up")?;
+/// ```
+pub struct ScrollRegion {
+    /// First line of the region on the display.
+    top: u16,
+
+    /// Display memory line the first line of the region is stored in.
+    memory_top: u16,
+
+    /// Height of the region in pixels, a multiple of the line height.
+    height: u16,
+
+    /// Offset of the next line to be written within the region.
+    next: u16,
+
+    /// Text color.
+    color: Color,
+
+    /// Background color of the region.
+    background: Color,
+
+    /// Parser for ANSI escape sequences, which holds the colors they selected.
+    ansi: Parser,
+}
+
+impl ScrollRegion {
+    /// Configures the display so that everything between `top_fixed` lines at the top and
+    /// `bottom_fixed` lines at the bottom scrolls, and clears the scrolling area. Lines are
+    /// counted on the panel in portrait orientation.
+    ///
+    /// Returns `Error::OutOfBounds` if the fixed areas leave no room for a line of text.
+    pub fn new<SPI, PIN, DELAY, C>(
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        top_fixed: u16,
+        bottom_fixed: u16,
+        color: Color,
+        background: Color,
+    ) -> Result<ScrollRegion, DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let panel_height = display.panel_height;
+        let available = panel_height
+            .checked_sub(top_fixed)
+            .and_then(|height| height.checked_sub(bottom_fixed))
+            .ok_or(Error::OutOfBounds)?;
+        let height = available - available % LINE_ADVANCE;
+        if height == 0 {
+            return Err(Error::OutOfBounds);
+        }
+
+        // Panels may show the memory from a line offset on. The memory lines around the region,
+        // including those the panel doesn't show, are fixed so that exactly the lines of the
+        // region scroll.
+        let memory_top = first_memory_line(display).saturating_add(top_fixed);
+        let bottom = display
+            .controller
+            .memory_height()
+            .checked_sub(memory_top)
+            .and_then(|lines| lines.checked_sub(height))
+            .ok_or(Error::OutOfBounds)?;
+        display.set_scroll_area(memory_top, bottom)?;
+        let mut region = ScrollRegion {
+            top: top_fixed,
+            memory_top,
+            height,
+            next: 0,
+            color,
+            background,
+            ansi: Parser::new(),
+        };
+        region.clear(display)?;
+        Ok(region)
+    }
+
+    /// Appends a line of text at the bottom of the region, scrolling older lines up. A `\t` moves
+    /// to the next tab stop, which are every four characters. ANSI color sequences change the
+    /// colors of the following text, also on later lines, while other escape sequences are
+    /// skipped.
+    pub fn push_line<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        text: &str,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let y = self.top + self.next;
+        let width = display.width();
+        let background = self.background;
+
+        let mut x = 0;
+        for c in text.chars() {
+            let c = match self.ansi.feed(c) {
+                Some(Action::Print(c)) => c,
+                _ => continue,
+            };
+            if x + CHARACTER_ADVANCE > width {
+                break;
+            }
+
+            let (foreground, ansi_background) = self.ansi.style();
+            let (color, cell) = (foreground.unwrap_or(self.color), ansi_background.unwrap_or(background));
+            if c == '\t' {
+                let stop = CHARACTER_ADVANCE * DEFAULT_TAB_WIDTH;
+                let next = ((x / stop + 1) * stop).min(width);
+                display.fill_region(&Rect::new(x, y, next - 1, y + LINE_ADVANCE - 1), |_, _| cell)?;
+                x = next;
+            } else if !c.is_control() {
+                display.draw_glyph::<Font57>(c, x, y, 1, &color, Some(&cell))?;
+                x += CHARACTER_ADVANCE;
+            }
+        }
+
+        // Erase what is left of the line that scrolled out of view.
+        if x < width {
+            display.fill_region(&Rect::new(x, y, width - 1, y + LINE_ADVANCE - 1), |_, _| background)?;
+        }
+
+        self.next = (self.next + LINE_ADVANCE) % self.height;
+        display.set_scroll_start(self.memory_top + self.next)
+    }
+
+    /// Clears the region and resets the scroll position.
+    pub fn clear<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let background = self.background;
+        let area = Rect::new(0, self.top, display.width() - 1, self.top + self.height - 1);
+        display.fill_region(&area, |_, _| background)?;
+        self.next = 0;
+        display.set_scroll_start(self.memory_top)
+    }
+}
+
+/// Returns the display memory line the first line of the panel in portrait orientation is stored
+/// in, taking the offsets of the controller and the panel into account.
+fn first_memory_line<SPI, PIN, DELAY, C>(display: &ST7734<SPI, PIN, DELAY, C>) -> u16
+where
+    SPI: spi::Write<u8>,
+    PIN: OutputPin,
+    DELAY: DelayMs<u64>,
+    C: Controller,
+{
+    let (column, row, _, _) = display.memory_window(0, 0, 0, 0);
+    match display.orientation {
+        Orientation::Portrait | Orientation::PortraitSwapped => row,
+        Orientation::Landscape | Orientation::LandScapeSwapped => column,
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::color::DefaultColor;
+    use crate::command::Instruction;
+    use crate::config::{Config, Variant};
+    use crate::controller::Chip;
+    use crate::testing::{NoDelay, Panel};
+
+    use alloc::vec;
+
+    #[test]
+    fn scroll_area_includes_the_row_offset_of_the_panel() {
+        let white = Color::from_default(DefaultColor::White);
+        let black = Color::from_default(DefaultColor::Black);
+
+        // The 80x160 panels show the memory from row 1 on.
+        let panel = Panel::new(132, 162);
+        let mut display = ST7734::with_controller_spi(Chip::ST7735S, panel.spi(), panel.dc(), NoDelay);
+        let mut region = ScrollRegion::new(&mut display, 16, 0, white, black).unwrap();
+        assert_eq!(panel.arguments(Instruction::VSCRDEF), Some(vec![0, 17, 0, 144, 0, 1]));
+        assert_eq!(panel.arguments(Instruction::VSCRSADD), Some(vec![0, 17]));
+        region.push_line(&mut display, "booting...").unwrap();
+        assert_eq!(panel.arguments(Instruction::VSCRSADD), Some(vec![0, 25]));
+
+        // The 128x128 panels show the memory from row 3 on.
+        let panel = Panel::new(132, 162);
+        let mut display = panel.display();
+        display.apply_config(&Config::new(Variant::GreenTab128x128)).unwrap();
+        ScrollRegion::new(&mut display, 16, 0, white, black).unwrap();
+        assert_eq!(panel.arguments(Instruction::VSCRDEF), Some(vec![0, 19, 0, 112, 0, 31]));
+        assert_eq!(panel.arguments(Instruction::VSCRSADD), Some(vec![0, 19]));
+    }
+}
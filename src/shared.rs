@@ -0,0 +1,99 @@
+//! Display handle shared between modules and interrupt handlers.
+//!
+//! This module is available with the `critical-section` feature. A
+//! [`SharedST7734`](struct.SharedST7734.html) can be stored in a `static` and drawn to from
+//! anywhere; each access runs in a critical section, so it can't be interrupted by another
+//! access. Keep the drawing done in a single access short, as interrupts are disabled meanwhile
+//! on single-core MCUs.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! static DISPLAY: SharedST7734<Spi, Pin, Delay> = SharedST7734::new();
+//!
+//! DISPLAY.init(ST7734::new_with_spi(spi, dc, delay));
+//!
+//! // From an interrupt handler:
+//! DISPLAY.lock(|display| display.draw_pixel(10, 10, &Color::from_default(DefaultColor::Red)));
+//! ```
+
+use crate::controller::{Controller, ST7735};
+use crate::error::DisplayError;
+use crate::queue::DrawOp;
+use crate::ST7734;
+
+use core::cell::RefCell;
+use critical_section::Mutex;
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Slot holding the driver of a `SharedST7734`.
+type Slot<SPI, PIN, DELAY, C> = Mutex<RefCell<Option<ST7734<SPI, PIN, DELAY, C>>>>;
+
+/// Driver protected by a critical section, usable from several contexts.
+pub struct SharedST7734<SPI, PIN, DELAY, C = ST7735> {
+    /// Driver, once it was set with `init`.
+    display: Slot<SPI, PIN, DELAY, C>,
+}
+
+impl<SPI, PIN, DELAY, C> SharedST7734<SPI, PIN, DELAY, C> {
+    /// Creates an empty handle. Usable in `static` items.
+    pub const fn new() -> SharedST7734<SPI, PIN, DELAY, C> {
+        SharedST7734 {
+            display: Mutex::new(RefCell::new(None)),
+        }
+    }
+
+    /// Stores the driver in the handle, replacing any previous one. If called from within `lock`,
+    /// the driver can't be stored and is returned again, otherwise `None` is returned.
+    pub fn init(&self, display: ST7734<SPI, PIN, DELAY, C>) -> Option<ST7734<SPI, PIN, DELAY, C>> {
+        critical_section::with(|cs| match self.display.borrow(cs).try_borrow_mut() {
+            Ok(mut slot) => {
+                *slot = Some(display);
+                None
+            }
+            Err(_) => Some(display),
+        })
+    }
+
+    /// Removes the driver from the handle and returns it. Returns `None` if no driver was stored,
+    /// or if called from within `lock`.
+    pub fn take(&self) -> Option<ST7734<SPI, PIN, DELAY, C>> {
+        critical_section::with(|cs| match self.display.borrow(cs).try_borrow_mut() {
+            Ok(mut slot) => slot.take(),
+            Err(_) => None,
+        })
+    }
+
+    /// Calls `f` with exclusive access to the driver inside a critical section and returns its
+    /// result. Returns `None` if no driver was stored yet, or if called from within `f`.
+    pub fn lock<F, R>(&self, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut ST7734<SPI, PIN, DELAY, C>) -> R,
+    {
+        critical_section::with(|cs| match self.display.borrow(cs).try_borrow_mut() {
+            Ok(mut display) => display.as_mut().map(f),
+            Err(_) => None,
+        })
+    }
+}
+
+impl<SPI, PIN, DELAY, C> SharedST7734<SPI, PIN, DELAY, C>
+where
+    SPI: spi::Write<u8>,
+    PIN: OutputPin,
+    DELAY: DelayMs<u64>,
+    C: Controller,
+{
+    /// Performs a draw operation. Returns `None` if the driver was not available.
+    pub fn draw(&self, op: &DrawOp) -> Option<Result<(), DisplayError<SPI, PIN>>> {
+        self.lock(|display| op.execute(display))
+    }
+}
+
+impl<SPI, PIN, DELAY, C> Default for SharedST7734<SPI, PIN, DELAY, C> {
+    fn default() -> SharedST7734<SPI, PIN, DELAY, C> {
+        SharedST7734::new()
+    }
+}
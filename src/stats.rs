@@ -0,0 +1,137 @@
+/// Counters describing the traffic the driver generated on the bus.
+///
+/// Counters accumulate until they are reset, so resetting them once per frame gives the cost of
+/// each frame.
+///
+/// # Examples
+///
+/// ```ignore
+/// display.reset_stats();
+/// draw_frame(&mut display);
+/// let stats = display.stats();
+/// println!("{} pixels in {} transactions", stats.pixels_written, stats.transactions);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct Stats {
+    /// Number of pixels written to display memory.
+    pub pixels_written: u64,
+
+    /// Number of bytes transmitted, including commands and their arguments.
+    pub bytes_transmitted: u64,
+
+    /// Number of separate bus writes.
+    pub transactions: u64,
+
+    /// Number of times the address window was set.
+    pub address_windows: u64,
+}
+
+/// Statistics about the time it takes to transfer frames to the display.
+///
+/// Durations are given in microseconds and measured with a timestamp source provided by the
+/// application, e.g. a hardware timer or `std::time::Instant`.
+///
+/// # Examples
+///
+/// ```ignore
+/// let start = Instant::now();
+/// loop {
+///     draw_frame(&mut frame);
+///     frame.flush_timed(&mut display, || start.elapsed().as_micros() as u64);
+///
+///     let times = frame.frame_times();
+///     println!("{} us avg, {} us max, {:.1} fps", times.average(), times.max(), times.fps());
+/// }
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct FrameTimes {
+    /// Number of recorded frames.
+    count: u32,
+
+    /// Sum of all recorded durations.
+    total: u64,
+
+    /// Shortest recorded duration.
+    min: u64,
+
+    /// Longest recorded duration.
+    max: u64,
+
+    /// Most recently recorded duration.
+    last: u64,
+}
+
+impl FrameTimes {
+    /// Creates empty statistics.
+    pub fn new() -> FrameTimes {
+        FrameTimes::default()
+    }
+
+    /// Records the duration of a single frame.
+    pub fn record(&mut self, duration: u64) {
+        if self.count == 0 || duration < self.min {
+            self.min = duration;
+        }
+        if duration > self.max {
+            self.max = duration;
+        }
+
+        self.count += 1;
+        self.total += duration;
+        self.last = duration;
+    }
+
+    /// Runs `f` and records the time between the timestamps returned by `now` before and after.
+    pub fn measure<C, F, R>(&mut self, mut now: C, f: F) -> R
+    where
+        C: FnMut() -> u64,
+        F: FnOnce() -> R,
+    {
+        let start = now();
+        let result = f();
+        self.record(now().saturating_sub(start));
+        result
+    }
+
+    /// Number of recorded frames.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Shortest frame time, `0` if no frames were recorded.
+    pub fn min(&self) -> u64 {
+        self.min
+    }
+
+    /// Longest frame time.
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Most recent frame time.
+    pub fn last(&self) -> u64 {
+        self.last
+    }
+
+    /// Average frame time, `0` if no frames were recorded.
+    pub fn average(&self) -> u64 {
+        if self.count == 0 {
+            0
+        } else {
+            self.total / self.count as u64
+        }
+    }
+
+    /// Estimated frames per second based on the average frame time.
+    pub fn fps(&self) -> f32 {
+        match self.average() {
+            0 => 0.0,
+            average => 1_000_000.0 / average as f32,
+        }
+    }
+
+    /// Discards all recorded frames.
+    pub fn reset(&mut self) {
+        *self = FrameTimes::default();
+    }
+}
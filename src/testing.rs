@@ -0,0 +1,411 @@
+//! Snapshot testing of drawing code against reference images.
+//!
+//! This module is available with the `std` feature. A [`Panel`](struct.Panel.html) emulates the
+//! display memory: it provides the SPI bus and data/command pin for a driver and decodes the
+//! address window and memory write commands sent to them into pixels. The rendered pixels are
+//! compared against a reference image stored as PPM with
+//! [`assert_golden`](fn.assert_golden.html), so user interfaces can be put under regression
+//! tests without hardware.
+//!
+//! Running the tests with the environment variable `UPDATE_GOLDEN` set writes the rendered
+//! images as new references instead of comparing them.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! #[test]
+//! fn status_screen() {
+//!     let panel = Panel::new(128, 160);
+//!     let mut display = panel.display();
+//!     draw_status_screen(&mut display);
+//!     st7735::testing::assert_golden(&panel.pixels().as_image(), "tests/golden/status.ppm");
+//! }
+//! ```
+
+use crate::color::Color;
+use crate::command::Instruction;
+use crate::framebuffer::rgb::Framebuffer;
+use crate::geometry::Rect;
+use crate::image::Image;
+use crate::screenshot::write_ppm;
+use crate::ST7734;
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+use std::cell::RefCell;
+use std::convert::Infallible;
+use std::env;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+/// Emulated display memory shared by the bus and pin of a panel.
+struct PanelState {
+    /// Pixels in display coordinates.
+    pixels: Framebuffer,
+
+    /// Level of the data/command pin.
+    data: bool,
+
+    /// Last instruction received.
+    instruction: Option<u8>,
+
+    /// Arguments received for the last instruction.
+    arguments: Vec<u8>,
+
+    /// Address window set with CASET and RASET.
+    window: Rect,
+
+    /// Position of the next pixel written.
+    cursor: (u16, u16),
+
+    /// Instructions other than RAMWR with their arguments, in the order they were received.
+    commands: Vec<(u8, Vec<u8>)>,
+}
+
+impl PanelState {
+    /// Processes a byte sent over the bus.
+    fn receive(&mut self, byte: u8) {
+        let ramwr = num::ToPrimitive::to_u8(&Instruction::RAMWR).unwrap();
+        if !self.data {
+            self.instruction = Some(byte);
+            self.arguments.clear();
+            if byte == ramwr {
+                self.cursor = (self.window.x0, self.window.y0);
+            } else {
+                self.commands.push((byte, Vec::new()));
+            }
+            return;
+        }
+
+        self.arguments.push(byte);
+        if self.instruction != Some(ramwr) {
+            if let Some((_, arguments)) = self.commands.last_mut() {
+                arguments.push(byte);
+            }
+        }
+        let caset = num::ToPrimitive::to_u8(&Instruction::CASET).unwrap();
+        let raset = num::ToPrimitive::to_u8(&Instruction::RASET).unwrap();
+        match self.instruction {
+            Some(instruction) if instruction == caset && self.arguments.len() == 4 => {
+                let (start, end) = self.range();
+                self.window = Rect::new(start, self.window.y0, end, self.window.y1);
+            }
+            Some(instruction) if instruction == raset && self.arguments.len() == 4 => {
+                let (start, end) = self.range();
+                self.window = Rect::new(self.window.x0, start, self.window.x1, end);
+            }
+            Some(instruction) if instruction == ramwr && self.arguments.len() == 2 => {
+                let color = Color::from_hex(u16::from_be_bytes([self.arguments[0], self.arguments[1]]));
+                self.arguments.clear();
+                let (x, y) = self.cursor;
+                if x < self.pixels.width() && y < self.pixels.height() {
+                    self.pixels.set_pixel(x, y, &color);
+                }
+                self.cursor = if x >= self.window.x1 {
+                    (self.window.x0, if y >= self.window.y1 { self.window.y0 } else { y + 1 })
+                } else {
+                    (x + 1, y)
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the start and end address of a CASET or RASET command.
+    fn range(&self) -> (u16, u16) {
+        let arguments = &self.arguments;
+        (
+            u16::from_be_bytes([arguments[0], arguments[1]]),
+            u16::from_be_bytes([arguments[2], arguments[3]]),
+        )
+    }
+}
+
+/// Emulated display recording the pixels written by a driver.
+///
+/// Pixels are recorded at the coordinates the driver addresses, i.e. in display coordinates of
+/// the current orientation. Only RGB565 in big endian byte order is decoded.
+#[derive(Clone)]
+pub struct Panel {
+    /// Shared emulated display memory.
+    state: Rc<RefCell<PanelState>>,
+}
+
+impl Panel {
+    /// Creates a black panel of `width` x `height` pixels.
+    pub fn new(width: u16, height: u16) -> Panel {
+        Panel {
+            state: Rc::new(RefCell::new(PanelState {
+                pixels: Framebuffer::new(width, height, Color::from_hex(0)),
+                data: false,
+                instruction: None,
+                arguments: Vec::new(),
+                window: Rect::new(0, 0, width - 1, height - 1),
+                cursor: (0, 0),
+                commands: Vec::new(),
+            })),
+        }
+    }
+
+    /// Returns the SPI bus of the panel.
+    pub fn spi(&self) -> PanelSpi {
+        PanelSpi {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Returns the data/command pin of the panel.
+    pub fn dc(&self) -> PanelPin {
+        PanelPin {
+            state: Some(self.state.clone()),
+        }
+    }
+
+    /// Creates a driver connected to the panel.
+    pub fn display(&self) -> ST7734<PanelSpi, PanelPin, NoDelay> {
+        ST7734::new_with_spi(self.spi(), self.dc(), NoDelay)
+    }
+
+    /// Returns a copy of the recorded pixels.
+    pub fn pixels(&self) -> Framebuffer {
+        let state = self.state.borrow();
+        let mut pixels = Framebuffer::new(state.pixels.width(), state.pixels.height(), Color::from_hex(0));
+        pixels.draw_image(0, 0, &state.pixels.as_image());
+        pixels
+    }
+
+    /// Returns the arguments sent with the last `instruction`, or `None` if it wasn't sent.
+    /// Memory writes with RAMWR aren't recorded.
+    pub fn arguments(&self, instruction: Instruction) -> Option<Vec<u8>> {
+        let instruction = num::ToPrimitive::to_u8(&instruction).unwrap();
+        let state = self.state.borrow();
+        state
+            .commands
+            .iter()
+            .rev()
+            .find(|&&(received, _)| received == instruction)
+            .map(|(_, arguments)| arguments.clone())
+    }
+}
+
+/// SPI bus of a `Panel`.
+pub struct PanelSpi {
+    /// Shared emulated display memory.
+    state: Rc<RefCell<PanelState>>,
+}
+
+impl spi::Write<u8> for PanelSpi {
+    type Error = Infallible;
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Infallible> {
+        let mut state = self.state.borrow_mut();
+        for &byte in words {
+            state.receive(byte);
+        }
+        Ok(())
+    }
+}
+
+/// Data/command pin of a `Panel`.
+pub struct PanelPin {
+    /// Shared emulated display memory, if the pin is connected.
+    state: Option<Rc<RefCell<PanelState>>>,
+}
+
+impl PanelPin {
+    /// Returns a pin that is not connected to a panel, e.g. for the reset line.
+    pub fn unconnected() -> PanelPin {
+        PanelPin { state: None }
+    }
+}
+
+impl OutputPin for PanelPin {
+    type Error = Infallible;
+
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        if let Some(ref state) = self.state {
+            state.borrow_mut().data = false;
+        }
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        if let Some(ref state) = self.state {
+            state.borrow_mut().data = true;
+        }
+        Ok(())
+    }
+}
+
+/// Delay provider returning immediately.
+pub struct NoDelay;
+
+impl DelayMs<u64> for NoDelay {
+    fn delay_ms(&mut self, _ms: u64) {}
+}
+
+/// Difference between a rendered image and its reference.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Mismatch {
+    /// The reference could not be read.
+    Unreadable(String),
+
+    /// The images differ in size.
+    Size {
+        expected: (u16, u16),
+        actual: (u16, u16),
+    },
+
+    /// Some pixels differ.
+    Pixels {
+        /// Number of differing pixels.
+        count: usize,
+
+        /// Smallest area containing all differing pixels.
+        bounds: Rect,
+    },
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Mismatch::Unreadable(ref error) => write!(f, "reference image unreadable: {}", error),
+            Mismatch::Size { expected, actual } => write!(
+                f,
+                "size differs: expected {}x{}, got {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            Mismatch::Pixels { count, bounds } => write!(
+                f,
+                "{} pixels differ within ({}, {})-({}, {})",
+                count, bounds.x0, bounds.y0, bounds.x1, bounds.y1
+            ),
+        }
+    }
+}
+
+/// Compares `image` against `reference` and returns the difference, if any, together with an
+/// image marking differing pixels in red over a dimmed copy of `image`.
+pub fn compare(image: &Image, reference: &Image) -> Result<(), (Mismatch, Framebuffer)> {
+    if (image.width(), image.height()) != (reference.width(), reference.height()) {
+        let mismatch = Mismatch::Size {
+            expected: (reference.width(), reference.height()),
+            actual: (image.width(), image.height()),
+        };
+        let mut diff = Framebuffer::new(image.width(), image.height(), Color::from_hex(0));
+        diff.draw_image(0, 0, image);
+        return Err((mismatch, diff));
+    }
+
+    let black = Color::from_hex(0);
+    let red = Color::from_rgb(31, 0, 0);
+    let mut diff = Framebuffer::new(image.width(), image.height(), Color::from_hex(0));
+    let mut count = 0;
+    let mut bounds: Option<Rect> = None;
+    for y in 0..image.height() {
+        for x in 0..image.width() {
+            let pixel = image.pixel(x, y);
+            if pixel == reference.pixel(x, y) {
+                diff.set_pixel(x, y, &pixel.blend(&black, 192));
+            } else {
+                diff.set_pixel(x, y, &red);
+                count += 1;
+                let point = Rect::new(x, y, x, y);
+                bounds = Some(bounds.map_or(point, |bounds| bounds.union(&point)));
+            }
+        }
+    }
+
+    match bounds {
+        Some(bounds) => Err((Mismatch::Pixels { count, bounds }, diff)),
+        None => Ok(()),
+    }
+}
+
+/// Compares `image` against the reference PPM image at `path`.
+///
+/// If the environment variable `UPDATE_GOLDEN` is set, `image` is written to `path` instead.
+///
+/// # Panics
+///
+/// Panics with a report if the images differ or the reference can't be read. An image marking
+/// the differing pixels is written next to the reference with the extension `diff.ppm`.
+pub fn assert_golden<P: AsRef<Path>>(image: &Image, path: P) {
+    let path = path.as_ref();
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("error creating reference directory");
+        }
+        write_ppm(BufWriter::new(File::create(path).expect("error creating reference")), image)
+            .expect("error writing reference");
+        return;
+    }
+
+    let reference = match read_ppm(path) {
+        Ok(reference) => reference,
+        Err(error) => panic!(
+            "{}: {} (set UPDATE_GOLDEN to create it)",
+            path.display(),
+            Mismatch::Unreadable(error.to_string())
+        ),
+    };
+
+    if let Err((mismatch, diff)) = compare(image, &reference.as_image()) {
+        let diff_path: PathBuf = path.with_extension("diff.ppm");
+        let _ = File::create(&diff_path).and_then(|file| write_ppm(BufWriter::new(file), &diff.as_image()));
+        panic!("{}: {}, see {}", path.display(), mismatch, diff_path.display());
+    }
+}
+
+/// Reads a binary PPM image with 8 bits per color component.
+pub fn read_ppm<P: AsRef<Path>>(path: P) -> io::Result<Framebuffer> {
+    let data = fs::read(path)?;
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, String::from(message));
+
+    // Header fields are separated by whitespace and may be followed by comments.
+    let mut fields = Vec::new();
+    let mut position = 0;
+    while fields.len() < 4 {
+        while position < data.len() && (data[position] as char).is_ascii_whitespace() {
+            position += 1;
+        }
+        if position < data.len() && data[position] == b'#' {
+            while position < data.len() && data[position] != b'\n' {
+                position += 1;
+            }
+            continue;
+        }
+        let start = position;
+        while position < data.len() && !(data[position] as char).is_ascii_whitespace() {
+            position += 1;
+        }
+        if start == position {
+            return Err(invalid("truncated header"));
+        }
+        fields.push(String::from_utf8_lossy(&data[start..position]).into_owned());
+    }
+    position += 1;
+
+    if fields[0] != "P6" || fields[3] != "255" {
+        return Err(invalid("not an 8-bit binary PPM image"));
+    }
+    let width: u16 = fields[1].parse().map_err(|_| invalid("invalid width"))?;
+    let height: u16 = fields[2].parse().map_err(|_| invalid("invalid height"))?;
+    let pixels = data.get(position..).unwrap_or(&[]);
+    if width == 0 || height == 0 || pixels.len() < 3 * width as usize * height as usize {
+        return Err(invalid("truncated pixel data"));
+    }
+
+    let mut image = Framebuffer::new(width, height, Color::from_hex(0));
+    for (index, rgb) in pixels.chunks(3).take(width as usize * height as usize).enumerate() {
+        let (x, y) = ((index % width as usize) as u16, (index / width as usize) as u16);
+        image.set_pixel(x, y, &Color::from_rgb888(rgb[0], rgb[1], rgb[2]));
+    }
+    Ok(image)
+}
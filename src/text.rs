@@ -0,0 +1,533 @@
+use crate::ansi::{Action, Erase, Parser};
+use crate::color::{Color, DefaultColor};
+use crate::fonts::{Font, CHARACTER_ADVANCE, LINE_ADVANCE};
+use crate::controller::Controller;
+use crate::error::DisplayError;
+use crate::geometry::Rect;
+use crate::ST7734;
+
+use core::fmt;
+use core::marker::PhantomData;
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Distance between two tab stops in characters unless changed with `with_tab_width`.
+pub(crate) const DEFAULT_TAB_WIDTH: u16 = 4;
+
+/// Writes formatted text to the display, starting at a fixed position.
+///
+/// Characters are laid out left to right with their top left corner at the current position.
+/// A `\n` moves to the start of the next line and a `\t` to the next tab stop, which are every
+/// four characters unless changed with `with_tab_width`.
+///
+/// ANSI escape sequences for colors, cursor positioning and erasing are interpreted, see the
+/// `ansi` module. Cursor positions are relative to the starting position of the writer, and
+/// the default colors are the ones set on the writer.
+///
+/// `fmt::Write` only reports that writing failed. If drawing fails, the error is kept and can be
+/// retrieved with `take_error`.
+///
+/// # Examples
+///
+/// ```ignore
+/// use core::fmt::Write;
+///
+/// let temperature = 21;
+/// write!(display.text_at(0, 0), "T = {}°C", temperature).unwrap();
+///
+/// let red = Color::from_default(DefaultColor::Red);
+/// writeln!(display.text_at(0, 10).with_color(red), "Error").unwrap();
+/// ```
+pub struct TextWriter<'a, SPI, PIN, DELAY, C, F>
+where
+    SPI: spi::Write<u8>,
+    PIN: OutputPin,
+{
+    /// Display the text is written to.
+    display: &'a mut ST7734<SPI, PIN, DELAY, C>,
+
+    /// Horizontal position lines start at.
+    x_start: u16,
+
+    /// Vertical position of the first line.
+    y_start: u16,
+
+    /// Horizontal position of the next character.
+    x: u16,
+
+    /// Vertical position of the current line.
+    y: u16,
+
+    /// Text color.
+    color: Color,
+
+    /// Color filling the cell behind each character. Transparent if `None`.
+    background: Option<Color>,
+
+    /// Factor each pixel of a glyph is enlarged by.
+    scale: u16,
+
+    /// Distance between two lines in pixels. Derived from the scale if `None`.
+    line_height: Option<u16>,
+
+    /// Distance between two tab stops in characters.
+    tab_width: u16,
+
+    /// Parser for ANSI escape sequences, which holds the colors they selected.
+    ansi: Parser,
+
+    /// Font used to draw characters.
+    font: PhantomData<F>,
+
+    /// Error that made the last write fail.
+    error: Option<DisplayError<SPI, PIN>>,
+}
+
+impl<'a, SPI, PIN, DELAY, C, F> TextWriter<'a, SPI, PIN, DELAY, C, F>
+where
+    SPI: spi::Write<u8>,
+    PIN: OutputPin,
+    DELAY: DelayMs<u64>,
+    C: Controller,
+    F: Font,
+{
+    /// Creates a writer starting at the provided coordinates.
+    pub(crate) fn new(display: &'a mut ST7734<SPI, PIN, DELAY, C>, x: u16, y: u16, color: Color) -> Self {
+        TextWriter {
+            display,
+            x_start: x,
+            y_start: y,
+            x,
+            y,
+            color,
+            background: None,
+            scale: 1,
+            line_height: None,
+            tab_width: DEFAULT_TAB_WIDTH,
+            ansi: Parser::new(),
+            font: PhantomData,
+            error: None,
+        }
+    }
+
+    /// Changes the text color.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Fills the cell behind each character with `background`, which erases previous text.
+    pub fn with_background(mut self, background: Color) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    /// Enlarges each pixel of a glyph to `scale` x `scale` pixels. Use `fonts::text_width` and
+    /// `fonts::text_height` to measure the text beforehand.
+    pub fn with_scale(mut self, scale: u16) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Changes the distance between the tops of two consecutive lines to `pixels`. Values
+    /// smaller than the glyph height make lines overlap.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use core::fmt::Write;
+    ///
+    /// write!(display.text_at(0, 0).with_line_height(10), "line 1\nline 2").unwrap();
+    /// ```
+    pub fn with_line_height(mut self, pixels: u16) -> Self {
+        self.line_height = Some(pixels);
+        self
+    }
+
+    /// Places tab stops every `characters` characters. A width of 0 is treated as 1.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use core::fmt::Write;
+    ///
+    /// let mut writer = display.text_at(0, 0).with_tab_width(8);
+    /// writeln!(writer, "name\tvalue").unwrap();
+    /// writeln!(writer, "rpm\t{}", rpm).unwrap();
+    /// ```
+    pub fn with_tab_width(mut self, characters: u16) -> Self {
+        self.tab_width = characters.max(1);
+        self
+    }
+
+    /// Changes the font.
+    pub fn with_font<G: Font>(self, _font: G) -> TextWriter<'a, SPI, PIN, DELAY, C, G> {
+        TextWriter {
+            display: self.display,
+            x_start: self.x_start,
+            y_start: self.y_start,
+            x: self.x,
+            y: self.y,
+            color: self.color,
+            background: self.background,
+            scale: self.scale,
+            line_height: self.line_height,
+            tab_width: self.tab_width,
+            ansi: self.ansi,
+            font: PhantomData,
+            error: self.error,
+        }
+    }
+
+    /// Returns the position the next character will be drawn at.
+    pub fn position(&self) -> (u16, u16) {
+        (self.x, self.y)
+    }
+
+    /// Returns the error that made the last write fail, if any.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut writer = display.text_at(0, 0);
+    /// if write!(writer, "{} rpm", speed).is_err() {
+    ///     let error = writer.take_error();
+    /// }
+    /// ```
+    pub fn take_error(&mut self) -> Option<DisplayError<SPI, PIN>> {
+        self.error.take()
+    }
+
+    /// Returns the current text color and background, taking ANSI colors into account.
+    fn colors(&self) -> (Color, Option<Color>) {
+        let (foreground, background) = self.ansi.style();
+        (foreground.unwrap_or(self.color), background.or(self.background))
+    }
+
+    /// Returns the size of a character cell and the distance between two lines.
+    fn cell(&self) -> (u16, u16, u16) {
+        let height = LINE_ADVANCE.saturating_mul(self.scale);
+        (CHARACTER_ADVANCE.saturating_mul(self.scale), height, self.line_height.unwrap_or(height))
+    }
+
+    /// Fills an area with the background, or black if the background is transparent. The area
+    /// is clipped to the display and nothing is erased if it starts right of or below its end.
+    fn erase(&mut self, x0: u16, y0: u16, x1: u16, y1: u16) -> Result<(), DisplayError<SPI, PIN>> {
+        if x0 > x1 || y0 > y1 {
+            return Ok(());
+        }
+        let background = self.colors().1.unwrap_or(Color::from_default(DefaultColor::Black));
+        match Rect::new(x0, y0, x1, y1).intersection(&self.display.bounds()) {
+            Some(area) => self.display.draw_filled_rect(area.x0, area.y0, area.x1, area.y1, &background),
+            None => Ok(()),
+        }
+    }
+
+    /// Executes an action of the ANSI parser.
+    fn apply(&mut self, action: Action) -> Result<(), DisplayError<SPI, PIN>> {
+        let (advance, height, line) = self.cell();
+        let bounds = self.display.bounds();
+        let (right, bottom) = (bounds.x1, bounds.y1);
+        let (x, y) = (self.x, self.y);
+        let (cell_right, line_bottom) = (x.saturating_add(advance - 1), y.saturating_add(height - 1));
+        match action {
+            Action::Print(c) => return self.put(c),
+            Action::Style { .. } => {}
+            Action::MoveTo { row, column } => {
+                self.x = self.x_start.saturating_add(column.saturating_mul(advance));
+                self.y = self.y_start.saturating_add(row.saturating_mul(line));
+            }
+            Action::MoveBy { rows, columns } => {
+                let x = self.x as i32 + columns as i32 * advance as i32;
+                let y = self.y as i32 + rows as i32 * line as i32;
+                self.x = x.max(self.x_start as i32).min(u16::MAX as i32) as u16;
+                self.y = y.max(self.y_start as i32).min(u16::MAX as i32) as u16;
+            }
+            Action::EraseLine(erase) => {
+                match erase {
+                    Erase::ToEnd => self.erase(x, y, right, line_bottom)?,
+                    Erase::ToStart => self.erase(self.x_start, y, cell_right, line_bottom)?,
+                    Erase::All => self.erase(self.x_start, y, right, line_bottom)?,
+                }
+            }
+            Action::EraseScreen(erase) => {
+                match erase {
+                    Erase::ToEnd => {
+                        self.erase(x, y, right, line_bottom)?;
+                        if line_bottom < bottom {
+                            self.erase(self.x_start, line_bottom + 1, right, bottom)?;
+                        }
+                    }
+                    Erase::ToStart => {
+                        if y > self.y_start {
+                            self.erase(self.x_start, self.y_start, right, y - 1)?;
+                        }
+                        self.erase(self.x_start, y, cell_right, line_bottom)?;
+                    }
+                    Erase::All => self.erase(self.x_start, self.y_start, right, bottom)?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws a character or handles a control character.
+    fn put(&mut self, c: char) -> Result<(), DisplayError<SPI, PIN>> {
+        let (advance, height, line) = self.cell();
+        let (color, background) = self.colors();
+        match c {
+            '\n' => {
+                self.x = self.x_start;
+                self.y = self.y.saturating_add(line);
+            }
+            '\r' => self.x = self.x_start,
+            '\t' => {
+                // Move to the next tab stop, filling the skipped cells if a background is set.
+                let column = (self.x - self.x_start) / advance;
+                let stop = (column / self.tab_width + 1) * self.tab_width;
+                let x = self.x_start.saturating_add(stop.saturating_mul(advance));
+                if let Some(background) = background {
+                    let bottom = self.y.saturating_add(height - 1);
+                    self.display.draw_filled_rect(self.x, self.y, x - 1, bottom, &background)?;
+                }
+                self.x = x;
+            }
+            // Remaining control characters have no glyph.
+            c if c.is_control() => {}
+            _ => {
+                let (x, y, scale) = (self.x, self.y, self.scale);
+                self.display.draw_glyph::<F>(c, x, y, scale, &color, background.as_ref())?;
+                self.x = self.x.saturating_add(advance);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, SPI, PIN, DELAY, C, F> fmt::Write for TextWriter<'a, SPI, PIN, DELAY, C, F>
+where
+    SPI: spi::Write<u8>,
+    PIN: OutputPin,
+    DELAY: DelayMs<u64>,
+    C: Controller,
+    F: Font,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if let Some(action) = self.ansi.feed(c) {
+                if let Err(error) = self.apply(action) {
+                    self.error = Some(error);
+                    return Err(fmt::Error);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Blinking text cursor for input fields.
+///
+/// The caret is a vertical bar drawn in the column left of a character cell, which is the gap
+/// between two characters of the built-in font. Drawing and erasing it therefore leaves the text
+/// around it untouched. Its position is tracked in character cells relative to the origin of the
+/// text, using the same layout as `TextWriter`.
+///
+/// # Examples
+///
+/// ```ignore
+/// let white = Color::from_default(DefaultColor::White);
+/// let black = Color::from_default(DefaultColor::Black);
+/// let mut caret = Caret::new(10, 20, white, black);
+///
+/// loop {
+///     if let Some(column) = input.poll() {
+///         caret.move_to(&mut display, column, 0);
+///     }
+///     caret.tick(&mut display);
+///     delay.delay_ms(500);
+/// }
+/// ```
+pub struct Caret {
+    /// Position of the first character cell.
+    origin: (u16, u16),
+
+    /// Current column and row.
+    cell: (u16, u16),
+
+    /// Color of the bar.
+    color: Color,
+
+    /// Color restored when the caret is hidden.
+    background: Color,
+
+    /// Whether the caret is currently drawn.
+    visible: bool,
+}
+
+impl Caret {
+    /// Creates a hidden caret in the first cell of text starting at the provided coordinates.
+    pub fn new(x: u16, y: u16, color: Color, background: Color) -> Caret {
+        Caret {
+            origin: (x, y),
+            cell: (0, 0),
+            color,
+            background,
+            visible: false,
+        }
+    }
+
+    /// Returns the current column and row.
+    pub fn cell(&self) -> (u16, u16) {
+        self.cell
+    }
+
+    /// Returns the top left corner of the current character cell in pixels.
+    pub fn position(&self) -> (u16, u16) {
+        (
+            self.origin.0.saturating_add(self.cell.0.saturating_mul(CHARACTER_ADVANCE)),
+            self.origin.1.saturating_add(self.cell.1.saturating_mul(LINE_ADVANCE)),
+        )
+    }
+
+    /// Returns `true` if the caret is currently drawn.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Toggles the caret. Call periodically to make it blink.
+    pub fn tick<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        if self.visible {
+            self.hide(display)
+        } else {
+            self.show(display)
+        }
+    }
+
+    /// Draws the caret.
+    pub fn show<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let color = self.color;
+        self.draw(display, &color)?;
+        self.visible = true;
+        Ok(())
+    }
+
+    /// Erases the caret with the background color.
+    pub fn hide<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let background = self.background;
+        self.draw(display, &background)?;
+        self.visible = false;
+        Ok(())
+    }
+
+    /// Moves the caret to the given column and row. It is shown at the new position right away,
+    /// so it doesn't disappear while the user is typing.
+    pub fn move_to<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        column: u16,
+        row: u16,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        if self.visible {
+            self.hide(display)?;
+        }
+        self.cell = (column, row);
+        self.show(display)
+    }
+
+    /// Draws the bar in the given color.
+    fn draw<SPI, PIN, DELAY, C>(
+        &self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let (x, y) = self.position();
+        let x = x.saturating_sub(1);
+        display.draw_filled_rect(x, y, x, y.saturating_add(LINE_ADVANCE - 1), color)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::color::{Color, DefaultColor};
+    use crate::testing::Panel;
+
+    use core::fmt::Write;
+
+    /// Fills a panel with white and writes `text` starting in its second line.
+    fn write(text: &str) -> Panel {
+        let panel = Panel::new(128, 160);
+        let mut display = panel.display();
+        display.draw_filled_rect(0, 0, 127, 159, &Color::from_default(DefaultColor::White)).unwrap();
+        write!(display.text_at(0, 8), "{}", text).unwrap();
+        panel
+    }
+
+    #[test]
+    fn erase_line_to_end_keeps_the_text_before_the_cursor() {
+        let pixels = write("ab\x1b[K").pixels();
+        let (white, black) = (Color::from_default(DefaultColor::White), Color::from_hex(0));
+        assert_eq!(pixels.pixel(12, 8), black);
+        assert_eq!(pixels.pixel(127, 15), black);
+        assert_eq!(pixels.pixel(127, 7), white);
+        assert_eq!(pixels.pixel(127, 16), white);
+    }
+
+    #[test]
+    fn erase_line_to_start_includes_the_cursor_cell() {
+        let pixels = write("ab\x1b[1K").pixels();
+        let (white, black) = (Color::from_default(DefaultColor::White), Color::from_hex(0));
+        assert!((0..18).all(|x| (8..16).all(|y| pixels.pixel(x, y) == black)));
+        assert_eq!(pixels.pixel(18, 8), white);
+        assert_eq!(pixels.pixel(0, 16), white);
+    }
+
+    #[test]
+    fn erase_screen_starts_at_the_writer_origin() {
+        let pixels = write("a\nb\x1b[2J").pixels();
+        let (white, black) = (Color::from_default(DefaultColor::White), Color::from_hex(0));
+        assert_eq!(pixels.pixel(127, 7), white);
+        assert_eq!(pixels.pixel(0, 8), black);
+        assert_eq!(pixels.pixel(127, 159), black);
+    }
+}
@@ -0,0 +1,46 @@
+//! Drawing of TGA images.
+//!
+//! This module is available with the `tga` feature. TGA files are easy to generate from scripts
+//! and can be embedded with `include_bytes!`, so assets don't need to be converted to Rust arrays.
+//! Images are decoded with [`tinytga`](https://docs.rs/tinytga) while they are drawn.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! static LOGO: &[u8] = include_bytes!("logo.tga");
+//! st7735::tga::draw_tga(&mut display, 10, 10, LOGO).expect("invalid TGA image");
+//! ```
+
+use crate::color::Color;
+use crate::controller::Controller;
+use crate::error::{DisplayError, Error};
+use crate::ST7734;
+
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_graphics_core::Pixel;
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+use tinytga::Tga;
+
+/// Draws a TGA image with its top left corner at the given coordinates. Parts of the image
+/// outside of the display are skipped.
+///
+/// Returns `Error::UnsupportedFormat` if the data is not a valid TGA image.
+pub fn draw_tga<SPI, PIN, DELAY, C>(
+    display: &mut ST7734<SPI, PIN, DELAY, C>,
+    x: u16,
+    y: u16,
+    data: &[u8],
+) -> Result<(), DisplayError<SPI, PIN>>
+where
+    SPI: spi::Write<u8>,
+    PIN: OutputPin,
+    DELAY: DelayMs<u64>,
+    C: Controller,
+{
+    let tga: Tga<Rgb565> = Tga::from_slice(data).map_err(|_| Error::UnsupportedFormat)?;
+    display.draw_pixels(tga.pixels().map(|Pixel(point, color)| {
+        (x + point.x as u16, y + point.y as u16, Color::from(color))
+    }))
+}
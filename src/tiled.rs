@@ -0,0 +1,226 @@
+//! Several panels combined into one larger drawing surface.
+//!
+//! A [`TiledDisplay`](struct.TiledDisplay.html) places `N` drivers at fixed positions of a
+//! logical surface. Drawing operations take logical coordinates; each is clipped against the
+//! panels, translated into panel coordinates and sent to every panel it touches. Parts of the
+//! surface not covered by a panel are ignored.
+//!
+//! Every panel needs its own data/command pin or chip select. Panels sharing one SPI bus are
+//! usually connected through a bus wrapper implementing `spi::Write` for each panel, with the
+//! chip select set with `set_cs_pin`, so that all drivers have the same type. Set the
+//! orientation of the panels before combining them, as it changes their size.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! // Two 160x128 panels side by side form a 320x128 surface.
+//! let mut wall = TiledDisplay::horizontal([left, right]);
+//! wall.fill_screen(&Color::from_default(DefaultColor::Black))?;
+//! wall.draw_filled_rect(150, 40, 170, 80, &Color::from_default(DefaultColor::Red))?;
+//! ```
+
+use crate::color::{Color, DefaultColor};
+use crate::controller::Controller;
+use crate::error::DisplayError;
+use crate::geometry::Rect;
+use crate::image::Image;
+use crate::ST7734;
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// `N` panels forming one logical drawing surface.
+pub struct TiledDisplay<SPI, PIN, DELAY, C, const N: usize> {
+    /// Drivers of the panels.
+    panels: [ST7734<SPI, PIN, DELAY, C>; N],
+
+    /// Logical coordinates of the top left corner of each panel.
+    origins: [(u16, u16); N],
+}
+
+impl<SPI, PIN, DELAY, C, const N: usize> TiledDisplay<SPI, PIN, DELAY, C, N>
+where
+    SPI: spi::Write<u8>,
+    PIN: OutputPin,
+    DELAY: DelayMs<u64>,
+    C: Controller,
+{
+    /// Combines panels with their top left corners at the given logical coordinates. Panels
+    /// should not overlap, overlapping parts are drawn on all of them.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Four 128x160 panels arranged in a 2x2 grid.
+    /// let wall = TiledDisplay::new([a, b, c, d], [(0, 0), (128, 0), (0, 160), (128, 160)]);
+    /// ```
+    pub fn new(
+        panels: [ST7734<SPI, PIN, DELAY, C>; N],
+        origins: [(u16, u16); N],
+    ) -> TiledDisplay<SPI, PIN, DELAY, C, N> {
+        TiledDisplay { panels, origins }
+    }
+
+    /// Places the panels next to each other from left to right, aligned at the top.
+    pub fn horizontal(panels: [ST7734<SPI, PIN, DELAY, C>; N]) -> TiledDisplay<SPI, PIN, DELAY, C, N> {
+        let mut origins = [(0, 0); N];
+        let mut x = 0;
+        for (origin, panel) in origins.iter_mut().zip(panels.iter()) {
+            *origin = (x, 0);
+            x += panel.width();
+        }
+        TiledDisplay::new(panels, origins)
+    }
+
+    /// Places the panels below each other from top to bottom, aligned at the left.
+    pub fn vertical(panels: [ST7734<SPI, PIN, DELAY, C>; N]) -> TiledDisplay<SPI, PIN, DELAY, C, N> {
+        let mut origins = [(0, 0); N];
+        let mut y = 0;
+        for (origin, panel) in origins.iter_mut().zip(panels.iter()) {
+            *origin = (0, y);
+            y += panel.height();
+        }
+        TiledDisplay::new(panels, origins)
+    }
+
+    /// Returns the driver of panel `index`, e.g. to change settings of a single panel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `N`.
+    pub fn panel(&mut self, index: usize) -> &mut ST7734<SPI, PIN, DELAY, C> {
+        &mut self.panels[index]
+    }
+
+    /// Returns the area covered by panel `index` in logical coordinates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `N`.
+    pub fn panel_area(&self, index: usize) -> Rect {
+        let (x, y) = self.origins[index];
+        let panel = &self.panels[index];
+        Rect::new(x, y, x + panel.width() - 1, y + panel.height() - 1)
+    }
+
+    /// Returns the drivers of the panels.
+    pub fn release(self) -> [ST7734<SPI, PIN, DELAY, C>; N] {
+        self.panels
+    }
+
+    /// Width of the smallest rectangle containing all panels, starting at `x = 0`.
+    pub fn width(&self) -> u16 {
+        self.bounds().x1 + 1
+    }
+
+    /// Height of the smallest rectangle containing all panels, starting at `y = 0`.
+    pub fn height(&self) -> u16 {
+        self.bounds().y1 + 1
+    }
+
+    /// Returns the logical surface from the origin to the bottom right corner of the panels.
+    pub fn bounds(&self) -> Rect {
+        (0..N).fold(Rect::new(0, 0, 0, 0), |bounds, index| bounds.union(&self.panel_area(index)))
+    }
+
+    /// Draws `area` with the color returned by `pixel` for each of its logical coordinates.
+    /// Each panel receives only the part of `area` it covers, as one address window.
+    pub fn fill_region<F>(&mut self, area: &Rect, mut pixel: F) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        F: FnMut(u16, u16) -> Color,
+    {
+        for index in 0..N {
+            if let Some(part) = area.intersection(&self.panel_area(index)) {
+                let (x, y) = self.origins[index];
+                let local = Rect::new(part.x0 - x, part.y0 - y, part.x1 - x, part.y1 - y);
+                self.panels[index].fill_region(&local, |px, py| pixel(px + x, py + y))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws a single pixel. Pixels not covered by a panel are ignored.
+    pub fn draw_pixel(&mut self, x: u16, y: u16, color: &Color) -> Result<(), DisplayError<SPI, PIN>> {
+        self.draw_filled_rect(x, y, x, y, color)
+    }
+
+    /// Draws a filled rectangle, which may span several panels.
+    pub fn draw_filled_rect(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        let area = Rect::new(x0, y0, x1, y1);
+        for index in 0..N {
+            if let Some(part) = area.intersection(&self.panel_area(index)) {
+                let (x, y) = self.origins[index];
+                let panel = &mut self.panels[index];
+                panel.draw_filled_rect(part.x0 - x, part.y0 - y, part.x1 - x, part.y1 - y, color)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws a rectangle with the specified `color` as border color.
+    pub fn draw_rect(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        self.draw_horizontal_line(x0, x1, y0, color)?;
+        self.draw_horizontal_line(x0, x1, y1, color)?;
+        self.draw_vertical_line(x0, y0, y1, color)?;
+        self.draw_vertical_line(x1, y0, y1, color)
+    }
+
+    /// Draws a horizontal line between the provided coordinates.
+    pub fn draw_horizontal_line(
+        &mut self,
+        x0: u16,
+        x1: u16,
+        y: u16,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        self.draw_filled_rect(x0, y, x1, y, color)
+    }
+
+    /// Draws a vertical line between the provided coordinates.
+    pub fn draw_vertical_line(
+        &mut self,
+        x: u16,
+        y0: u16,
+        y1: u16,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>> {
+        self.draw_filled_rect(x, y0, x, y1, color)
+    }
+
+    /// Draws an image with its top left corner at the provided logical coordinates.
+    pub fn draw_image(&mut self, x: u16, y: u16, image: &Image) -> Result<(), DisplayError<SPI, PIN>> {
+        if image.width() == 0 || image.height() == 0 {
+            return Ok(());
+        }
+        let area = Rect::new(x, y, x + image.width() - 1, y + image.height() - 1);
+        self.fill_region(&area, |px, py| image.pixel(px - x, py - y))
+    }
+
+    /// Fills all panels with `color`.
+    pub fn fill_screen(&mut self, color: &Color) -> Result<(), DisplayError<SPI, PIN>> {
+        for panel in self.panels.iter_mut() {
+            panel.fill_screen(color)?;
+        }
+        Ok(())
+    }
+
+    /// Fills all panels with black.
+    pub fn clear_screen(&mut self) -> Result<(), DisplayError<SPI, PIN>> {
+        self.fill_screen(&Color::from_default(DefaultColor::Black))
+    }
+}
@@ -0,0 +1,61 @@
+/// Delays used when resetting and initializing the display, in milliseconds.
+///
+/// The defaults leave generous margins. Panels with slow boost converters may need longer
+/// delays, while `Timing::minimum()` uses the shortest delays the datasheet allows.
+///
+/// # Examples
+///
+/// ```ignore
+/// display.set_timing(Timing {
+///     sleep_out: 500,
+///     ..Timing::default()
+/// });
+/// display.reinit();
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Timing {
+    /// Time the reset pin is held low.
+    pub reset_pulse: u64,
+
+    /// Time waited after releasing the reset pin.
+    pub reset_wait: u64,
+
+    /// Time waited after the software reset command.
+    pub software_reset: u64,
+
+    /// Time waited after the sleep out command.
+    pub sleep_out: u64,
+
+    /// Time waited after enabling the panel supply with the power pin.
+    pub power_up: u64,
+
+    /// Time waited after the sleep in command before the panel supply is cut.
+    pub power_down: u64,
+}
+
+impl Timing {
+    /// Shortest delays allowed by the datasheet.
+    pub const fn minimum() -> Timing {
+        Timing {
+            reset_pulse: 1,
+            reset_wait: 5,
+            software_reset: 120,
+            sleep_out: 5,
+            power_up: 5,
+            power_down: 5,
+        }
+    }
+}
+
+impl Default for Timing {
+    fn default() -> Timing {
+        Timing {
+            reset_pulse: 1,
+            reset_wait: 120,
+            software_reset: 120,
+            sleep_out: 200,
+            power_up: 20,
+            power_down: 120,
+        }
+    }
+}
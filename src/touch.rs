@@ -0,0 +1,225 @@
+//! Touch input in display coordinates.
+//!
+//! Touch controllers report raw readings along the axes of the panel. A
+//! [`Calibration`](struct.Calibration.html) maps them to pixels in portrait orientation, and
+//! [`touch_point`](fn.touch_point.html) rotates the result to match the current orientation of
+//! the display, so touch points line up with what is drawn.
+//!
+//! Controllers are integrated by implementing [`TouchController`](trait.TouchController.html).
+//! The XPT2046 found on many ST7735 modules is supported by [`Xpt2046`](struct.Xpt2046.html).
+//! It usually shares the SPI bus with the display, so its chip select has to be high while the
+//! display is written and vice versa.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let mut touch = Xpt2046::new(touch_spi, touch_cs)?;
+//! let calibration = Calibration::default();
+//!
+//! if let Ok(Some((x, y))) = touch_point(&mut touch, &calibration, &display) {
+//!     display.draw_pixel(x, y, &Color::from_default(DefaultColor::Red));
+//! }
+//! ```
+
+use crate::controller::Controller;
+use crate::error::Error;
+use crate::{Orientation, ST7734};
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Controller command reading the X position.
+const XPT2046_X: u8 = 0xD0;
+
+/// Controller command reading the Y position.
+const XPT2046_Y: u8 = 0x90;
+
+/// Controller command reading the first pressure measurement.
+const XPT2046_Z1: u8 = 0xB0;
+
+/// Controller command reading the second pressure measurement.
+const XPT2046_Z2: u8 = 0xC0;
+
+/// Touch controller reporting raw positions along the panel axes.
+pub trait TouchController {
+    type Error;
+
+    /// Returns the raw X and Y reading of the current touch, or `None` if the panel is not touched.
+    fn read_raw(&mut self) -> Result<Option<(u16, u16)>, Self::Error>;
+}
+
+/// XPT2046 resistive touch controller.
+pub struct Xpt2046<SPI, CS> {
+    /// SPI bus the controller is connected to.
+    spi: SPI,
+
+    /// Chip select pin, active low.
+    cs: CS,
+
+    /// Minimum pressure value of a touch.
+    threshold: u16,
+
+    /// Number of readings averaged for a position.
+    samples: u8,
+}
+
+impl<SPI, CS> Xpt2046<SPI, CS>
+where
+    SPI: spi::Transfer<u8>,
+    CS: OutputPin,
+{
+    /// Creates a driver for the controller selected by `cs`. The SPI clock must not exceed
+    /// 2 MHz. Fails if the chip select pin can't be set.
+    pub fn new(spi: SPI, mut cs: CS) -> Result<Xpt2046<SPI, CS>, CS::Error> {
+        cs.set_high()?;
+        Ok(Xpt2046 {
+            spi,
+            cs,
+            threshold: 400,
+            samples: 4,
+        })
+    }
+
+    /// Sets the minimum pressure value on the 12 bit scale of the controller that counts as a
+    /// touch. Raise it if light brushes cause stray touches.
+    pub fn set_threshold(&mut self, threshold: u16) {
+        self.threshold = threshold;
+    }
+
+    /// Sets the number of readings averaged for a position, at least one. Averaging reduces
+    /// jitter of resistive panels.
+    pub fn set_samples(&mut self, samples: u8) {
+        self.samples = samples.max(1);
+    }
+
+    /// Releases the SPI bus and chip select pin.
+    pub fn release(self) -> (SPI, CS) {
+        (self.spi, self.cs)
+    }
+
+    /// Runs a conversion and returns the 12 bit result.
+    fn read_channel(&mut self, command: u8) -> Result<u16, Error<SPI::Error, CS::Error>> {
+        let mut buffer = [command, 0, 0];
+        self.cs.set_low().map_err(Error::Pin)?;
+        let result = self
+            .spi
+            .transfer(&mut buffer)
+            .map(|data| (((data[1] as u16) << 8) | data[2] as u16) >> 3)
+            .map_err(Error::Spi);
+        self.cs.set_high().map_err(Error::Pin)?;
+        result
+    }
+}
+
+impl<SPI, CS> TouchController for Xpt2046<SPI, CS>
+where
+    SPI: spi::Transfer<u8>,
+    CS: OutputPin,
+{
+    type Error = Error<SPI::Error, CS::Error>;
+
+    fn read_raw(&mut self) -> Result<Option<(u16, u16)>, Self::Error> {
+        let z1 = self.read_channel(XPT2046_Z1)?;
+        let z2 = self.read_channel(XPT2046_Z2)?;
+        if z1 + 4095 - z2 < self.threshold {
+            return Ok(None);
+        }
+
+        let (mut x, mut y) = (0u32, 0u32);
+        for _ in 0..self.samples {
+            x += self.read_channel(XPT2046_X)? as u32;
+            y += self.read_channel(XPT2046_Y)? as u32;
+        }
+        Ok(Some(((x / self.samples as u32) as u16, (y / self.samples as u32) as u16)))
+    }
+}
+
+/// Raw readings at the edges of the panel in portrait orientation.
+///
+/// Readings at the left edge are `x_min` and at the right edge `x_max`; if the axis of the
+/// controller runs the other way, `x_min` is larger than `x_max`. The same applies to `y`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Calibration {
+    /// Reading at the left edge.
+    pub x_min: u16,
+
+    /// Reading at the right edge.
+    pub x_max: u16,
+
+    /// Reading at the top edge.
+    pub y_min: u16,
+
+    /// Reading at the bottom edge.
+    pub y_max: u16,
+
+    /// Whether the X reading of the controller runs along the vertical panel axis.
+    pub swap_axes: bool,
+}
+
+impl Calibration {
+    /// Maps a raw reading to pixel coordinates of a display of `width` x `height` pixels in
+    /// portrait orientation. Readings beyond the calibrated edges are clamped.
+    pub fn to_portrait(&self, raw: (u16, u16), width: u16, height: u16) -> (u16, u16) {
+        let (raw_x, raw_y) = if self.swap_axes { (raw.1, raw.0) } else { raw };
+        let scale = |value: u16, min: u16, max: u16, size: u16| -> u16 {
+            let (value, min, max) = (value as i32, min as i32, max as i32);
+            let span = if max == min { 1 } else { max - min };
+            let position = (value - min) * (size as i32 - 1) / span;
+            position.max(0).min(size as i32 - 1) as u16
+        };
+        (
+            scale(raw_x, self.x_min, self.x_max, width),
+            scale(raw_y, self.y_min, self.y_max, height),
+        )
+    }
+
+    /// Maps a raw reading to display coordinates in `orientation`. `width` and `height` are the
+    /// size of the panel in portrait orientation.
+    pub fn to_display(&self, raw: (u16, u16), orientation: Orientation, width: u16, height: u16) -> (u16, u16) {
+        let (x, y) = self.to_portrait(raw, width, height);
+        match orientation {
+            Orientation::Portrait => (x, y),
+            Orientation::Landscape => (height - 1 - y, x),
+            Orientation::PortraitSwapped => (width - 1 - x, height - 1 - y),
+            Orientation::LandScapeSwapped => (y, width - 1 - x),
+        }
+    }
+}
+
+impl Default for Calibration {
+    /// Typical readings of an XPT2046 on a 1.8" panel.
+    fn default() -> Calibration {
+        Calibration {
+            x_min: 200,
+            x_max: 3900,
+            y_min: 200,
+            y_max: 3900,
+            swap_axes: false,
+        }
+    }
+}
+
+/// Reads the current touch of `touch` in coordinates of `display`, taking its orientation into
+/// account. Returns `None` if the panel is not touched.
+pub fn touch_point<T, SPI, PIN, DELAY, C>(
+    touch: &mut T,
+    calibration: &Calibration,
+    display: &ST7734<SPI, PIN, DELAY, C>,
+) -> Result<Option<(u16, u16)>, T::Error>
+where
+    T: TouchController,
+    SPI: spi::Write<u8>,
+    PIN: OutputPin,
+    DELAY: DelayMs<u64>,
+    C: Controller,
+{
+    let orientation = display.orientation();
+    let (width, height) = match orientation {
+        Orientation::Portrait | Orientation::PortraitSwapped => (display.width(), display.height()),
+        Orientation::Landscape | Orientation::LandScapeSwapped => (display.height(), display.width()),
+    };
+    Ok(touch
+        .read_raw()?
+        .map(|raw| calibration.to_display(raw, orientation, width, height)))
+}
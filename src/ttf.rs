@@ -0,0 +1,126 @@
+//! Anti-aliased text in any size from TrueType and OpenType fonts.
+//!
+//! This module is available with the `ttf` feature. Glyphs are rasterized with
+//! [`fontdue`](https://docs.rs/fontdue) while drawing and blended into a `Framebuffer`, so text
+//! can be placed on top of images and gradients. Rasterizing takes far more time and memory
+//! than drawing bitmap fonts, which makes it a good fit for targets like the Raspberry Pi or an
+//! ESP32 with PSRAM.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! static ROBOTO: &[u8] = include_bytes!("Roboto-Regular.ttf");
+//!
+//! let font = TtfFont::from_bytes(ROBOTO).expect("invalid font");
+//! let mut frame = Framebuffer::new(160, 128, Color::from_default(DefaultColor::Black));
+//! frame.draw_text_ttf("21.5°C", 10, 30, &font, 32.0, &Color::from_default(DefaultColor::White));
+//! frame.flush(&mut display)?;
+//! ```
+
+use crate::color::Color;
+use crate::framebuffer::rgb::Framebuffer;
+use crate::framebuffer::storage::Storage;
+
+use fontdue::FontSettings;
+
+/// TrueType or OpenType font.
+pub struct TtfFont {
+    /// Parsed font.
+    font: fontdue::Font,
+}
+
+impl TtfFont {
+    /// Parses a TrueType or OpenType font. Returns the message of the parser if the data is not
+    /// a valid font.
+    pub fn from_bytes(data: &[u8]) -> Result<TtfFont, &'static str> {
+        let font = fontdue::Font::from_bytes(data, FontSettings::default())?;
+        Ok(TtfFont { font })
+    }
+
+    /// Returns the parsed font, e.g. to look up glyph metrics.
+    pub fn font(&self) -> &fontdue::Font {
+        &self.font
+    }
+
+    /// Returns the height of the part of a line above the baseline for text of `size` pixels.
+    pub fn ascent(&self, size: f32) -> u16 {
+        match self.font.horizontal_line_metrics(size) {
+            Some(metrics) => metrics.ascent.ceil() as u16,
+            None => size.ceil() as u16,
+        }
+    }
+
+    /// Returns the distance between the baselines of consecutive lines for text of `size`
+    /// pixels.
+    pub fn line_height(&self, size: f32) -> u16 {
+        match self.font.horizontal_line_metrics(size) {
+            Some(metrics) => metrics.new_line_size.ceil() as u16,
+            None => size.ceil() as u16,
+        }
+    }
+
+    /// Returns the width in pixels of the longest line of `text` in `size` pixels, including
+    /// kerning.
+    pub fn text_width(&self, text: &str, size: f32) -> u16 {
+        let mut width: f32 = 0.0;
+        for line in text.split('\n') {
+            let mut line_width = 0.0;
+            let mut previous = None;
+            for c in line.chars() {
+                line_width += self.advance(previous, c, size);
+                previous = Some(c);
+            }
+            width = width.max(line_width);
+        }
+        width.ceil() as u16
+    }
+
+    /// Returns the advance of `c` in `size` pixels, including the kerning after `previous`.
+    fn advance(&self, previous: Option<char>, c: char, size: f32) -> f32 {
+        let kerning = match previous {
+            Some(previous) => self.font.horizontal_kern(previous, c, size).unwrap_or(0.0),
+            None => 0.0,
+        };
+        kerning + self.font.metrics(c, size).advance_width
+    }
+}
+
+impl<S: Storage<Color>> Framebuffer<S> {
+    /// Draws anti-aliased text in `size` pixels with the top left corner of the first line at
+    /// the provided coordinates. Glyph edges are blended with the pixels already in the
+    /// framebuffer. A `\n` moves to the start of the next line and the text is clipped to the
+    /// framebuffer bounds.
+    pub fn draw_text_ttf(&mut self, text: &str, x: u16, y: u16, font: &TtfFont, size: f32, color: &Color) {
+        let mut baseline = y as i32 + font.ascent(size) as i32;
+        let mut pen = x as f32;
+        let mut previous = None;
+        for c in text.chars() {
+            if c == '\n' {
+                baseline += font.line_height(size) as i32;
+                pen = x as f32;
+                previous = None;
+                continue;
+            }
+            if let Some(previous) = previous {
+                pen += font.font.horizontal_kern(previous, c, size).unwrap_or(0.0);
+            }
+            previous = Some(c);
+
+            let (metrics, coverage) = font.font.rasterize(c, size);
+            let left = pen.round() as i32 + metrics.xmin;
+            let top = baseline - metrics.ymin - metrics.height as i32;
+            for row in 0..metrics.height {
+                for column in 0..metrics.width {
+                    let alpha = coverage[row * metrics.width + column];
+                    let (px, py) = (left + column as i32, top + row as i32);
+                    let inside = px >= 0 && py >= 0 && px < self.width() as i32 && py < self.height() as i32;
+                    if alpha > 0 && inside {
+                        let blended = self.pixel(px as u16, py as u16).blend(color, alpha);
+                        self.set_pixel(px as u16, py as u16, &blended);
+                    }
+                }
+            }
+            pen += metrics.advance_width;
+        }
+    }
+}
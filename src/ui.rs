@@ -0,0 +1,285 @@
+//! Immediate-mode user interface drawn into a framebuffer.
+//!
+//! The interface is described anew for every frame by calling widget methods in the order they
+//! appear top to bottom. Widgets don't keep state of their own, so the UI always shows the
+//! current values of the application. Only the focus and pending activation persist between
+//! frames in a `UiState`, which is driven by the buttons or encoder of the device through
+//! `UiState::handle`.
+//!
+//! Every frame is drawn completely into a `Framebuffer`, and `Framebuffer::flush_changed`
+//! transfers only the tiles that differ from the previous frame, so redrawing everything is
+//! cheap on the bus.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let mut frame = Framebuffer::new(128, 160, Color::from_default(DefaultColor::Black));
+//! let mut state = UiState::new(Theme::default());
+//!
+//! loop {
+//!     for event in [down.poll()?, select.poll()?].iter().flatten() {
+//!         state.handle(*event);
+//!     }
+//!
+//!     let mut ui = state.begin(&mut frame);
+//!     ui.label("Volume");
+//!     ui.bar(volume, 100);
+//!     if ui.button("Louder") {
+//!         volume = (volume + 10).min(100);
+//!     }
+//!     if ui.button("Mute") {
+//!         volume = 0;
+//!     }
+//!     drop(ui);
+//!
+//!     frame.flush_changed(&mut display)?;
+//! }
+//! ```
+
+use crate::color::{Color, DefaultColor};
+use crate::fonts::font57::Font57;
+use crate::fonts::{self, LINE_ADVANCE};
+use crate::framebuffer::rgb::Framebuffer;
+use crate::framebuffer::storage::Storage;
+use crate::input::InputEvent;
+
+use alloc::vec::Vec;
+
+/// Space between the widgets and the edges of the framebuffer.
+const PADDING: u16 = 2;
+
+/// Vertical space between two widgets.
+const SPACING: u16 = 3;
+
+/// Space between the border of a button and its label.
+const BUTTON_PADDING: u16 = 2;
+
+/// Height of a bar.
+const BAR_HEIGHT: u16 = 8;
+
+/// Colors of the user interface.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Theme {
+    /// Color of text and borders.
+    pub foreground: Color,
+
+    /// Color filling the framebuffer.
+    pub background: Color,
+
+    /// Color of the focused button and the filled part of bars.
+    pub accent: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme {
+            foreground: Color::from_default(DefaultColor::White),
+            background: Color::from_default(DefaultColor::Black),
+            accent: Color::from_default(DefaultColor::Teal),
+        }
+    }
+}
+
+/// State of the user interface that persists between frames.
+pub struct UiState {
+    /// Colors used to draw the widgets.
+    theme: Theme,
+
+    /// Index of the focused widget among the focusable widgets.
+    focus: usize,
+
+    /// Number of focusable widgets of the previous frame.
+    focusable: usize,
+
+    /// Whether the focused widget is activated in the next frame.
+    activated: bool,
+
+    /// Whether going back was requested for the next frame.
+    back: bool,
+}
+
+impl UiState {
+    /// Creates the state with the first focusable widget focused.
+    pub fn new(theme: Theme) -> UiState {
+        UiState {
+            theme,
+            focus: 0,
+            focusable: 0,
+            activated: false,
+            back: false,
+        }
+    }
+
+    /// Returns the colors used to draw the widgets.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Changes the colors used to draw the widgets.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// Returns the index of the focused widget among the focusable widgets.
+    pub fn focus(&self) -> usize {
+        self.focus
+    }
+
+    /// Moves the focus to the next focusable widget, wrapping around after the last one.
+    pub fn focus_next(&mut self) {
+        self.focus = (self.focus + 1) % self.focusable.max(1);
+    }
+
+    /// Moves the focus to the previous focusable widget, wrapping around before the first one.
+    pub fn focus_previous(&mut self) {
+        let count = self.focusable.max(1);
+        self.focus = (self.focus + count - 1) % count;
+    }
+
+    /// Activates the focused widget, e.g. presses the focused button, in the next frame.
+    pub fn activate(&mut self) {
+        self.activated = true;
+    }
+
+    /// Handles a navigation event: `Up` and `Down` move the focus, `Select` activates the focused
+    /// widget, `Back` is reported by `Ui::back` in the next frame and encoder detents move the
+    /// focus forward or backward.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if let Some(event) = knob.poll()? {
+    ///     state.handle(event);
+    /// }
+    /// ```
+    pub fn handle(&mut self, event: InputEvent) {
+        match event {
+            InputEvent::Up => self.focus_previous(),
+            InputEvent::Down => self.focus_next(),
+            InputEvent::Select => self.activate(),
+            InputEvent::Back => self.back = true,
+            InputEvent::Encoder(detents) => {
+                for _ in 0..detents.unsigned_abs() {
+                    if detents > 0 {
+                        self.focus_next();
+                    } else {
+                        self.focus_previous();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Starts a frame by filling `frame` with the background color. Widgets are added with the
+    /// methods of the returned `Ui`, the frame ends when it is dropped.
+    pub fn begin<'a, S: Storage<Color>>(&'a mut self, frame: &'a mut Framebuffer<S>) -> Ui<'a, S> {
+        frame.clear(&self.theme.background);
+        Ui {
+            state: self,
+            frame,
+            y: PADDING,
+            focusable: 0,
+        }
+    }
+}
+
+/// Frame of the user interface, laying out widgets from top to bottom.
+pub struct Ui<'a, S: Storage<Color> = Vec<Color>> {
+    /// State persisting between frames.
+    state: &'a mut UiState,
+
+    /// Framebuffer the widgets are drawn into.
+    frame: &'a mut Framebuffer<S>,
+
+    /// Top edge of the next widget.
+    y: u16,
+
+    /// Number of focusable widgets added so far.
+    focusable: usize,
+}
+
+impl<'a, S: Storage<Color>> Ui<'a, S> {
+    /// Adds a line of text. A `\n` starts another line.
+    pub fn label(&mut self, text: &str) {
+        let color = self.state.theme.foreground;
+        self.frame.draw_text::<Font57>(text, PADDING, self.y, &color);
+        let lines = text.split('\n').count() as u16;
+        self.advance(lines * LINE_ADVANCE);
+    }
+
+    /// Adds a button spanning the width of the framebuffer. Returns `true` if the button is
+    /// focused and was activated.
+    pub fn button(&mut self, text: &str) -> bool {
+        let theme = self.state.theme;
+        let focused = self.focusable == self.state.focus;
+        self.focusable += 1;
+
+        let (x0, y0) = (PADDING, self.y);
+        let x1 = self.frame.width().saturating_sub(PADDING + 1);
+        let y1 = y0 + LINE_ADVANCE + 2 * BUTTON_PADDING;
+        let text_x = x0 + (x1 - x0 + 1).saturating_sub(fonts::text_width(text, Font57 {}, 1)) / 2;
+        let text_y = y0 + BUTTON_PADDING + 1;
+
+        if focused {
+            self.frame.fill_rect(x0, y0, x1, y1, &theme.accent);
+            self.frame.draw_text::<Font57>(text, text_x, text_y, &theme.background);
+        } else {
+            self.outline(x0, y0, x1, y1, &theme.foreground);
+            self.frame.draw_text::<Font57>(text, text_x, text_y, &theme.foreground);
+        }
+        self.advance(y1 - y0 + 1);
+
+        focused && self.state.activated
+    }
+
+    /// Adds a horizontal bar spanning the width of the framebuffer, filled to `value / max`.
+    pub fn bar(&mut self, value: u32, max: u32) {
+        let theme = self.state.theme;
+        let (x0, y0) = (PADDING, self.y);
+        let x1 = self.frame.width().saturating_sub(PADDING + 1);
+        let y1 = y0 + BAR_HEIGHT - 1;
+        self.outline(x0, y0, x1, y1, &theme.foreground);
+
+        let inner = (x1 - x0).saturating_sub(1) as u32;
+        let filled = (inner * value.min(max)).checked_div(max).unwrap_or(0);
+        if filled > 0 {
+            self.frame.fill_rect(x0 + 1, y0 + 1, x0 + filled as u16, y1 - 1, &theme.accent);
+        }
+        self.advance(BAR_HEIGHT);
+    }
+
+    /// Returns `true` if going back was requested with `InputEvent::Back`, e.g. to return to
+    /// the previous screen.
+    pub fn back(&self) -> bool {
+        self.state.back
+    }
+
+    /// Adds empty space of the given height.
+    pub fn space(&mut self, height: u16) {
+        self.y = self.y.saturating_add(height);
+    }
+
+    /// Moves below a widget of the given height.
+    fn advance(&mut self, height: u16) {
+        self.y = self.y.saturating_add(height + SPACING);
+    }
+
+    /// Draws the border of a rectangle.
+    fn outline(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, color: &Color) {
+        self.frame.fill_rect(x0, y0, x1, y0, color);
+        self.frame.fill_rect(x0, y1, x1, y1, color);
+        self.frame.fill_rect(x0, y0, x0, y1, color);
+        self.frame.fill_rect(x1, y0, x1, y1, color);
+    }
+}
+
+impl<'a, S: Storage<Color>> Drop for Ui<'a, S> {
+    fn drop(&mut self) {
+        self.state.focusable = self.focusable;
+        if self.state.focus >= self.focusable {
+            self.state.focus = 0;
+        }
+        self.state.activated = false;
+        self.state.back = false;
+    }
+}
@@ -0,0 +1,87 @@
+//! Periodic health checks for long-running devices.
+//!
+//! Panels occasionally lock up or lose their configuration due to ESD or electrical noise, which
+//! shows up as a frozen, white or garbled screen. The [`Watchdog`](struct.Watchdog.html) runs an
+//! application-provided health check in a fixed interval and re-initializes the display if the
+//! check fails. Like the screen saver, it does not own a clock; the application passes the current
+//! time in milliseconds to every call.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let mut watchdog = Watchdog::new(10_000);
+//!
+//! loop {
+//!     if watchdog.poll(&mut display, now(), |_| panel_responds())? {
+//!         redraw(&mut display);
+//!     }
+//! }
+//! ```
+
+use crate::controller::Controller;
+use crate::error::DisplayError;
+use crate::ST7734;
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Re-initializes the display when a periodic health check fails.
+pub struct Watchdog {
+    /// Interval between two health checks in milliseconds.
+    interval: u64,
+
+    /// Time of the last health check.
+    last_check: u64,
+
+    /// Number of times the display was re-initialized.
+    recoveries: u32,
+}
+
+impl Watchdog {
+    /// Creates a watchdog that checks the display every `interval` milliseconds.
+    pub fn new(interval: u64) -> Watchdog {
+        Watchdog {
+            interval,
+            last_check: 0,
+            recoveries: 0,
+        }
+    }
+
+    /// Runs `healthy` if the check interval elapsed and re-initializes the display if it returns
+    /// `false`. Returns `true` if the display was re-initialized and has to be redrawn.
+    ///
+    /// Passing `|_| false` re-initializes the display in every interval, which is a simple way of
+    /// recovering from lockups that can't be detected.
+    pub fn poll<SPI, PIN, DELAY, C, F>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        now: u64,
+        healthy: F,
+    ) -> Result<bool, DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+        F: FnOnce(&mut ST7734<SPI, PIN, DELAY, C>) -> bool,
+    {
+        if now.saturating_sub(self.last_check) < self.interval {
+            return Ok(false);
+        }
+
+        self.last_check = now;
+        if healthy(display) {
+            return Ok(false);
+        }
+
+        display.reinit()?;
+        self.recoveries += 1;
+        Ok(true)
+    }
+
+    /// Number of times the display was re-initialized.
+    pub fn recoveries(&self) -> u32 {
+        self.recoveries
+    }
+}
@@ -0,0 +1,221 @@
+use crate::color::{Color, DefaultColor};
+use crate::controller::Controller;
+use crate::error::DisplayError;
+use crate::geometry::Rect;
+use crate::ST7734;
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Outline of the charging bolt in a 16x16 grid.
+const BOLT: [(i32, i32); 6] = [(10, 0), (3, 9), (8, 9), (6, 16), (13, 7), (8, 7)];
+
+/// Battery icon showing the charge level.
+///
+/// The fill color changes when the level drops to the low and critical thresholds, and a bolt
+/// is drawn over the fill while charging. Updates only redraw the part of the fill that changed.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut battery = Battery::new(Rect::new(100, 2, 123, 11));
+/// battery.draw(&mut display);
+///
+/// loop {
+///     battery.update(&mut display, fuel_gauge.percent(), charger.is_charging());
+/// }
+/// ```
+pub struct Battery {
+    /// Area covered by the icon including the terminal.
+    area: Rect,
+
+    /// Charge level in percent.
+    level: u8,
+
+    /// Whether the bolt is shown.
+    charging: bool,
+
+    /// Level at or below which the fill uses the low color.
+    low: u8,
+
+    /// Level at or below which the fill uses the critical color.
+    critical: u8,
+
+    /// Color of the outline and terminal.
+    outline: Color,
+
+    /// Fill color above the low threshold.
+    fill: Color,
+
+    /// Fill color at or below the low threshold.
+    low_fill: Color,
+
+    /// Fill color at or below the critical threshold.
+    critical_fill: Color,
+
+    /// Color of the charging bolt.
+    bolt: Color,
+
+    /// Color of the empty part.
+    background: Color,
+}
+
+impl Battery {
+    /// Creates a battery icon covering `area`, which should be at least 8x6 pixels. The terminal
+    /// takes up the rightmost two columns.
+    pub fn new(area: Rect) -> Battery {
+        Battery {
+            area,
+            level: 0,
+            charging: false,
+            low: 20,
+            critical: 10,
+            outline: Color::from_default(DefaultColor::White),
+            fill: Color::from_default(DefaultColor::Lime),
+            low_fill: Color::from_default(DefaultColor::Yellow),
+            critical_fill: Color::from_default(DefaultColor::Red),
+            bolt: Color::from_default(DefaultColor::White),
+            background: Color::from_default(DefaultColor::Black),
+        }
+    }
+
+    /// Sets the levels in percent at or below which the fill turns to the low and critical colors.
+    pub fn set_thresholds(&mut self, low: u8, critical: u8) {
+        self.low = low;
+        self.critical = critical;
+    }
+
+    /// Sets the fill colors above the low threshold, at or below it and at or below the critical
+    /// threshold.
+    pub fn set_fill_colors(&mut self, fill: Color, low: Color, critical: Color) {
+        self.fill = fill;
+        self.low_fill = low;
+        self.critical_fill = critical;
+    }
+
+    /// Sets the colors of the outline, charging bolt and empty part.
+    pub fn set_colors(&mut self, outline: Color, bolt: Color, background: Color) {
+        self.outline = outline;
+        self.bolt = bolt;
+        self.background = background;
+    }
+
+    /// Returns the charge level in percent.
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Draws the complete icon.
+    pub fn draw<SPI, PIN, DELAY, C>(
+        &self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let body = self.body();
+        display.draw_rect(body.x0, body.y0, body.x1, body.y1, &self.outline)?;
+        display.draw_rect(body.x0 + 1, body.y0 + 1, body.x1 - 1, body.y1 - 1, &self.background)?;
+
+        let third = self.area.height() / 3;
+        display.draw_filled_rect(body.x1 + 1, self.area.y0 + third, self.area.x1, self.area.y1 - third, &self.outline)?;
+
+        let inner = self.inner();
+        display.fill_region(&inner, |x, y| self.inner_color(x, y))
+    }
+
+    /// Changes the charge level and charging state and redraws what changed.
+    pub fn update<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        level: u8,
+        charging: bool,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let level = level.min(100);
+        let previous_width = self.fill_width();
+        let previous_color = self.fill_color();
+        let previous_charging = self.charging;
+        self.level = level;
+        self.charging = charging;
+
+        let inner = self.inner();
+        let width = self.fill_width();
+        if previous_charging != charging || previous_color != self.fill_color() {
+            display.fill_region(&inner, |x, y| self.inner_color(x, y))?;
+        } else if previous_width != width {
+            let x0 = inner.x0 + previous_width.min(width);
+            let x1 = inner.x0 + previous_width.max(width) - 1;
+            display.fill_region(&Rect::new(x0, inner.y0, x1, inner.y1), |x, y| self.inner_color(x, y))?;
+        }
+        Ok(())
+    }
+
+    /// Area of the battery body without the terminal.
+    fn body(&self) -> Rect {
+        Rect::new(self.area.x0, self.area.y0, self.area.x1 - 2, self.area.y1)
+    }
+
+    /// Area inside the outline that is filled according to the level.
+    fn inner(&self) -> Rect {
+        let body = self.body();
+        Rect::new(body.x0 + 2, body.y0 + 2, body.x1 - 2, body.y1 - 2)
+    }
+
+    /// Width of the filled part of the inner area.
+    fn fill_width(&self) -> u16 {
+        (self.inner().width() as u32 * self.level as u32 / 100) as u16
+    }
+
+    /// Fill color for the current level.
+    fn fill_color(&self) -> Color {
+        if self.level <= self.critical {
+            self.critical_fill
+        } else if self.level <= self.low {
+            self.low_fill
+        } else {
+            self.fill
+        }
+    }
+
+    /// Returns the color of a pixel in the inner area.
+    fn inner_color(&self, x: u16, y: u16) -> Color {
+        let inner = self.inner();
+        if self.charging && self.is_bolt(x - inner.x0, y - inner.y0, inner.width(), inner.height()) {
+            self.bolt
+        } else if x - inner.x0 < self.fill_width() {
+            self.fill_color()
+        } else {
+            self.background
+        }
+    }
+
+    /// Returns `true` if the pixel lies within the bolt scaled to the inner area, using the
+    /// even-odd rule on the pixel center.
+    fn is_bolt(&self, x: u16, y: u16, width: u16, height: u16) -> bool {
+        // Pixel center in the 16x16 grid of the bolt, in 1/2 units.
+        let px = (2 * x as i32 + 1) * 16;
+        let py = (2 * y as i32 + 1) * 16;
+        let (w, h) = (2 * width as i32, 2 * height as i32);
+
+        let mut inside = false;
+        for i in 0..BOLT.len() {
+            let (x0, y0) = BOLT[i];
+            let (x1, y1) = BOLT[(i + 1) % BOLT.len()];
+            let (x0, y0, x1, y1) = (x0 * w, y0 * h, x1 * w, y1 * h);
+            if (y0 > py) != (y1 > py) && px < x0 + (x1 - x0) * (py - y0) / (y1 - y0) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+}
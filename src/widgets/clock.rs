@@ -0,0 +1,186 @@
+use crate::color::{Color, DefaultColor};
+use crate::controller::Controller;
+use crate::error::DisplayError;
+use crate::ST7734;
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Sine of multiples of 6° from 0° to 90°, scaled by 1024.
+const SINE: [i32; 16] = [0, 107, 213, 316, 416, 512, 602, 685, 761, 828, 887, 935, 974, 1002, 1018, 1024];
+
+/// Returns the sine and cosine of `position` sixtieths of a full turn, scaled by 1024.
+fn sin_cos(position: u16) -> (i32, i32) {
+    let sin = |position: u16| -> i32 {
+        let position = position % 60;
+        match position / 15 {
+            0 => SINE[position as usize],
+            1 => SINE[(30 - position) as usize],
+            2 => -SINE[(position - 30) as usize],
+            _ => -SINE[(60 - position) as usize],
+        }
+    };
+    (sin(position), sin(position + 15))
+}
+
+/// Analog clock face with hour, minute and second hands.
+///
+/// Hands are updated by erasing only the ones that moved and redrawing the hands on top, so the
+/// face does not flicker. Angles are taken from a table of the 60 minute positions, the hour
+/// hand moves in steps of 12 minutes.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut clock = AnalogClock::new(64, 80, 50);
+/// clock.draw_face(&mut display);
+///
+/// loop {
+///     let (hours, minutes, seconds) = rtc.time();
+///     clock.update(&mut display, hours, minutes, seconds);
+///     delay.delay_ms(100);
+/// }
+/// ```
+pub struct AnalogClock {
+    /// Center of the face.
+    center: (u16, u16),
+
+    /// Radius of the face.
+    radius: u16,
+
+    /// Color of the rim and ticks.
+    face: Color,
+
+    /// Colors of the hour, minute and second hands.
+    hands: [Color; 3],
+
+    /// Background color of the face.
+    background: Color,
+
+    /// Positions of the hour, minute and second hand in sixtieths of a turn, once drawn.
+    positions: Option<[u16; 3]>,
+}
+
+impl AnalogClock {
+    /// Creates a clock with a face of `radius` pixels around the provided coordinates.
+    pub fn new(x: u16, y: u16, radius: u16) -> AnalogClock {
+        AnalogClock {
+            center: (x, y),
+            radius,
+            face: Color::from_default(DefaultColor::White),
+            hands: [
+                Color::from_default(DefaultColor::White),
+                Color::from_default(DefaultColor::White),
+                Color::from_default(DefaultColor::Red),
+            ],
+            background: Color::from_default(DefaultColor::Black),
+            positions: None,
+        }
+    }
+
+    /// Sets the colors of the face, the hour, minute and second hands and the background.
+    pub fn set_colors(&mut self, face: Color, hour: Color, minute: Color, second: Color, background: Color) {
+        self.face = face;
+        self.hands = [hour, minute, second];
+        self.background = background;
+    }
+
+    /// Draws the face with its rim and hour ticks. The hands are drawn by the next `update`.
+    pub fn draw_face<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let (x, y) = self.center;
+        display.draw_filled_circle(x, y, self.radius, &self.background)?;
+        display.draw_circle(x, y, self.radius, &self.face)?;
+
+        for hour in 0..12 {
+            let length = if hour % 3 == 0 { self.radius / 5 } else { self.radius / 10 };
+            let (x0, y0) = self.point(hour * 5, self.radius - 2);
+            let (x1, y1) = self.point(hour * 5, self.radius.saturating_sub(2 + length));
+            display.draw_line_signed(x0, y0, x1, y1, &self.face)?;
+        }
+        self.positions = None;
+        Ok(())
+    }
+
+    /// Moves the hands to the given time.
+    pub fn update<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let minutes = minutes as u16 % 60;
+        let positions = [(hours as u16 % 12) * 5 + minutes / 12, minutes, seconds as u16 % 60];
+        if self.positions == Some(positions) {
+            return Ok(());
+        }
+
+        if let Some(previous) = self.positions {
+            for (hand, (&before, &after)) in previous.iter().zip(positions.iter()).enumerate() {
+                if before != after {
+                    self.draw_hand(display, hand, before, &self.background)?;
+                }
+            }
+        }
+
+        for (hand, (color, &position)) in self.hands.iter().zip(positions.iter()).enumerate() {
+            self.draw_hand(display, hand, position, color)?;
+        }
+        let (x, y) = self.center;
+        display.draw_filled_circle(x, y, 2, &self.face)?;
+        self.positions = Some(positions);
+        Ok(())
+    }
+
+    /// Draws a hand at a position in sixtieths of a turn.
+    fn draw_hand<SPI, PIN, DELAY, C>(
+        &self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        hand: usize,
+        position: u16,
+        color: &Color,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        // Hands stay clear of the ticks.
+        let length = match hand {
+            0 => self.radius / 2,
+            1 => self.radius * 3 / 5,
+            _ => self.radius.saturating_sub(3 + self.radius / 5),
+        };
+        let (x0, y0) = (self.center.0 as i32, self.center.1 as i32);
+        let (x1, y1) = self.point(position, length);
+        display.draw_line_signed(x0, y0, x1, y1, color)
+    }
+
+    /// Returns the point at `distance` from the center in the direction of `position` sixtieths
+    /// of a turn clockwise from 12 o'clock.
+    fn point(&self, position: u16, distance: u16) -> (i32, i32) {
+        let (sin, cos) = sin_cos(position);
+        let distance = distance as i32;
+        (
+            self.center.0 as i32 + (sin * distance + 512).div_euclid(1024),
+            self.center.1 as i32 - (cos * distance + 512).div_euclid(1024),
+        )
+    }
+}
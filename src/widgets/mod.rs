@@ -0,0 +1,12 @@
+//! Ready-made widgets for common instrument and status displays.
+//!
+//! Widgets own the state needed to update themselves incrementally and draw to a display passed
+//! to their methods, so several widgets can share one driver.
+
+#[cfg(feature = "graphics-primitives")]
+pub mod battery;
+#[cfg(feature = "graphics-primitives")]
+pub mod clock;
+pub mod oscilloscope;
+#[cfg(all(feature = "fonts", feature = "alloc"))]
+pub mod status_bar;
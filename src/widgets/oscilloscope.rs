@@ -0,0 +1,190 @@
+use crate::color::Color;
+use crate::controller::Controller;
+use crate::error::DisplayError;
+use crate::geometry::Rect;
+use crate::ST7734;
+
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Rolling waveform display.
+///
+/// Samples are drawn left to right, one column per sample, and wrap around at the right edge
+/// like the sweep of an oscilloscope. Each sample only redraws its own column and clears the
+/// following one as a gap marking the write position, so a full redraw is never needed and
+/// high sample rates can be shown over SPI.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut scope = Oscilloscope::new(Rect::new(0, 20, 127, 99), -512, 511, green, black);
+/// scope.set_grid(Some((Color::from_hex(0x2104), 16)));
+/// scope.set_trigger(Some((0, Color::from_default(DefaultColor::Red))));
+/// scope.draw_background(&mut display);
+///
+/// loop {
+///     scope.extend(&mut display, adc.read_samples());
+/// }
+/// ```
+pub struct Oscilloscope {
+    /// Area covered by the waveform.
+    area: Rect,
+
+    /// Sample value shown at the bottom edge.
+    min: i32,
+
+    /// Sample value shown at the top edge.
+    max: i32,
+
+    /// Waveform color.
+    color: Color,
+
+    /// Background color.
+    background: Color,
+
+    /// Grid color and spacing in pixels, if shown.
+    grid: Option<(Color, u16)>,
+
+    /// Trigger level and line color, if shown.
+    trigger: Option<(i32, Color)>,
+
+    /// Column of the next sample relative to the area.
+    column: u16,
+
+    /// Row of the previous sample, connected to the next one.
+    previous: Option<u16>,
+}
+
+impl Oscilloscope {
+    /// Creates a waveform display covering `area` that shows samples from `min` to `max`.
+    pub fn new(area: Rect, min: i32, max: i32, color: Color, background: Color) -> Oscilloscope {
+        Oscilloscope {
+            area,
+            min,
+            max: if max > min { max } else { min + 1 },
+            color,
+            background,
+            grid: None,
+            trigger: None,
+            column: 0,
+            previous: None,
+        }
+    }
+
+    /// Changes the vertical scale to show samples from `min` to `max`. Takes effect for new samples.
+    pub fn set_scale(&mut self, min: i32, max: i32) {
+        self.min = min;
+        self.max = if max > min { max } else { min + 1 };
+        self.previous = None;
+    }
+
+    /// Shows grid lines in the given color every `spacing` pixels, or hides them.
+    pub fn set_grid(&mut self, grid: Option<(Color, u16)>) {
+        self.grid = grid.filter(|&(_, spacing)| spacing > 0);
+    }
+
+    /// Shows a horizontal line at the given sample level, or hides it.
+    pub fn set_trigger(&mut self, trigger: Option<(i32, Color)>) {
+        self.trigger = trigger;
+    }
+
+    /// Clears the whole area and draws the grid and trigger line. The sweep restarts at the
+    /// left edge.
+    pub fn draw_background<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let area = self.area;
+        display.fill_region(&area, |x, y| self.background_at(x - area.x0, y))?;
+        self.column = 0;
+        self.previous = None;
+        Ok(())
+    }
+
+    /// Draws the next sample.
+    pub fn push<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        sample: i32,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let row = self.row(sample);
+        let (top, bottom) = match self.previous {
+            Some(previous) if self.column > 0 => (previous.min(row), previous.max(row)),
+            _ => (row, row),
+        };
+
+        let x = self.area.x0 + self.column;
+        let column = Rect::new(x, self.area.y0, x, self.area.y1);
+        let color = self.color;
+        display.fill_region(&column, |_, y| {
+            if y >= top && y <= bottom {
+                color
+            } else {
+                self.background_at(x - self.area.x0, y)
+            }
+        })?;
+
+        self.previous = Some(row);
+        self.column = (self.column + 1) % self.area.width();
+
+        // Gap in front of the write position.
+        let x = self.area.x0 + self.column;
+        let gap = Rect::new(x, self.area.y0, x, self.area.y1);
+        display.fill_region(&gap, |_, _| self.background)
+    }
+
+    /// Draws all samples of `samples`.
+    pub fn extend<SPI, PIN, DELAY, C, I>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+        samples: I,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+        I: IntoIterator<Item = i32>,
+    {
+        for sample in samples {
+            self.push(display, sample)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the display row of a sample value, clamped to the area.
+    fn row(&self, sample: i32) -> u16 {
+        let sample = sample.max(self.min).min(self.max) as i64;
+        let span = (self.max as i64) - (self.min as i64);
+        let offset = (sample - self.min as i64) * (self.area.height() as i64 - 1) / span;
+        self.area.y1 - offset as u16
+    }
+
+    /// Returns the background, grid or trigger color at a column of the area and display row.
+    fn background_at(&self, column: u16, y: u16) -> Color {
+        if let Some((level, color)) = self.trigger {
+            if y == self.row(level) {
+                return color;
+            }
+        }
+        if let Some((color, spacing)) = self.grid {
+            if column.is_multiple_of(spacing) || (y - self.area.y0).is_multiple_of(spacing) {
+                return color;
+            }
+        }
+        self.background
+    }
+}
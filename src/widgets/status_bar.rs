@@ -0,0 +1,186 @@
+use crate::color::Color;
+use crate::controller::Controller;
+use crate::error::DisplayError;
+use crate::fonts::icons::Icons;
+use crate::fonts::{CHARACTER_ADVANCE, LINE_ADVANCE};
+use crate::geometry::Rect;
+use crate::ST7734;
+
+use alloc::string::String;
+use core::fmt::Write;
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Space between the slots and the edges of the bar.
+const PADDING: u16 = 2;
+
+/// Position of content within a status bar.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Slot {
+    Left,
+    Center,
+    Right,
+}
+
+/// Content of a slot and the area it was last drawn to.
+#[derive(Default)]
+struct SlotState {
+    /// Text including icon characters.
+    text: String,
+
+    /// Area covered on the display, if anything was drawn.
+    drawn: Option<Rect>,
+
+    /// Whether the text changed since it was drawn.
+    dirty: bool,
+}
+
+/// Strip with left, center and right aligned slots for text and icons, usually at the top of
+/// the screen.
+///
+/// Slots are drawn with the `Icons` font, so icons and text can be mixed. Only slots whose
+/// content changed are redrawn by `render`, and space left by shorter content is cleared.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut bar = StatusBar::new(Rect::new(0, 0, 127, 11), white, navy);
+/// bar.draw(&mut display);
+///
+/// loop {
+///     bar.set_text(Slot::Left, &format!("{}", icons::WIFI));
+///     bar.set_text(Slot::Center, &format!("{:02}:{:02}", hours, minutes));
+///     bar.set_text(Slot::Right, &format!("{}%{}", level, icons::BATTERY_HALF));
+///     bar.render(&mut display);
+/// }
+/// ```
+pub struct StatusBar {
+    /// Area covered by the bar.
+    area: Rect,
+
+    /// Text color.
+    color: Color,
+
+    /// Background color.
+    background: Color,
+
+    /// Left, center and right slot.
+    slots: [SlotState; 3],
+}
+
+impl StatusBar {
+    /// Creates an empty status bar covering `area`, which should be at least 8 pixels high.
+    pub fn new(area: Rect, color: Color, background: Color) -> StatusBar {
+        StatusBar {
+            area,
+            color,
+            background,
+            slots: Default::default(),
+        }
+    }
+
+    /// Changes the content of `slot`. It is redrawn by the next `render` if it differs from
+    /// the current content.
+    pub fn set_text(&mut self, slot: Slot, text: &str) {
+        let state = &mut self.slots[slot as usize];
+        if state.text != text {
+            state.text.clear();
+            state.text.push_str(text);
+            state.dirty = true;
+        }
+    }
+
+    /// Returns the content of `slot`.
+    pub fn text(&self, slot: Slot) -> &str {
+        &self.slots[slot as usize].text
+    }
+
+    /// Draws the background and all slots.
+    pub fn draw<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        let area = self.area;
+        display.draw_filled_rect(area.x0, area.y0, area.x1, area.y1, &self.background)?;
+        for slot in self.slots.iter_mut() {
+            slot.drawn = None;
+            slot.dirty = true;
+        }
+        self.render(display)
+    }
+
+    /// Redraws the slots whose content changed.
+    pub fn render<SPI, PIN, DELAY, C>(
+        &mut self,
+        display: &mut ST7734<SPI, PIN, DELAY, C>,
+    ) -> Result<(), DisplayError<SPI, PIN>>
+    where
+        SPI: spi::Write<u8>,
+        PIN: OutputPin,
+        DELAY: DelayMs<u64>,
+        C: Controller,
+    {
+        for (index, slot) in [Slot::Left, Slot::Center, Slot::Right].iter().enumerate() {
+            if !self.slots[index].dirty {
+                continue;
+            }
+
+            let area = self.slot_area(*slot);
+            if let Some(ref text) = area {
+                let mut writer = display
+                    .text_at(text.x0, text.y0)
+                    .with_font(Icons {})
+                    .with_color(self.color)
+                    .with_background(self.background);
+                let _ = write!(writer, "{}", self.slots[index].text);
+                if let Some(error) = writer.take_error() {
+                    return Err(error);
+                }
+            }
+
+            if let Some(previous) = self.slots[index].drawn {
+                match area {
+                    Some(area) => {
+                        if previous.x0 < area.x0 {
+                            let x1 = previous.x1.min(area.x0 - 1);
+                            display.draw_filled_rect(previous.x0, previous.y0, x1, previous.y1, &self.background)?;
+                        }
+                        if previous.x1 > area.x1 {
+                            let x0 = previous.x0.max(area.x1 + 1);
+                            display.draw_filled_rect(x0, previous.y0, previous.x1, previous.y1, &self.background)?;
+                        }
+                    }
+                    None => display.draw_filled_rect(previous.x0, previous.y0, previous.x1, previous.y1, &self.background)?,
+                }
+            }
+
+            self.slots[index].drawn = area;
+            self.slots[index].dirty = false;
+        }
+        Ok(())
+    }
+
+    /// Returns the area covered by the content of `slot`, or `None` if it is empty.
+    fn slot_area(&self, slot: Slot) -> Option<Rect> {
+        let characters = self.slots[slot as usize].text.chars().count() as u16;
+        if characters == 0 {
+            return None;
+        }
+
+        let width = characters * CHARACTER_ADVANCE;
+        let y = self.area.y0 + self.area.height().saturating_sub(LINE_ADVANCE) / 2;
+        let x = match slot {
+            Slot::Left => self.area.x0 + PADDING,
+            Slot::Center => self.area.x0 + self.area.width().saturating_sub(width) / 2,
+            Slot::Right => (self.area.x1 + 1).saturating_sub(PADDING + width),
+        };
+        Some(Rect::new(x, y, x + width - 1, y + LINE_ADVANCE - 1))
+    }
+}